@@ -0,0 +1,272 @@
+//! 管理用API（/api/admin）のクライアント
+
+use crate::error::{AdminError, Result};
+use pc_inventory_protocol::admin::MAX_PC_LIST_LIMIT;
+use pc_inventory_protocol::{
+    ApiKeyInfo, ApiKeyListResponse, CreateApiKeyRequest, CreateApiKeyResponse, DeleteResponse, ErrorResponse,
+    PcDetailResponse, PcListQuery, PcListResponse, PcMergeRequest, PcMergeResponse, PcRecord,
+};
+use reqwest::{Client, Method, RequestBuilder, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+
+/// 管理用APIのクライアント
+///
+/// すべてのリクエストに `Authorization: Bearer <API キー>` を付けて送信します。
+pub struct AdminClient {
+    client: Client,
+    server_url: Url,
+    api_key: String,
+    /// list_all_pcs で一度に取得する件数
+    page_size: u32,
+}
+
+impl AdminClient {
+    /// クライアントを作成
+    ///
+    /// # 引数
+    /// * `server_url` - サーバーのURL（例: http://localhost:8080）
+    /// * `api_key` - API キー（pc-inventory-server --create-api-key で作成）
+    /// * `timeout` - リクエストのタイムアウト
+    pub fn new(server_url: &str, api_key: &str, timeout: Duration) -> Result<Self> {
+        let server_url = Url::parse(server_url)
+            .map_err(|e| AdminError::InvalidServerUrl(server_url.to_string(), e.to_string()))?;
+        if api_key.trim().is_empty() {
+            return Err(AdminError::MissingApiKey);
+        }
+        let client = Client::builder().timeout(timeout).build()?;
+        Ok(Self {
+            client,
+            server_url,
+            api_key: api_key.trim().to_string(),
+            page_size: MAX_PC_LIST_LIMIT,
+        })
+    }
+
+    /// PC情報の一覧・検索
+    pub async fn list_pcs(&self, query: &PcListQuery) -> Result<Vec<PcRecord>> {
+        let response: PcListResponse = self.send(self.request(Method::GET, "/api/admin/pcs").query(query)).await?;
+        Ok(response.items)
+    }
+
+    /// 条件に一致するすべてのPC情報（MAX_PC_LIST_LIMIT 件ずつ取得）
+    pub async fn list_all_pcs(&self, search: Option<String>) -> Result<Vec<PcRecord>> {
+        let mut query = PcListQuery { search, limit: Some(self.page_size), after_id: None };
+        let mut items = Vec::new();
+        loop {
+            let page = self.list_pcs(&query).await?;
+            let complete = page.len() < self.page_size as usize;
+            query.after_id = page.last().map(|pc| pc.id);
+            items.extend(page);
+            if complete {
+                return Ok(items);
+            }
+        }
+    }
+
+    /// PC情報と変更履歴
+    pub async fn get_pc(&self, id: i32) -> Result<PcDetailResponse> {
+        self.send(self.request(Method::GET, &format!("/api/admin/pcs/{}", id))).await
+    }
+
+    /// PC情報を削除
+    pub async fn delete_pc(&self, id: i32) -> Result<DeleteResponse> {
+        self.send(self.request(Method::DELETE, &format!("/api/admin/pcs/{}", id))).await
+    }
+
+    /// source_id のPC情報を id のPC情報に統合（統合元は削除される）
+    pub async fn merge_pcs(&self, id: i32, source_id: i32) -> Result<PcMergeResponse> {
+        let request = PcMergeRequest { source_id };
+        self.send(self.request(Method::POST, &format!("/api/admin/pcs/{}/merge", id)).json(&request)).await
+    }
+
+    /// API キーの一覧
+    pub async fn list_api_keys(&self) -> Result<Vec<ApiKeyInfo>> {
+        let response: ApiKeyListResponse = self.send(self.request(Method::GET, "/api/admin/api-keys")).await?;
+        Ok(response.items)
+    }
+
+    /// API キーを作成
+    pub async fn create_api_key(&self, name: &str) -> Result<CreateApiKeyResponse> {
+        let request = CreateApiKeyRequest { name: name.to_string() };
+        self.send(self.request(Method::POST, "/api/admin/api-keys").json(&request)).await
+    }
+
+    /// API キーを削除（以降の認証に使用できなくなる）
+    pub async fn delete_api_key(&self, id: i32) -> Result<DeleteResponse> {
+        self.send(self.request(Method::DELETE, &format!("/api/admin/api-keys/{}", id))).await
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.client
+            .request(method, endpoint_url(&self.server_url, path))
+            .bearer_auth(&self.api_key)
+    }
+
+    /// リクエストを送信し、成功時はレスポンスを、失敗時はサーバーのエラーメッセージを返す
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response.json().await?);
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        Err(error_from_response(status, &body))
+    }
+}
+
+/// サーバーのURLのパスを置き換えたURL（サーバーのURLに含まれるパス・クエリは使用しない）
+fn endpoint_url(server_url: &Url, path: &str) -> Url {
+    let mut url = server_url.clone();
+    url.set_path(path);
+    url.set_query(None);
+    url
+}
+
+/// エラーレスポンスからエラーを作成（ErrorResponse でない場合は本文をそのまま使用）
+fn error_from_response(status: StatusCode, body: &str) -> AdminError {
+    let message = serde_json::from_str::<ErrorResponse>(body)
+        .map(|e| e.message)
+        .unwrap_or_else(|_| body.trim().to_string());
+    match status {
+        StatusCode::UNAUTHORIZED => AdminError::Unauthorized(message),
+        StatusCode::NOT_FOUND => AdminError::NotFound(message),
+        _ => AdminError::Server {
+            status: status.as_u16(),
+            message,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::{Path, Query, State};
+    use axum::http::HeaderMap;
+    use axum::routing::{delete, get};
+    use axum::{Json, Router};
+    use chrono::Utc;
+    use std::sync::{Arc, Mutex};
+
+    /// 受信した一覧の要求（search, after_id）
+    type ListRequests = Arc<Mutex<Vec<(Option<String>, Option<i32>)>>>;
+
+    fn record(id: i32) -> PcRecord {
+        PcRecord {
+            id,
+            uuid: format!("uuid-{}", id),
+            mac_address: None,
+            network_type: None,
+            user_name: Some(if id % 2 == 0 { "sales" } else { "dev" }.to_string()),
+            ip_address: None,
+            ipv6_address: None,
+            os: None,
+            os_version: None,
+            model_name: None,
+            manufacturer: None,
+            total_memory_bytes: None,
+            logical_processors: None,
+            agent_version: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_seen_at: None,
+        }
+    }
+
+    /// 管理用APIと同じ規則（ID順、after_id より後を limit 件まで）で応答するサーバー
+    async fn start_mock_server(pcs: Vec<PcRecord>) -> (String, ListRequests) {
+        async fn list(
+            State((pcs, requests)): State<(Arc<Vec<PcRecord>>, ListRequests)>,
+            headers: HeaderMap,
+            Query(query): Query<PcListQuery>,
+        ) -> std::result::Result<Json<PcListResponse>, (StatusCode, Json<ErrorResponse>)> {
+            if headers.get("authorization").and_then(|v| v.to_str().ok()) != Some("Bearer test-key") {
+                return Err((StatusCode::UNAUTHORIZED, Json(ErrorResponse::new("Invalid API key"))));
+            }
+            requests.lock().unwrap().push((query.search.clone(), query.after_id));
+            let items = pcs
+                .iter()
+                .filter(|pc| pc.id > query.after_id.unwrap_or(0))
+                .filter(|pc| query.search.as_deref().is_none_or(|search| pc.user_name.as_deref() == Some(search)))
+                .take(query.effective_limit() as usize)
+                .cloned()
+                .collect();
+            Ok(Json(PcListResponse::new(items)))
+        }
+
+        async fn delete_pc(Path(id): Path<i32>) -> (StatusCode, Json<ErrorResponse>) {
+            (StatusCode::NOT_FOUND, Json(ErrorResponse::new(format!("PC not found: {}", id))))
+        }
+
+        let requests = ListRequests::default();
+        let app = Router::new()
+            .route("/api/admin/pcs", get(list))
+            .route("/api/admin/pcs/:id", delete(delete_pc))
+            .with_state((Arc::new(pcs), requests.clone()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (url, requests)
+    }
+
+    #[tokio::test]
+    async fn test_list_all_pcs_pages_through_results() {
+        let (url, requests) = start_mock_server((1..=7).map(record).collect()).await;
+        let mut client = AdminClient::new(&url, "test-key", Duration::from_secs(5)).unwrap();
+        client.page_size = 2;
+
+        // 2件ずつ、前回の最後のIDの続きから取得する
+        let ids: Vec<i32> = client.list_all_pcs(None).await.unwrap().iter().map(|pc| pc.id).collect();
+        assert_eq!(ids, (1..=7).collect::<Vec<_>>());
+        let after_ids: Vec<Option<i32>> = requests.lock().unwrap().iter().map(|(_, after_id)| *after_id).collect();
+        assert_eq!(after_ids, vec![None, Some(2), Some(4), Some(6)]);
+
+        // 検索条件はすべてのページで送信し、件数がちょうどページの倍数の場合は空のページで終了する
+        requests.lock().unwrap().clear();
+        let ids: Vec<i32> = client.list_all_pcs(Some("dev".to_string())).await.unwrap().iter().map(|pc| pc.id).collect();
+        assert_eq!(ids, vec![1, 3, 5, 7]);
+        let search = Some("dev".to_string());
+        assert_eq!(*requests.lock().unwrap(), vec![(search.clone(), None), (search.clone(), Some(3)), (search, Some(7))]);
+
+        // 実際のサーバーと同じく、1回で MAX_PC_LIST_LIMIT 件まで取得する
+        let client = AdminClient::new(&url, "test-key", Duration::from_secs(5)).unwrap();
+        assert_eq!(client.list_all_pcs(None).await.unwrap().len(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_errors_from_mock_server() {
+        let (url, _) = start_mock_server(Vec::new()).await;
+
+        let client = AdminClient::new(&url, "wrong-key", Duration::from_secs(5)).unwrap();
+        assert!(matches!(client.list_all_pcs(None).await, Err(AdminError::Unauthorized(_))));
+
+        let client = AdminClient::new(&url, "test-key", Duration::from_secs(5)).unwrap();
+        assert!(matches!(
+            client.delete_pc(99).await,
+            Err(AdminError::NotFound(message)) if message == "PC not found: 99"
+        ));
+    }
+
+    #[test]
+    fn test_endpoint_url() {
+        let server_url = Url::parse("https://inventory.example.com/old/path?x=1").unwrap();
+        assert_eq!(
+            endpoint_url(&server_url, "/api/admin/pcs/3").as_str(),
+            "https://inventory.example.com/api/admin/pcs/3"
+        );
+    }
+
+    #[test]
+    fn test_error_from_response() {
+        let body = serde_json::to_string(&ErrorResponse::new("Invalid API key")).unwrap();
+        assert!(matches!(
+            error_from_response(StatusCode::UNAUTHORIZED, &body),
+            AdminError::Unauthorized(message) if message == "Invalid API key"
+        ));
+        assert!(matches!(
+            error_from_response(StatusCode::BAD_GATEWAY, "upstream down\n"),
+            AdminError::Server { status: 502, message } if message == "upstream down"
+        ));
+    }
+}
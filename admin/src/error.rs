@@ -0,0 +1,36 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AdminError {
+    #[error("Invalid server URL '{0}': {1}")]
+    InvalidServerUrl(String, String),
+
+    #[error("API key is not set (use --api-key or PC_INVENTORY_API_KEY)")]
+    MissingApiKey,
+
+    #[error("Unauthorized: {0} (check --api-key or PC_INVENTORY_API_KEY)")]
+    Unauthorized(String),
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Server returned {status}: {message}")]
+    Server { status: u16, message: String },
+
+    #[error("Server unreachable: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Cancelled")]
+    Cancelled,
+}
+
+pub type Result<T> = std::result::Result<T, AdminError>;
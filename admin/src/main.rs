@@ -0,0 +1,262 @@
+//! PC情報の管理ツール
+//!
+//! サーバーの管理用API（/api/admin）を使用して、PC情報の一覧・検索・詳細表示・削除・統合・CSV出力と
+//! API キーの管理を行います。最初の API キーはサーバーで `pc-inventory-server --create-api-key <名前>` により作成します。
+//!
+//! ```text
+//! pc-inventory-admin list [--search <TEXT>] [--limit <N>]
+//! pc-inventory-admin search <TEXT>
+//! pc-inventory-admin show <ID>
+//! pc-inventory-admin delete <ID> [--yes]
+//! pc-inventory-admin merge <SOURCE_ID> --into <TARGET_ID> [--yes]
+//! pc-inventory-admin export [--search <TEXT>] [--output <PATH>] [--excel]
+//! pc-inventory-admin api-key list|create <NAME>|revoke <ID>
+//! ```
+
+mod api;
+mod error;
+mod output;
+
+use api::AdminClient;
+use clap::{Parser, Subcommand, ValueEnum};
+use error::{AdminError, Result};
+use pc_inventory_protocol::PcListQuery;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process::ExitCode;
+use std::time::Duration;
+
+/// PC情報の管理ツール（サーバーの管理用APIを使用）
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    /// サーバーのURL
+    #[arg(long, value_name = "URL", env = "PC_INVENTORY_SERVER", default_value = "http://localhost:8080", global = true)]
+    server: String,
+
+    /// API キー（pc-inventory-server --create-api-key で作成）
+    #[arg(long, value_name = "KEY", env = "PC_INVENTORY_API_KEY", hide_env_values = true, global = true)]
+    api_key: Option<String>,
+
+    /// 出力形式
+    #[arg(long, value_enum, default_value_t = Format::Table, global = true)]
+    format: Format,
+
+    /// リクエストのタイムアウト（秒）
+    #[arg(long, value_name = "SECONDS", default_value_t = 30, global = true)]
+    timeout: u64,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// 出力形式
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// 表
+    Table,
+    /// JSON（サーバーのレスポンスの内容）
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// PC情報の一覧（ID順）
+    List {
+        /// UUID・使用者名・機種名・MACアドレス・IPアドレスの部分一致
+        #[arg(long, value_name = "TEXT")]
+        search: Option<String>,
+
+        /// 最大件数
+        #[arg(long, value_name = "N")]
+        limit: Option<u32>,
+    },
+    /// PC情報を検索（list --search と同じ）
+    Search {
+        /// UUID・使用者名・機種名・MACアドレス・IPアドレスの部分一致
+        text: String,
+    },
+    /// PC情報と変更履歴を表示
+    Show {
+        /// PCのID
+        id: i32,
+    },
+    /// PC情報を削除（ソフトウェア・更新プログラム・変更履歴も削除）
+    Delete {
+        /// PCのID
+        id: i32,
+
+        /// 確認せずに削除
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// PC情報を統合（統合元の変更履歴を統合先に移し、統合元を削除）
+    Merge {
+        /// 統合元（削除される）PCのID
+        source_id: i32,
+
+        /// 統合先のPCのID
+        #[arg(long, value_name = "TARGET_ID")]
+        into: i32,
+
+        /// 確認せずに統合
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// PC情報を CSV で出力
+    Export {
+        /// UUID・使用者名・機種名・MACアドレス・IPアドレスの部分一致
+        #[arg(long, value_name = "TEXT")]
+        search: Option<String>,
+
+        /// 出力先のファイル（省略した場合は標準出力）
+        #[arg(long, short = 'o', value_name = "PATH")]
+        output: Option<String>,
+
+        /// Excel で開くために UTF-8 の BOM を付ける
+        #[arg(long)]
+        excel: bool,
+    },
+    /// API キーの管理
+    #[command(subcommand)]
+    ApiKey(ApiKeyCommand),
+}
+
+#[derive(Subcommand)]
+enum ApiKeyCommand {
+    /// API キーの一覧
+    List,
+    /// API キーを作成（キーは作成時にのみ表示）
+    Create {
+        /// 用途・利用者の名前
+        name: String,
+    },
+    /// API キーを削除
+    Revoke {
+        /// API キーのID
+        id: i32,
+
+        /// 確認せずに削除
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    let client = AdminClient::new(
+        &cli.server,
+        cli.api_key.as_deref().unwrap_or_default(),
+        Duration::from_secs(cli.timeout),
+    )?;
+    let format = cli.format;
+
+    match cli.command {
+        Command::List { search, limit } => list(&client, format, PcListQuery { search, limit, after_id: None }).await,
+        Command::Search { text } => list(&client, format, PcListQuery { search: Some(text), limit: None, after_id: None }).await,
+        Command::Show { id } => {
+            let detail = client.get_pc(id).await?;
+            print(format, &detail, || output::pc_detail(&detail))
+        }
+        Command::Delete { id, yes } => {
+            let pc = client.get_pc(id).await?.pc;
+            let prompt = format!(
+                "Delete PC {} ({}, {})?",
+                pc.id,
+                pc.uuid,
+                pc.user_name.as_deref().unwrap_or("no user")
+            );
+            if !yes && !confirm(&prompt)? {
+                return Err(AdminError::Cancelled);
+            }
+            let response = client.delete_pc(id).await?;
+            print(format, &response, || format!("Deleted PC {}", response.id))
+        }
+        Command::Merge { source_id, into, yes } => {
+            let source = client.get_pc(source_id).await?.pc;
+            let target = client.get_pc(into).await?.pc;
+            let prompt = format!(
+                "Merge PC {} ({}) into PC {} ({})? PC {} will be deleted.",
+                source.id, source.uuid, target.id, target.uuid, source.id
+            );
+            if !yes && !confirm(&prompt)? {
+                return Err(AdminError::Cancelled);
+            }
+            let response = client.merge_pcs(into, source_id).await?;
+            print(format, &response, || {
+                format!(
+                    "Merged PC {} into PC {} ({} history entries moved)",
+                    response.merged_id, response.id, response.history_moved
+                )
+            })
+        }
+        Command::Export { search, output: path, excel } => {
+            let items = client.list_all_pcs(search).await?;
+            match &path {
+                Some(path) => {
+                    output::write_csv(BufWriter::new(File::create(path)?), &items, excel)?;
+                    eprintln!("Exported {} PCs to {}", items.len(), path);
+                }
+                None => output::write_csv(io::stdout().lock(), &items, excel)?,
+            }
+            Ok(())
+        }
+        Command::ApiKey(ApiKeyCommand::List) => {
+            let items = client.list_api_keys().await?;
+            print(format, &items, || output::api_key_table(&items).to_string())
+        }
+        Command::ApiKey(ApiKeyCommand::Create { name }) => {
+            let response = client.create_api_key(&name).await?;
+            print(format, &response, || {
+                format!(
+                    "Created API key {} ({})\n\n{}\n\nStore this key now; it cannot be shown again.",
+                    response.key.id, response.key.name, response.secret
+                )
+            })
+        }
+        Command::ApiKey(ApiKeyCommand::Revoke { id, yes }) => {
+            if !yes && !confirm(&format!("Revoke API key {}?", id))? {
+                return Err(AdminError::Cancelled);
+            }
+            let response = client.delete_api_key(id).await?;
+            print(format, &response, || format!("Revoked API key {}", response.id))
+        }
+    }
+}
+
+/// PC情報の一覧を表示
+async fn list(client: &AdminClient, format: Format, query: PcListQuery) -> Result<()> {
+    let items = client.list_pcs(&query).await?;
+    print(format, &items, || format!("{}\nTotal: {}", output::pc_table(&items), items.len()))
+}
+
+/// 出力形式に応じて、JSON またはテキストを標準出力に表示
+fn print<T: Serialize>(format: Format, value: &T, text: impl FnOnce() -> String) -> Result<()> {
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(value)?),
+        Format::Table => println!("{}", text()),
+    }
+    Ok(())
+}
+
+/// 確認のプロンプトを標準エラーに表示し、y / yes が入力された場合のみ true を返す
+fn confirm(prompt: &str) -> Result<bool> {
+    eprint!("{} [y/N] ", prompt);
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes"))
+}
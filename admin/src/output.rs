@@ -0,0 +1,286 @@
+//! 表示・出力（表形式・CSV）
+//!
+//! 表形式の日時は実行しているPCのタイムゾーンで表示します。
+//! JSON・CSV の日時はサーバーのレスポンスのまま（UTC、RFC 3339）出力します。
+
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use comfy_table::presets::UTF8_FULL_CONDENSED;
+use comfy_table::Table;
+use pc_inventory_protocol::{ApiKeyInfo, PcDetailResponse, PcHistoryEntry, PcRecord};
+use std::io::Write;
+
+/// UTF-8 の BOM（Excel で文字化けせずに開くために CSV の先頭に付ける）
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// CSV の列（PcRecord の項目名）
+const CSV_HEADER: [&str; 17] = [
+    "id",
+    "uuid",
+    "mac_address",
+    "network_type",
+    "user_name",
+    "ip_address",
+    "ipv6_address",
+    "os",
+    "os_version",
+    "model_name",
+    "manufacturer",
+    "total_memory_bytes",
+    "logical_processors",
+    "agent_version",
+    "created_at",
+    "updated_at",
+    "last_seen_at",
+];
+
+/// PC情報の一覧の表
+pub fn pc_table(items: &[PcRecord]) -> Table {
+    let mut table = new_table();
+    table.set_header(["ID", "UUID", "User", "Model", "IP address", "MAC address", "OS", "Agent", "Last seen"]);
+    for pc in items {
+        table.add_row([
+            pc.id.to_string(),
+            pc.uuid.clone(),
+            text(&pc.user_name),
+            text(&pc.model_name),
+            text(&pc.ip_address),
+            text(&pc.mac_address),
+            text(&pc.os),
+            text(&pc.agent_version),
+            time(pc.last_seen_at),
+        ]);
+    }
+    table
+}
+
+/// PC情報の詳細（項目ごとの表と変更履歴の表）
+pub fn pc_detail(detail: &PcDetailResponse) -> String {
+    let pc = &detail.pc;
+    let mut table = new_table();
+    table.set_header(["Field", "Value"]);
+    table.add_rows([
+        ["ID".to_string(), pc.id.to_string()],
+        ["UUID".to_string(), pc.uuid.clone()],
+        ["User".to_string(), text(&pc.user_name)],
+        ["Model".to_string(), text(&pc.model_name)],
+        ["Manufacturer".to_string(), text(&pc.manufacturer)],
+        ["OS".to_string(), text(&pc.os)],
+        ["OS version".to_string(), text(&pc.os_version)],
+        ["Memory".to_string(), pc.total_memory_bytes.map(memory).unwrap_or_default()],
+        ["Logical processors".to_string(), pc.logical_processors.map(|n| n.to_string()).unwrap_or_default()],
+        ["IP address".to_string(), text(&pc.ip_address)],
+        ["IPv6 address".to_string(), text(&pc.ipv6_address)],
+        ["MAC address".to_string(), text(&pc.mac_address)],
+        ["Network type".to_string(), text(&pc.network_type)],
+        ["Agent".to_string(), text(&pc.agent_version)],
+        ["Created".to_string(), time(Some(pc.created_at))],
+        ["Updated".to_string(), time(Some(pc.updated_at))],
+        ["Last seen".to_string(), time(pc.last_seen_at)],
+    ]);
+
+    if detail.history.is_empty() {
+        return format!("{}\n\nHistory: (none)", table);
+    }
+    format!("{}\n\nHistory ({}):\n{}", table, detail.history.len(), history_table(&detail.history))
+}
+
+/// 変更履歴の表（新しい順）
+fn history_table(history: &[PcHistoryEntry]) -> Table {
+    let mut table = new_table();
+    table.set_header(["Valid from", "Recorded", "Reason", "UUID", "User", "Model", "IP address", "MAC address", "OS", "Agent"]);
+    for entry in history {
+        table.add_row([
+            time(Some(entry.valid_from)),
+            time(Some(entry.recorded_at)),
+            entry.reason.as_str().to_string(),
+            entry.uuid.clone(),
+            text(&entry.user_name),
+            text(&entry.model_name),
+            text(&entry.ip_address),
+            text(&entry.mac_address),
+            text(&entry.os),
+            text(&entry.agent_version),
+        ]);
+    }
+    table
+}
+
+/// API キーの一覧の表
+pub fn api_key_table(items: &[ApiKeyInfo]) -> Table {
+    let mut table = new_table();
+    table.set_header(["ID", "Name", "Created", "Last used"]);
+    for key in items {
+        table.add_row([key.id.to_string(), key.name.clone(), time(Some(key.created_at)), time(key.last_used_at)]);
+    }
+    table
+}
+
+/// PC情報の一覧を CSV で書き込み（列は PcRecord の項目名）
+///
+/// 使用者名などはクライアントから送信された値のため、表計算ソフトで数式として
+/// 実行されないよう、"=" "+" "-" "@" タブ・改行で始まる値の先頭に "'" を付けます。
+///
+/// # 引数
+/// * `writer` - 書き込み先
+/// * `items` - PC情報
+/// * `bom` - 先頭に UTF-8 の BOM を付ける（Excel で開く場合）
+pub fn write_csv<W: Write>(mut writer: W, items: &[PcRecord], bom: bool) -> csv::Result<()> {
+    if bom {
+        writer.write_all(UTF8_BOM)?;
+    }
+    let mut csv = csv::Writer::from_writer(writer);
+    csv.write_record(CSV_HEADER)?;
+    for pc in items {
+        csv.write_record(csv_row(pc))?;
+    }
+    csv.flush()?;
+    Ok(())
+}
+
+/// CSV の1行（日時は RFC 3339、UTC）
+fn csv_row(pc: &PcRecord) -> [String; 17] {
+    let number = |value: Option<u64>| value.map(|n| n.to_string()).unwrap_or_default();
+    let timestamp = |value: DateTime<Utc>| value.to_rfc3339_opts(SecondsFormat::AutoSi, true);
+    [
+        pc.id.to_string(),
+        csv_text(&pc.uuid),
+        csv_text(&text(&pc.mac_address)),
+        csv_text(&text(&pc.network_type)),
+        csv_text(&text(&pc.user_name)),
+        csv_text(&text(&pc.ip_address)),
+        csv_text(&text(&pc.ipv6_address)),
+        csv_text(&text(&pc.os)),
+        csv_text(&text(&pc.os_version)),
+        csv_text(&text(&pc.model_name)),
+        csv_text(&text(&pc.manufacturer)),
+        number(pc.total_memory_bytes),
+        number(pc.logical_processors.map(u64::from)),
+        csv_text(&text(&pc.agent_version)),
+        timestamp(pc.created_at),
+        timestamp(pc.updated_at),
+        pc.last_seen_at.map(timestamp).unwrap_or_default(),
+    ]
+}
+
+/// 数式として解釈される文字で始まる値の先頭に "'" を付ける（CSV インジェクション対策）
+fn csv_text(value: &str) -> String {
+    if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+fn new_table() -> Table {
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL_CONDENSED);
+    table
+}
+
+fn text(value: &Option<String>) -> String {
+    value.clone().unwrap_or_default()
+}
+
+/// 日時を実行しているPCのタイムゾーンで表示（None の場合は空）
+fn time(value: Option<DateTime<Utc>>) -> String {
+    value
+        .map(|t| t.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+/// メモリ容量を GB（小数点以下1桁）で表示
+fn memory(bytes: u64) -> String {
+    format!("{:.1} GB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use pc_inventory_protocol::HistoryReason;
+
+    fn sample_pc() -> PcRecord {
+        PcRecord {
+            id: 7,
+            uuid: "4C4C4544-0042-3510-8052-B7C04F4E3332".to_string(),
+            mac_address: Some("00:11:22:33:44:55".to_string()),
+            network_type: Some("wired".to_string()),
+            user_name: Some("山田, 太郎".to_string()),
+            ip_address: Some("192.168.1.10".to_string()),
+            ipv6_address: None,
+            os: Some("Windows 11 Pro".to_string()),
+            os_version: Some("10.0.22631".to_string()),
+            model_name: Some("OptiPlex 7090".to_string()),
+            manufacturer: Some("Dell Inc.".to_string()),
+            total_memory_bytes: Some(16 * 1024 * 1024 * 1024),
+            logical_processors: Some(8),
+            agent_version: Some("0.1.0".to_string()),
+            created_at: Utc.with_ymd_and_hms(2026, 4, 1, 0, 0, 0).unwrap(),
+            updated_at: Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap(),
+            last_seen_at: None,
+        }
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let mut buffer = Vec::new();
+        write_csv(&mut buffer, &[sample_pc()], true).unwrap();
+
+        let (bom, csv) = buffer.split_at(UTF8_BOM.len());
+        assert_eq!(bom, UTF8_BOM);
+        let csv = String::from_utf8(csv.to_vec()).unwrap();
+        let mut lines = csv.lines();
+        assert!(lines.next().unwrap().starts_with("id,uuid,mac_address,network_type,user_name,"));
+        let row = lines.next().unwrap();
+        assert!(row.starts_with("7,4C4C4544-0042-3510-8052-B7C04F4E3332,00:11:22:33:44:55,wired,\"山田, 太郎\",192.168.1.10,,"));
+        assert!(row.ends_with(",2026-10-01T00:00:00Z,"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn test_write_csv_escapes_formulas() {
+        let mut pc = sample_pc();
+        pc.user_name = Some("=HYPERLINK(\"http://example.com\")".to_string());
+        pc.model_name = Some("@SUM(A1)".to_string());
+        pc.os = Some("-1+1".to_string());
+
+        let mut buffer = Vec::new();
+        write_csv(&mut buffer, &[pc], false).unwrap();
+        let csv = String::from_utf8(buffer).unwrap();
+        let row = csv.lines().nth(1).unwrap();
+        assert!(row.contains(",\"'=HYPERLINK(\"\"http://example.com\"\")\","));
+        assert!(row.contains(",'-1+1,"));
+        assert!(row.contains(",'@SUM(A1),"));
+        assert_eq!(csv_text("+81-3-1234-5678"), "'+81-3-1234-5678");
+        assert_eq!(csv_text("OptiPlex 7090"), "OptiPlex 7090");
+    }
+
+    #[test]
+    fn test_pc_detail() {
+        let pc = sample_pc();
+        let history = vec![PcHistoryEntry {
+            uuid: pc.uuid.clone(),
+            mac_address: None,
+            network_type: None,
+            user_name: Some("佐藤".to_string()),
+            ip_address: Some("192.168.1.20".to_string()),
+            ipv6_address: None,
+            os: None,
+            os_version: None,
+            model_name: None,
+            agent_version: None,
+            reason: HistoryReason::Updated,
+            valid_from: pc.created_at,
+            recorded_at: pc.updated_at,
+        }];
+
+        let output = pc_detail(&PcDetailResponse::new(pc.clone(), history));
+        assert!(output.contains("16.0 GB"));
+        assert!(output.contains("History (1):"));
+        assert!(output.contains("192.168.1.20"));
+        assert!(output.contains("updated"));
+
+        let output = pc_detail(&PcDetailResponse::new(pc, Vec::new()));
+        assert!(output.ends_with("History: (none)"));
+    }
+}
@@ -0,0 +1,36 @@
+//! ビルド時の情報をクライアントに埋め込む
+//!
+//! - PC_INVENTORY_BUILD_HASH: ビルド元のコミットハッシュ
+//!   （環境変数で指定した値を優先し、無ければ `git rev-parse` で取得。取得できない場合は埋め込まない）
+//! - PC_INVENTORY_TARGET: ビルド対象のターゲット（自動更新で取得する実行ファイルの選択に使用）
+
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=PC_INVENTORY_BUILD_HASH");
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+    println!("cargo:rerun-if-changed=../.git/refs/heads");
+
+    let hash = std::env::var("PC_INVENTORY_BUILD_HASH")
+        .ok()
+        .filter(|hash| !hash.trim().is_empty())
+        .or_else(git_commit_hash);
+
+    if let Some(hash) = hash {
+        println!("cargo:rustc-env=PC_INVENTORY_BUILD_HASH={}", hash.trim());
+    }
+
+    // TARGET は Cargo が build.rs にのみ渡すため、コンパイル時の環境変数として引き継ぐ
+    let target = std::env::var("TARGET").unwrap_or_default();
+    println!("cargo:rustc-env=PC_INVENTORY_TARGET={}", target);
+}
+
+/// 現在のコミットハッシュ（先頭12文字）
+fn git_commit_hash() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    String::from_utf8(output.stdout).ok()
+}
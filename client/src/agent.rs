@@ -0,0 +1,35 @@
+use pc_inventory_protocol::{AgentInfo, Section};
+
+/// クライアントのバージョン（Cargo.toml の version）
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// ビルド元のコミットハッシュ（build.rs で埋め込み、取得できなかった場合は None）
+pub const BUILD_HASH: Option<&str> = option_env!("PC_INVENTORY_BUILD_HASH");
+
+/// ビルド対象のターゲット（例: "x86_64-pc-windows-msvc"、自動更新で取得する実行ファイルの選択に使用）
+pub const TARGET: &str = env!("PC_INVENTORY_TARGET");
+
+/// サーバーに送信するクライアントの情報を作成
+///
+/// # 引数
+/// * `capabilities` - 収集できる項目（Collector::capabilities）
+pub fn agent_info(capabilities: Vec<Section>) -> AgentInfo {
+    AgentInfo {
+        version: VERSION.to_string(),
+        build_hash: BUILD_HASH.map(str::to_string),
+        capabilities,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_agent_info_is_valid() {
+        let agent = agent_info(vec![Section::Adapters]);
+        assert_eq!(agent.version, env!("CARGO_PKG_VERSION"));
+        assert!(agent.validate().is_ok());
+        assert!(!TARGET.is_empty());
+    }
+}
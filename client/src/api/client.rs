@@ -1,6 +1,11 @@
 use crate::error::{ClientError, Result};
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use pc_inventory_protocol::{
+    v1, ApiVersion, CapabilitiesResponse, ErrorResponse, HeartbeatRequest, HeartbeatResponse, PcAdapterRequest, PcAdapterResponse, PcInfoRequest,
+    PcInfoResponse, PcSoftwareRequest, PcSoftwareResponse, PcUpdateRequest, PcUpdateResponse, ReleaseInfo, ReleaseResponse,
+};
+use reqwest::{Client, Response, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
 use std::time::Duration;
 
 /// API通信クライアント
@@ -15,34 +20,9 @@ pub struct ApiClient {
 
     /// タイムアウト（秒）
     timeout_secs: u64,
-}
-
-/// サーバーに送信するPC情報データ
-#[derive(Debug, Clone, Serialize)]
-pub struct PcInfoData {
-    pub uuid: String,
-    pub mac_address: String,
-    pub network_type: String,
-    pub user_name: String,
-    pub ip_address: String,
-    pub os: String,
-    pub os_version: String,
-    pub model_name: String,
-}
 
-/// サーバーからのレスポンス（成功時）
-#[derive(Debug, Clone, Deserialize)]
-pub struct ApiResponse {
-    pub status: String,
-    pub action: String,
-    pub id: i32,
-}
-
-/// サーバーからのエラーレスポンス
-#[derive(Debug, Clone, Deserialize)]
-pub struct ErrorResponse {
-    pub status: String,
-    pub message: String,
+    /// 使用するAPIバージョン（None の場合はバージョンを含まない従来のパス）
+    api_version: Option<ApiVersion>,
 }
 
 impl ApiClient {
@@ -60,7 +40,7 @@ impl ApiClient {
         let client = Client::builder()
             .timeout(Duration::from_secs(timeout_secs))
             .build()
-            .map_err(|e| ClientError::ApiError(e))?;
+            .map_err(ClientError::ApiError)?;
 
         tracing::info!("API client created. Server: {}, Timeout: {}s", server_url, timeout_secs);
 
@@ -68,26 +48,67 @@ impl ApiClient {
             client,
             server_url,
             timeout_secs,
+            api_version: None,
         })
     }
 
+    /// サーバーの対応状況を取得
+    ///
+    /// GET /api/capabilities でサーバーが受け付けるAPIバージョンと任意の項目を取得します。
+    ///
+    /// # 戻り値
+    /// サーバーの対応状況。エンドポイントが存在しない（404）場合は
+    /// バージョン導入前のサーバーとみなし `CapabilitiesResponse::legacy()` を返します。
+    ///
+    /// # エラー
+    /// send_pc_info と同様
+    pub async fn capabilities(&self) -> Result<CapabilitiesResponse> {
+        let url = self.endpoint_url("/api/capabilities")?;
+
+        tracing::debug!("Fetching server capabilities: {}", url);
+
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(Self::request_error)?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            tracing::info!("Server does not report capabilities, using unversioned API");
+            return Ok(CapabilitiesResponse::legacy());
+        }
+
+        Self::parse_response(response).await
+    }
+
+    /// 以降の送信で使用するAPIバージョンを指定
+    ///
+    /// # 引数
+    /// * `version` - APIバージョン（None の場合はバージョンを含まない従来のパス）
+    pub fn use_api_version(&mut self, version: Option<ApiVersion>) {
+        self.api_version = version;
+    }
+
     /// PC情報をサーバーに送信
     ///
-    /// POST /api/pc-info エンドポイントにPC情報をJSON形式で送信します。
+    /// POST /api/{version}/pc-info エンドポイントにPC情報をJSON形式で送信します。
+    /// v2 より前のバージョンを使用する場合は、そのバージョンの形式に変換して送信します
+    /// （ハードウェア情報・クライアントの情報は送信されません）。
     ///
     /// # 引数
     /// * `data` - 送信するPC情報
     ///
     /// # 戻り値
-    /// サーバーからのレスポンス（ApiResponse）
+    /// サーバーからのレスポンス（PcInfoResponse）
     ///
     /// # エラー
     /// - ネットワークエラー
     /// - タイムアウト
     /// - サーバーエラー（4xx, 5xx）
     /// - レスポンスのパースエラー
-    pub async fn send_pc_info(&self, data: &PcInfoData) -> Result<ApiResponse> {
-        let url = &self.server_url;
+    pub async fn send_pc_info(&self, data: &PcInfoRequest) -> Result<PcInfoResponse> {
+        let url = self.client_endpoint("pc-info")?;
 
         tracing::info!("Sending PC info to server");
         tracing::debug!("  URL: {}", url);
@@ -95,52 +116,300 @@ impl ApiClient {
         tracing::debug!("  User: {}", data.user_name);
         tracing::debug!("  IP: {}", data.ip_address);
 
+        let request = match self.api_version {
+            Some(ApiVersion::V2) => self.client.post(url).json(data),
+            Some(ApiVersion::V1) | None => self.client.post(url).json(&v1::PcInfoRequest::from(data.clone())),
+        };
+
+        let response = request
+            .send()
+            .await
+            .map_err(Self::request_error)?;
+
+        let api_response: PcInfoResponse = Self::parse_response(response).await?;
+
+        tracing::info!("PC info sent successfully. Action: {}, ID: {}",
+            api_response.action, api_response.id);
+
+        Ok(api_response)
+    }
+
+    /// ハートビートをサーバーに送信
+    ///
+    /// POST /api/{version}/pc-heartbeat エンドポイントにUUID（とタグ）のみを送信し、
+    /// サーバー側の最終接触日時（last_seen_at）を更新します。
+    /// 前回送信時からPC情報に変化が無い場合に send_pc_info の代わりに使用します。
+    ///
+    /// # 引数
+    /// * `data` - ハートビートリクエストDTO
+    ///
+    /// # 戻り値
+    /// * `Ok(Some(HeartbeatResponse))` - 更新成功
+    /// * `Ok(None)` - サーバーにPCが登録されていない場合（PC情報の送信が必要）
+    ///
+    /// # エラー
+    /// send_pc_info と同様
+    pub async fn send_heartbeat(&self, data: &HeartbeatRequest) -> Result<Option<HeartbeatResponse>> {
+        let url = self.client_endpoint("pc-heartbeat")?;
+
+        tracing::info!("Sending heartbeat to server");
+        tracing::debug!("  URL: {}", url);
+        tracing::debug!("  UUID: {}", data.uuid);
+
+        let response = self
+            .client
+            .post(url)
+            .json(data)
+            .send()
+            .await
+            .map_err(Self::request_error)?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            tracing::warn!("PC is not registered on the server (UUID: {})", data.uuid);
+            return Ok(None);
+        }
+
+        Self::parse_response(response).await.map(Some)
+    }
+
+    /// ソフトウェア一覧をサーバーに送信
+    ///
+    /// POST /api/{version}/pc-software エンドポイントに、前回送信分からの差分
+    /// （または full_sync 時は完全な一覧）をJSON形式で送信します。
+    /// エンドポイントURLは server_url のスキーム・ホスト・ポートから組み立てます。
+    ///
+    /// # 引数
+    /// * `data` - 送信するソフトウェア一覧データ
+    ///
+    /// # エラー
+    /// send_pc_info と同様
+    pub async fn send_software(&self, data: &PcSoftwareRequest) -> Result<PcSoftwareResponse> {
+        let url = self.client_endpoint("pc-software")?;
+
+        tracing::info!("Sending software inventory to server (full_sync: {}, added: {}, removed: {})",
+            data.full_sync, data.added.len(), data.removed.len());
+        tracing::debug!("  URL: {}", url);
+
+        let response = self
+            .client
+            .post(url)
+            .json(data)
+            .send()
+            .await
+            .map_err(Self::request_error)?;
+
+        Self::parse_response(response).await
+    }
+
+    /// Windows Update適用状況をサーバーに送信
+    ///
+    /// POST /api/{version}/pc-updates エンドポイントにUBRと更新プログラム一覧を送信します。
+    /// サーバー側の更新プログラム一覧は送信した一覧で置き換えられます。
+    ///
+    /// # 引数
+    /// * `data` - 送信するWindows Update適用状況データ
+    ///
+    /// # エラー
+    /// send_pc_info と同様
+    pub async fn send_updates(&self, data: &PcUpdateRequest) -> Result<PcUpdateResponse> {
+        let url = self.client_endpoint("pc-updates")?;
+
+        tracing::info!("Sending update status to server (UBR: {:?}, hotfixes: {})",
+            data.os_build_revision, data.hotfixes.len());
+        tracing::debug!("  URL: {}", url);
+
+        let response = self
+            .client
+            .post(url)
+            .json(data)
+            .send()
+            .await
+            .map_err(Self::request_error)?;
+
+        Self::parse_response(response).await
+    }
+
+    /// ネットワークアダプタ一覧をサーバーに送信
+    ///
+    /// POST /api/{version}/pc-adapters エンドポイントにすべてのアダプタの情報を送信します。
+    /// サーバー側のアダプタ一覧は送信した一覧で置き換えられます。
+    ///
+    /// # 引数
+    /// * `data` - 送信するネットワークアダプタ一覧データ
+    ///
+    /// # エラー
+    /// send_pc_info と同様
+    pub async fn send_adapters(&self, data: &PcAdapterRequest) -> Result<PcAdapterResponse> {
+        let url = self.client_endpoint("pc-adapters")?;
+
+        tracing::info!("Sending network adapters to server (adapters: {})", data.adapters.len());
+        tracing::debug!("  URL: {}", url);
+
         let response = self
             .client
             .post(url)
             .json(data)
             .send()
             .await
-            .map_err(|e| {
-                tracing::error!("Failed to send request: {}", e);
-                ClientError::ApiError(e)
-            })?;
+            .map_err(Self::request_error)?;
+
+        Self::parse_response(response).await
+    }
+
+    /// 配布チャネルの最新のクライアントを取得
+    ///
+    /// GET /api/releases/latest で、指定したチャネル・ターゲットの最新のバージョンを取得します。
+    ///
+    /// # 引数
+    /// * `channel` - 配布チャネル（例: "stable"）
+    /// * `target` - 実行環境のターゲット（例: "x86_64-pc-windows-msvc"）
+    ///
+    /// # 戻り値
+    /// 最新のバージョン。配布されていない、またはエンドポイントが存在しない（404）場合は None
+    ///
+    /// # エラー
+    /// send_pc_info と同様
+    pub async fn latest_release(&self, channel: &str, target: &str) -> Result<Option<ReleaseInfo>> {
+        let mut url = self.endpoint_url("/api/releases/latest")?;
+        url.query_pairs_mut()
+            .append_pair("channel", channel)
+            .append_pair("target", target);
+
+        tracing::debug!("Checking for client release: {}", url);
+
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(Self::request_error)?;
 
+        if response.status() == StatusCode::NOT_FOUND {
+            tracing::debug!("Server does not publish client releases");
+            return Ok(None);
+        }
+
+        let release: ReleaseResponse = Self::parse_response(response).await?;
+        Ok(release.release)
+    }
+
+    /// 配布用クライアントの実行ファイルを取得
+    ///
+    /// GET /api/releases/{id}/binary で実行ファイルをダウンロードします。
+    /// 署名の検証は行わないため、呼び出し側で ReleaseInfo::verify を呼び出してください。
+    ///
+    /// # 引数
+    /// * `release` - latest_release で取得した配布用クライアント
+    ///
+    /// # エラー
+    /// send_pc_info と同様
+    pub async fn download_release(&self, release: &ReleaseInfo) -> Result<Vec<u8>> {
+        let url = self.endpoint_url(&format!("/api/releases/{}/binary", release.id))?;
+
+        tracing::info!("Downloading client release {} ({} bytes)", release.version, release.size);
+        tracing::debug!("  URL: {}", url);
+
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(Self::request_error)?;
+
+        if !response.status().is_success() {
+            return Err(Self::error_response(response).await);
+        }
+
+        let binary = response.bytes().await.map_err(Self::request_error)?;
+        Ok(binary.to_vec())
+    }
+
+    /// リクエスト送信時のエラーを変換
+    ///
+    /// 接続できない・タイムアウトした場合はサーバーに到達できないものとして
+    /// ServerUnreachable を返し、それ以外は ApiError を返します。
+    fn request_error(e: reqwest::Error) -> ClientError {
+        tracing::error!("Failed to send request: {}", e);
+        if e.is_connect() || e.is_timeout() {
+            ClientError::ServerUnreachable(e.to_string())
+        } else {
+            ClientError::ApiError(e)
+        }
+    }
+
+    /// レスポンスを解析
+    ///
+    /// 成功ステータスの場合はボディを指定の型にパースし、
+    /// エラーステータスの場合はエラーメッセージを含む NetworkError を返します。
+    async fn parse_response<T: DeserializeOwned>(response: Response) -> Result<T> {
         let status = response.status();
         tracing::debug!("Response status: {}", status);
 
         if status.is_success() {
             // 成功レスポンスをパース
-            let api_response = response
-                .json::<ApiResponse>()
+            response
+                .json::<T>()
                 .await
                 .map_err(|e| {
                     tracing::error!("Failed to parse success response: {}", e);
                     ClientError::ApiError(e)
-                })?;
+                })
+        } else {
+            Err(Self::error_response(response).await)
+        }
+    }
 
-            tracing::info!("PC info sent successfully. Action: {}, ID: {}",
-                api_response.action, api_response.id);
+    /// エラーステータスのレスポンスからエラーメッセージを含む NetworkError を作成
+    async fn error_response(response: Response) -> ClientError {
+        let status = response.status();
 
-            Ok(api_response)
-        } else {
-            // エラーレスポンスをパース
-            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        // エラーレスポンスをパース
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
 
-            tracing::error!("Server returned error status: {}", status);
-            tracing::error!("Error response: {}", error_text);
+        tracing::error!("Server returned error status: {}", status);
+        tracing::error!("Error response: {}", error_text);
 
-            // エラーメッセージを構築
-            let error_message = if let Ok(err_response) = serde_json::from_str::<ErrorResponse>(&error_text) {
-                format!("Server error: {}", err_response.message)
-            } else {
-                format!("HTTP {}: {}", status, error_text)
-            };
+        // エラーメッセージを構築
+        let error_message = if let Ok(err_response) = serde_json::from_str::<ErrorResponse>(&error_text) {
+            format!("Server error: {}", err_response.message)
+        } else {
+            format!("HTTP {}: {}", status, error_text)
+        };
 
-            Err(ClientError::NetworkError(error_message))
+        ClientError::NetworkError(error_message)
+    }
+
+    /// クライアントからの送信先のURLを組み立て
+    ///
+    /// 使用するAPIバージョンのパス（例: "/api/v2/pc-software"）を返します。
+    /// バージョンを指定していない場合は従来のパスを使用し、
+    /// PC情報は server_url そのものに送信します。
+    ///
+    /// # 引数
+    /// * `name` - エンドポイント名（例: "pc-software"）
+    fn client_endpoint(&self, name: &str) -> Result<Url> {
+        match self.api_version {
+            Some(version) => self.endpoint_url(&format!("{}/{}", version.path_prefix(), name)),
+            None if name == "pc-info" => Url::parse(&self.server_url)
+                .map_err(|e| ClientError::InvalidConfig(format!("Invalid server URL '{}': {}", self.server_url, e))),
+            None => self.endpoint_url(&format!("/api/{}", name)),
         }
     }
 
+    /// サーバー上の別エンドポイントのURLを組み立て
+    ///
+    /// server_url（例: "http://192.168.1.10:8080/api/pc-info"）のパスを置き換えます。
+    ///
+    /// # 引数
+    /// * `path` - エンドポイントパス（例: "/api/pc-software"）
+    fn endpoint_url(&self, path: &str) -> Result<Url> {
+        let mut url = Url::parse(&self.server_url)
+            .map_err(|e| ClientError::InvalidConfig(format!("Invalid server URL '{}': {}", self.server_url, e)))?;
+        url.set_path(path);
+        url.set_query(None);
+        Ok(url)
+    }
+
     /// サーバーの疎通確認（ヘルスチェック）
     ///
     /// サーバーが起動しているか確認します。
@@ -168,54 +437,38 @@ impl ApiClient {
     }
 
     /// サーバーURLを取得
+    #[allow(dead_code)]
     pub fn server_url(&self) -> &str {
         &self.server_url
     }
 
     /// タイムアウト秒数を取得
+    #[allow(dead_code)]
     pub fn timeout_secs(&self) -> u64 {
         self.timeout_secs
     }
 }
 
-impl PcInfoData {
-    /// すべてのフィールドが設定されているか検証
-    ///
-    /// # エラー
-    /// 必須フィールドが空の場合
-    pub fn validate(&self) -> Result<()> {
-        if self.uuid.trim().is_empty() {
-            return Err(ClientError::MissingField("uuid".to_string()));
-        }
-        if self.mac_address.trim().is_empty() {
-            return Err(ClientError::MissingField("mac_address".to_string()));
-        }
-        if self.network_type.trim().is_empty() {
-            return Err(ClientError::MissingField("network_type".to_string()));
-        }
-        if self.user_name.trim().is_empty() {
-            return Err(ClientError::MissingField("user_name".to_string()));
-        }
-        if self.ip_address.trim().is_empty() {
-            return Err(ClientError::MissingField("ip_address".to_string()));
-        }
-        if self.os.trim().is_empty() {
-            return Err(ClientError::MissingField("os".to_string()));
-        }
-        if self.os_version.trim().is_empty() {
-            return Err(ClientError::MissingField("os_version".to_string()));
-        }
-        if self.model_name.trim().is_empty() {
-            return Err(ClientError::MissingField("model_name".to_string()));
-        }
-
-        Ok(())
-    }
+/// 送信内容のハッシュ（SHA-256の16進文字列）を計算
+///
+/// 前回送信したデータと比較し、変化が無ければハートビートのみを送信するために使用します。
+pub fn content_hash(data: &PcInfoRequest) -> String {
+    // 文字列と列挙型のみの構造体のためシリアライズは失敗しない
+    let json = serde_json::to_vec(data).unwrap_or_default();
+    Sha256::digest(&json)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use pc_inventory_protocol::testing::pc_info_request;
+
+    fn sample() -> PcInfoRequest {
+        pc_info_request("192.168.1.100", None)
+    }
 
     #[test]
     fn test_api_client_new() {
@@ -228,37 +481,58 @@ mod tests {
     }
 
     #[test]
-    fn test_pc_info_data_validate_success() {
-        let data = PcInfoData {
-            uuid: "test-uuid".to_string(),
-            mac_address: "00:11:22:33:44:55".to_string(),
-            network_type: "Ethernet".to_string(),
-            user_name: "testuser".to_string(),
-            ip_address: "192.168.1.100".to_string(),
-            os: "Windows 11 Pro".to_string(),
-            os_version: "10.0.22631".to_string(),
-            model_name: "Test Model".to_string(),
-        };
+    fn test_validation_error_conversion() {
+        let mut data = sample();
+        data.uuid = "".to_string();
+        let err = ClientError::from(data.validate().unwrap_err());
+        assert!(matches!(err, ClientError::MissingField(field) if field == "uuid"));
+
+        let mut data = sample();
+        data.ip_address = "999.1.1.1".to_string();
+        let err = ClientError::from(data.validate().unwrap_err());
+        assert!(matches!(err, ClientError::InvalidData(_)));
+    }
 
-        assert!(data.validate().is_ok());
+    #[test]
+    fn test_endpoint_url() {
+        let client = ApiClient::new("http://192.168.1.10:8080/api/pc-info".to_string(), 30).unwrap();
+        let url = client.endpoint_url("/api/pc-software").unwrap();
+        assert_eq!(url.as_str(), "http://192.168.1.10:8080/api/pc-software");
     }
 
     #[test]
-    fn test_pc_info_data_validate_missing_uuid() {
-        let data = PcInfoData {
-            uuid: "".to_string(),
-            mac_address: "00:11:22:33:44:55".to_string(),
-            network_type: "Ethernet".to_string(),
-            user_name: "testuser".to_string(),
-            ip_address: "192.168.1.100".to_string(),
-            os: "Windows 11 Pro".to_string(),
-            os_version: "10.0.22631".to_string(),
-            model_name: "Test Model".to_string(),
-        };
+    fn test_client_endpoint_follows_api_version() {
+        let mut client = ApiClient::new("http://192.168.1.10:8080/custom/pc-info".to_string(), 30).unwrap();
+
+        // バージョン導入前のサーバー: PC情報は server_url、その他は従来のパス
+        let url = client.client_endpoint("pc-info").unwrap();
+        assert_eq!(url.as_str(), "http://192.168.1.10:8080/custom/pc-info");
+        let url = client.client_endpoint("pc-heartbeat").unwrap();
+        assert_eq!(url.as_str(), "http://192.168.1.10:8080/api/pc-heartbeat");
+
+        client.use_api_version(Some(ApiVersion::V2));
+        let url = client.client_endpoint("pc-info").unwrap();
+        assert_eq!(url.as_str(), "http://192.168.1.10:8080/api/v2/pc-info");
+
+        client.use_api_version(Some(ApiVersion::V1));
+        let url = client.client_endpoint("pc-updates").unwrap();
+        assert_eq!(url.as_str(), "http://192.168.1.10:8080/api/v1/pc-updates");
+    }
 
-        let result = data.validate();
-        assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), ClientError::MissingField(_)));
+    #[test]
+    fn test_content_hash_detects_changes() {
+        let data = sample();
+        let hash = content_hash(&data);
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, content_hash(&data.clone()));
+
+        let mut changed = data.clone();
+        changed.ip_address = "192.168.1.101".to_string();
+        assert_ne!(hash, content_hash(&changed));
+
+        let mut changed = data;
+        changed.ipv6_address = Some("2001:db8::10".to_string());
+        assert_ne!(hash, content_hash(&changed));
     }
 
     #[tokio::test]
@@ -266,16 +540,8 @@ mod tests {
     async fn test_send_pc_info() {
         let client = ApiClient::new("http://localhost:8080".to_string(), 30).unwrap();
 
-        let data = PcInfoData {
-            uuid: "test-uuid-client".to_string(),
-            mac_address: "00:11:22:33:44:55".to_string(),
-            network_type: "Ethernet".to_string(),
-            user_name: "testuser".to_string(),
-            ip_address: "192.168.1.100".to_string(),
-            os: "Windows 11 Pro".to_string(),
-            os_version: "10.0.22631".to_string(),
-            model_name: "Test Model".to_string(),
-        };
+        let mut data = sample();
+        data.uuid = "test-uuid-client".to_string();
 
         let result = client.send_pc_info(&data).await;
         println!("Result: {:?}", result);
@@ -0,0 +1,261 @@
+use crate::api::transport::Transport;
+use crate::error::{ClientError, Result};
+use chrono::Utc;
+use pc_inventory_protocol::{
+    compare_versions, v1, ApiVersion, CapabilitiesResponse, ConfigOverrides, HeartbeatRequest, HeartbeatResponse, PcAdapterRequest, PcAdapterResponse, PcInfoRequest, PcInfoResponse,
+    PcSoftwareRequest, PcSoftwareResponse, PcUpdateRequest, PcUpdateResponse, ReleaseInfo, Section,
+};
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// MemoryTransport の応答の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MemoryTransportMode {
+    /// 送信内容を記録して成功を返す
+    #[default]
+    Online,
+    /// サーバーエラー（5xx 相当）を返す
+    ServerError,
+    /// サーバーに到達できない（ServerUnreachable）
+    Unreachable,
+}
+
+/// メモリ上で動作する送信手段（テスト用）
+///
+/// サーバーに送信する代わりに送信内容を記録します。
+/// clone したインスタンスは記録と応答の種類を共有するため、
+/// スケジューラに渡した後もテスト側から送信内容を確認できます。
+///
+/// 応答はサーバーと同じ規則で返します。
+/// - PC情報: 未登録のUUIDは "created"、登録済みは "updated"
+/// - ハートビート・付随情報: 未登録のUUIDはハートビートのみ `Ok(None)`、それ以外はエラー
+/// - 対応状況（set_capabilities）に含まれない項目: エラー
+/// - v2 より前のバージョンで送信されたPC情報: その形式に含まれない項目を除いて記録
+/// - 配布用クライアント（publish_release）: チャネル・ターゲットが一致するうち最新のバージョン
+/// - 配布する設定（set_config）: PC情報・ハートビートのレスポンスにタグに関係なく含める
+#[derive(Debug, Clone, Default)]
+pub struct MemoryTransport {
+    inner: Arc<Mutex<MemoryServer>>,
+    /// use_api_version で指定されたバージョン（clone ごとに独立）
+    api_version: Option<ApiVersion>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryServer {
+    mode: MemoryTransportMode,
+    /// 対応状況（None の場合は CapabilitiesResponse::current()）
+    capabilities: Option<CapabilitiesResponse>,
+    /// 登録済みのUUID（登録順、ID は添字 + 1）
+    registered: Vec<String>,
+    pc_info: Vec<PcInfoRequest>,
+    heartbeats: Vec<String>,
+    adapters: Vec<PcAdapterRequest>,
+    software: Vec<PcSoftwareRequest>,
+    updates: Vec<PcUpdateRequest>,
+    /// 配布用クライアントと実行ファイル
+    releases: Vec<(ReleaseInfo, Vec<u8>)>,
+    /// ダウンロードされた配布用クライアントのID（ダウンロード順）
+    downloads: Vec<i32>,
+    /// 配布する設定
+    config: Option<ConfigOverrides>,
+}
+
+impl MemoryServer {
+    fn check(&self) -> Result<()> {
+        match self.mode {
+            MemoryTransportMode::Online => Ok(()),
+            MemoryTransportMode::ServerError => {
+                Err(ClientError::NetworkError("Server error: Internal server error".to_string()))
+            }
+            MemoryTransportMode::Unreachable => {
+                Err(ClientError::ServerUnreachable("Connection refused".to_string()))
+            }
+        }
+    }
+
+    fn capabilities(&self) -> CapabilitiesResponse {
+        self.capabilities.clone().unwrap_or_else(CapabilitiesResponse::current)
+    }
+
+    fn check_section(&self, section: Section) -> Result<()> {
+        self.check()?;
+        if self.capabilities().accepts(section) {
+            Ok(())
+        } else {
+            Err(ClientError::NetworkError(format!("HTTP 404 Not Found: {:?} is not supported", section)))
+        }
+    }
+
+    fn id_of(&self, uuid: &str) -> Option<i32> {
+        self.registered.iter().position(|u| u == uuid).map(|i| i as i32 + 1)
+    }
+
+    fn registered_id(&self, uuid: &str) -> Result<i32> {
+        self.id_of(uuid)
+            .ok_or_else(|| ClientError::NetworkError(format!("Server error: PC not found: {}", uuid)))
+    }
+}
+
+impl MemoryTransport {
+    /// 新しいインスタンスを作成（応答は Online）
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 応答の種類を変更
+    pub fn set_mode(&self, mode: MemoryTransportMode) {
+        self.lock().mode = mode;
+    }
+
+    /// サーバーの対応状況を変更
+    pub fn set_capabilities(&self, capabilities: CapabilitiesResponse) {
+        self.lock().capabilities = Some(capabilities);
+    }
+
+    /// 配布する設定を変更（None の場合は配布しない）
+    pub fn set_config(&self, config: Option<ConfigOverrides>) {
+        self.lock().config = config;
+    }
+
+    /// 登録済みのPCをすべて削除（サーバーのデータベースの初期化・PC情報の削除を再現、送信内容の記録は残す）
+    pub fn forget_registrations(&self) {
+        self.lock().registered.clear();
+    }
+
+    /// 配布用クライアントを登録
+    pub fn publish_release(&self, release: ReleaseInfo, binary: Vec<u8>) {
+        self.lock().releases.push((release, binary));
+    }
+
+    /// ダウンロードされた配布用クライアントのID（ダウンロード順）
+    pub fn downloads(&self) -> Vec<i32> {
+        self.lock().downloads.clone()
+    }
+
+    /// 送信されたPC情報（送信順）
+    pub fn sent_pc_info(&self) -> Vec<PcInfoRequest> {
+        self.lock().pc_info.clone()
+    }
+
+    /// ハートビートを送信したUUID（送信順、未登録で拒否されたものを含む）
+    pub fn heartbeats(&self) -> Vec<String> {
+        self.lock().heartbeats.clone()
+    }
+
+    /// 送信されたネットワークアダプタ一覧（送信順）
+    pub fn sent_adapters(&self) -> Vec<PcAdapterRequest> {
+        self.lock().adapters.clone()
+    }
+
+    /// 送信されたソフトウェア一覧（送信順）
+    pub fn sent_software(&self) -> Vec<PcSoftwareRequest> {
+        self.lock().software.clone()
+    }
+
+    /// 送信されたWindows Update適用状況（送信順）
+    pub fn sent_updates(&self) -> Vec<PcUpdateRequest> {
+        self.lock().updates.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MemoryServer> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Transport for MemoryTransport {
+    fn capabilities(&self) -> impl Future<Output = Result<CapabilitiesResponse>> + Send {
+        let server = self.lock();
+        std::future::ready(server.check().map(|_| server.capabilities()))
+    }
+
+    fn use_api_version(&mut self, version: Option<ApiVersion>) {
+        self.api_version = version;
+    }
+
+    fn send_pc_info(&self, data: &PcInfoRequest) -> impl Future<Output = Result<PcInfoResponse>> + Send {
+        let data = match self.api_version {
+            Some(ApiVersion::V2) => data.clone(),
+            Some(ApiVersion::V1) | None => v1::PcInfoRequest::from(data.clone()).into(),
+        };
+        let mut server = self.lock();
+        let result = server.check().map(|_| {
+            server.pc_info.push(data.clone());
+            let response = match server.id_of(&data.uuid) {
+                Some(id) => PcInfoResponse::updated(id),
+                None => {
+                    server.registered.push(data.uuid.clone());
+                    PcInfoResponse::created(server.registered.len() as i32)
+                }
+            };
+            response.with_config(server.config.clone())
+        });
+        std::future::ready(result)
+    }
+
+    fn send_heartbeat(&self, data: &HeartbeatRequest) -> impl Future<Output = Result<Option<HeartbeatResponse>>> + Send {
+        let mut server = self.lock();
+        let result = server.check().map(|_| {
+            server.heartbeats.push(data.uuid.clone());
+            server
+                .id_of(&data.uuid)
+                .map(|id| HeartbeatResponse::touched(id, Utc::now()).with_config(server.config.clone()))
+        });
+        std::future::ready(result)
+    }
+
+    fn send_adapters(&self, data: &PcAdapterRequest) -> impl Future<Output = Result<PcAdapterResponse>> + Send {
+        let mut server = self.lock();
+        let result = server.check_section(Section::Adapters).and_then(|_| server.registered_id(&data.uuid)).map(|id| {
+            server.adapters.push(data.clone());
+            PcAdapterResponse::synced(id, data.adapters.len() as u64)
+        });
+        std::future::ready(result)
+    }
+
+    fn send_software(&self, data: &PcSoftwareRequest) -> impl Future<Output = Result<PcSoftwareResponse>> + Send {
+        let mut server = self.lock();
+        let result = server.check_section(Section::Software).and_then(|_| server.registered_id(&data.uuid)).map(|id| {
+            server.software.push(data.clone());
+            PcSoftwareResponse::synced(id, data.added.len() as u64, data.removed.len() as u64)
+        });
+        std::future::ready(result)
+    }
+
+    fn send_updates(&self, data: &PcUpdateRequest) -> impl Future<Output = Result<PcUpdateResponse>> + Send {
+        let mut server = self.lock();
+        let result = server.check_section(Section::Updates).and_then(|_| server.registered_id(&data.uuid)).map(|id| {
+            server.updates.push(data.clone());
+            PcUpdateResponse::synced(id, data.hotfixes.len() as u64)
+        });
+        std::future::ready(result)
+    }
+
+    fn latest_release(&self, channel: &str, target: &str) -> impl Future<Output = Result<Option<ReleaseInfo>>> + Send {
+        let server = self.lock();
+        let result = server.check().map(|_| {
+            server
+                .releases
+                .iter()
+                .map(|(release, _)| release)
+                .filter(|release| release.channel == channel && release.target == target)
+                .max_by(|a, b| compare_versions(&a.version, &b.version))
+                .cloned()
+        });
+        std::future::ready(result)
+    }
+
+    fn download_release(&self, release: &ReleaseInfo) -> impl Future<Output = Result<Vec<u8>>> + Send {
+        let mut server = self.lock();
+        let result = server.check().and_then(|_| {
+            let binary = server
+                .releases
+                .iter()
+                .find(|(published, _)| published.id == release.id)
+                .map(|(_, binary)| binary.clone())
+                .ok_or_else(|| ClientError::NetworkError(format!("Server error: Release not found: {}", release.id)))?;
+            server.downloads.push(release.id);
+            Ok(binary)
+        });
+        std::future::ready(result)
+    }
+}
@@ -1,3 +1,9 @@
 pub mod client;
+#[cfg(any(test, feature = "test-util"))]
+pub mod memory;
+pub mod transport;
 
-pub use client::{ApiClient, PcInfoData, ApiResponse};
+pub use client::{content_hash, ApiClient};
+#[cfg(any(test, feature = "test-util"))]
+pub use memory::{MemoryTransport, MemoryTransportMode};
+pub use transport::Transport;
@@ -0,0 +1,94 @@
+use crate::api::client::ApiClient;
+use crate::error::Result;
+use pc_inventory_protocol::{
+    ApiVersion, CapabilitiesResponse, HeartbeatRequest, HeartbeatResponse, PcAdapterRequest, PcAdapterResponse, PcInfoRequest, PcInfoResponse, PcSoftwareRequest,
+    PcSoftwareResponse, PcUpdateRequest, PcUpdateResponse, ReleaseInfo,
+};
+use std::future::Future;
+
+/// サーバーへの送信手段
+///
+/// 送信処理（scheduler）はこのトレイトを介してサーバーと通信します。
+/// 実運用では ApiClient（HTTP）を使用し、テストでは送信内容を記録する MemoryTransport に差し替えます。
+///
+/// サーバーに接続できない場合は `ClientError::ServerUnreachable` を返してください。
+/// 送信処理はこのエラーをオフライン状態として扱います。
+///
+/// 送信処理は capabilities でサーバーの対応状況を取得し、use_api_version で
+/// 使用するAPIバージョンを指定してから各項目を送信します。
+pub trait Transport {
+    /// サーバーの対応状況を取得（GET /api/capabilities）
+    ///
+    /// 対応状況を返さない（バージョン導入前の）サーバーの場合は
+    /// `CapabilitiesResponse::legacy()` を返します。
+    fn capabilities(&self) -> impl Future<Output = Result<CapabilitiesResponse>> + Send;
+
+    /// 以降の送信で使用するAPIバージョンを指定
+    ///
+    /// None の場合はバージョンを含まない従来のパス（v1 の形式）で送信します。
+    fn use_api_version(&mut self, version: Option<ApiVersion>);
+
+    /// PC情報を送信（POST /api/{version}/pc-info）
+    fn send_pc_info(&self, data: &PcInfoRequest) -> impl Future<Output = Result<PcInfoResponse>> + Send;
+
+    /// ハートビートを送信（POST /api/{version}/pc-heartbeat）
+    ///
+    /// サーバーにPCが登録されていない場合は `Ok(None)` を返します。
+    fn send_heartbeat(&self, data: &HeartbeatRequest) -> impl Future<Output = Result<Option<HeartbeatResponse>>> + Send;
+
+    /// ネットワークアダプタ一覧を送信（POST /api/{version}/pc-adapters）
+    fn send_adapters(&self, data: &PcAdapterRequest) -> impl Future<Output = Result<PcAdapterResponse>> + Send;
+
+    /// ソフトウェア一覧を送信（POST /api/{version}/pc-software）
+    fn send_software(&self, data: &PcSoftwareRequest) -> impl Future<Output = Result<PcSoftwareResponse>> + Send;
+
+    /// Windows Update適用状況を送信（POST /api/{version}/pc-updates）
+    fn send_updates(&self, data: &PcUpdateRequest) -> impl Future<Output = Result<PcUpdateResponse>> + Send;
+
+    /// 配布チャネルの最新のクライアントを取得（GET /api/releases/latest）
+    ///
+    /// 配布されていない、またはサーバーが配布に対応していない場合は `Ok(None)` を返します。
+    fn latest_release(&self, channel: &str, target: &str) -> impl Future<Output = Result<Option<ReleaseInfo>>> + Send;
+
+    /// 配布用クライアントの実行ファイルを取得（GET /api/releases/{id}/binary）
+    ///
+    /// 署名の検証は呼び出し側で行います。
+    fn download_release(&self, release: &ReleaseInfo) -> impl Future<Output = Result<Vec<u8>>> + Send;
+}
+
+impl Transport for ApiClient {
+    fn capabilities(&self) -> impl Future<Output = Result<CapabilitiesResponse>> + Send {
+        ApiClient::capabilities(self)
+    }
+
+    fn use_api_version(&mut self, version: Option<ApiVersion>) {
+        ApiClient::use_api_version(self, version)
+    }
+    fn send_pc_info(&self, data: &PcInfoRequest) -> impl Future<Output = Result<PcInfoResponse>> + Send {
+        ApiClient::send_pc_info(self, data)
+    }
+
+    fn send_heartbeat(&self, data: &HeartbeatRequest) -> impl Future<Output = Result<Option<HeartbeatResponse>>> + Send {
+        ApiClient::send_heartbeat(self, data)
+    }
+
+    fn send_adapters(&self, data: &PcAdapterRequest) -> impl Future<Output = Result<PcAdapterResponse>> + Send {
+        ApiClient::send_adapters(self, data)
+    }
+
+    fn send_software(&self, data: &PcSoftwareRequest) -> impl Future<Output = Result<PcSoftwareResponse>> + Send {
+        ApiClient::send_software(self, data)
+    }
+
+    fn send_updates(&self, data: &PcUpdateRequest) -> impl Future<Output = Result<PcUpdateResponse>> + Send {
+        ApiClient::send_updates(self, data)
+    }
+
+    fn latest_release(&self, channel: &str, target: &str) -> impl Future<Output = Result<Option<ReleaseInfo>>> + Send {
+        ApiClient::latest_release(self, channel, target)
+    }
+
+    fn download_release(&self, release: &ReleaseInfo) -> impl Future<Output = Result<Vec<u8>>> + Send {
+        ApiClient::download_release(self, release)
+    }
+}
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
 use crate::error::{Result, ClientError};
+use crate::network::AdapterFilter;
+use pc_inventory_protocol::config::validate_tags;
+use pc_inventory_protocol::{ConfigOverrides, ReleaseKey};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ClientConfig {
@@ -9,6 +12,12 @@ pub struct ClientConfig {
     pub retry: RetrySettings,
     pub pc_info: PcInfoSettings,
     pub logging: LoggingSettings,
+    #[serde(default)]
+    pub software: SoftwareSettings,
+    #[serde(default)]
+    pub network: NetworkSettings,
+    #[serde(default)]
+    pub update: UpdateSettings,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -19,9 +28,29 @@ pub struct ServerSettings {
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ClientSettings {
-    pub last_send_datetime: String,
+    /// 実行時状態（最終送信日時・自動取得したPC情報）の保存先
+    #[serde(default = "default_state_file")]
+    pub state_file: String,
     pub check_interval_secs: u64,
     pub send_interval_secs: u64,
+    /// OSのネットワーク変更通知を購読し、IP・MAC・ネットワークタイプの変化時に即時送信するか
+    #[serde(default = "default_watch_network_changes")]
+    pub watch_network_changes: bool,
+    /// ネットワーク変更通知が落ち着くまで待つ時間（秒）
+    #[serde(default = "default_network_change_debounce_secs")]
+    pub network_change_debounce_secs: u64,
+}
+
+fn default_state_file() -> String {
+    "client_state.json".to_string()
+}
+
+fn default_watch_network_changes() -> bool {
+    true
+}
+
+fn default_network_change_debounce_secs() -> u64 {
+    10
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -30,16 +59,16 @@ pub struct RetrySettings {
     pub second_retry_delay_secs: u64,
 }
 
+/// PC情報の設定
+///
+/// 使用者が入力する項目のみを保持します。
+/// 自動取得した項目は状態ファイル（state::ClientState）に保存されます。
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct PcInfoSettings {
     pub user_name: String,
-    pub uuid: String,
-    pub mac_address: String,
-    pub network_type: String,
-    pub ip_address: String,
-    pub os: String,
-    pub os_version: String,
-    pub model_name: String,
+    /// PCのタグ（サーバーはタグに応じた設定を配布する、例: ["branch-office"]）
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -50,6 +79,91 @@ pub struct LoggingSettings {
     pub max_backup_files: usize,
 }
 
+/// インストール済みソフトウェア収集設定
+///
+/// 既存の config.toml との互換性のため、セクションが無い場合はデフォルト値を使用します。
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct SoftwareSettings {
+    /// ソフトウェア一覧の収集・送信を行うか
+    pub enabled: bool,
+    /// 最後に送信したソフトウェア一覧の保存先（差分計算用）
+    pub snapshot_file: String,
+}
+
+/// 代表アダプタの絞り込み設定
+///
+/// Hyper-V・VirtualBox・Docker・VPN などの仮想アダプタが代表アダプタに選ばれないよう、
+/// 候補とするアダプタを絞り込みます。いずれも省略時は空（絞り込みなし）です。
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct NetworkSettings {
+    /// 代表アダプタの選択方式
+    pub selection_mode: SelectionMode,
+    /// 候補とするインターフェース名（ワイルドカード "*", "?" 使用可）
+    pub include_interfaces: Vec<String>,
+    /// 除外するインターフェース名（ワイルドカード "*", "?" 使用可）
+    pub exclude_interfaces: Vec<String>,
+    /// 候補とするMACアドレスのプレフィックス（例: "4C:23:38"）
+    pub include_mac_prefixes: Vec<String>,
+    /// 除外するMACアドレスのプレフィックス（例: "00:15:5D"）
+    pub exclude_mac_prefixes: Vec<String>,
+    /// 候補とするサブネット（例: "192.168.0.0/16"）
+    pub include_subnets: Vec<String>,
+    /// 除外するサブネット（例: "172.16.0.0/12"）
+    pub exclude_subnets: Vec<String>,
+    /// 優先するインターフェース名（先頭ほど優先、ワイルドカード使用可）
+    pub preferred_adapters: Vec<String>,
+}
+
+/// 代表アダプタの選択方式
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionMode {
+    /// 優先順位（preferred_adapters → IPv4 → ネットワークタイプ → インデックス）で選択
+    #[default]
+    Priority,
+    /// server.url へのデフォルトルートを持つアダプタを選択
+    DefaultRoute,
+}
+
+/// 自動更新の設定
+///
+/// サーバーの配布チャネルから新しいバージョンを取得し、実行ファイルを置き換えます。
+/// 置き換え後の再起動はサービスマネージャー（NSSM）に任せるため、サービスとして実行している場合のみ有効にしてください。
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct UpdateSettings {
+    /// 自動更新を行うか
+    pub enabled: bool,
+    /// 配布チャネル（例: "stable"）
+    pub channel: String,
+    /// 署名の検証に使用する公開鍵（Ed25519、base64url）
+    pub public_key: String,
+    /// 新しいバージョンを確認する間隔（秒）
+    pub check_interval_secs: u64,
+}
+
+impl Default for UpdateSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            channel: "stable".to_string(),
+            public_key: String::new(),
+            check_interval_secs: 21600,
+        }
+    }
+}
+
+impl Default for SoftwareSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            snapshot_file: "software_snapshot.json".to_string(),
+        }
+    }
+}
+
 impl ClientConfig {
     pub fn load(path: &str) -> Result<Self> {
         let contents = fs::read_to_string(path)
@@ -63,10 +177,29 @@ impl ClientConfig {
         Ok(config)
     }
 
-    pub fn save(&self, path: &str) -> Result<()> {
-        let contents = toml::to_string_pretty(self)?;
-        fs::write(path, contents)?;
-        Ok(())
+    /// サーバーから配布された設定を上書き
+    ///
+    /// 指定の無い項目はこの設定（ローカルの config.toml）の値のままとします。
+    /// pc_info.user_name は配布の対象外で、常にローカルの値を使用します。
+    ///
+    /// # 引数
+    /// * `overrides` - サーバーから配布された設定（検証済みであること）
+    pub fn apply_overrides(&mut self, overrides: &ConfigOverrides) {
+        if let Some(secs) = overrides.check_interval_secs {
+            self.client.check_interval_secs = secs;
+        }
+        if let Some(secs) = overrides.send_interval_secs {
+            self.client.send_interval_secs = secs;
+        }
+        if let Some(secs) = overrides.first_retry_delay_secs {
+            self.retry.first_retry_delay_secs = secs;
+        }
+        if let Some(secs) = overrides.second_retry_delay_secs {
+            self.retry.second_retry_delay_secs = secs;
+        }
+        if let Some(level) = &overrides.log_level {
+            self.logging.level.clone_from(level);
+        }
     }
 
     fn validate(&self) -> Result<()> {
@@ -88,15 +221,26 @@ impl ClientConfig {
             ));
         }
 
+        validate_tags(&self.pc_info.tags)
+            .map_err(|e| ClientError::InvalidConfig(format!("pc_info.tags: {}", e)))?;
+
         // インターバルの検証
         if self.client.check_interval_secs == 0 {
             return Err(ClientError::InvalidConfig("check_interval_secs must be greater than 0".to_string()));
         }
 
+        if self.client.state_file.trim().is_empty() {
+            return Err(ClientError::InvalidConfig("client.state_file must not be empty".to_string()));
+        }
+
         if self.client.send_interval_secs == 0 {
             return Err(ClientError::InvalidConfig("send_interval_secs must be greater than 0".to_string()));
         }
 
+        if self.client.watch_network_changes && self.client.network_change_debounce_secs == 0 {
+            return Err(ClientError::InvalidConfig("network_change_debounce_secs must be greater than 0".to_string()));
+        }
+
         // リトライ設定の検証
         if self.retry.first_retry_delay_secs == 0 {
             return Err(ClientError::InvalidConfig("first_retry_delay_secs must be greater than 0".to_string()));
@@ -106,6 +250,28 @@ impl ClientConfig {
             return Err(ClientError::InvalidConfig("second_retry_delay_secs must be greater than 0".to_string()));
         }
 
+        // ソフトウェア収集設定の検証
+        if self.software.enabled && self.software.snapshot_file.trim().is_empty() {
+            return Err(ClientError::InvalidConfig("software.snapshot_file must not be empty".to_string()));
+        }
+
+        // 自動更新設定の検証
+        if self.update.enabled {
+            if self.update.channel.trim().is_empty() {
+                return Err(ClientError::InvalidConfig("update.channel must not be empty".to_string()));
+            }
+
+            if self.update.check_interval_secs == 0 {
+                return Err(ClientError::InvalidConfig("update.check_interval_secs must be greater than 0".to_string()));
+            }
+
+            ReleaseKey::parse(&self.update.public_key)
+                .map_err(|e| ClientError::InvalidConfig(format!("update.public_key: {}", e)))?;
+        }
+
+        // アダプタ絞り込み設定の検証
+        AdapterFilter::from_settings(&self.network)?;
+
         // ログレベルの検証
         let valid_levels = ["trace", "debug", "info", "warn", "error"];
         if !valid_levels.contains(&self.logging.level.as_str()) {
@@ -117,29 +283,4 @@ impl ClientConfig {
 
         Ok(())
     }
-
-    pub fn update_pc_info(&mut self, uuid: String, mac_address: String, network_type: String,
-                          ip_address: String, os: String, os_version: String, model_name: String) {
-        self.pc_info.uuid = uuid;
-        self.pc_info.mac_address = mac_address;
-        self.pc_info.network_type = network_type;
-        self.pc_info.ip_address = ip_address;
-        self.pc_info.os = os;
-        self.pc_info.os_version = os_version;
-        self.pc_info.model_name = model_name;
-    }
-
-    pub fn update_last_send_datetime(&mut self, datetime: String) {
-        self.client.last_send_datetime = datetime;
-    }
-
-    pub fn is_pc_info_complete(&self) -> bool {
-        !self.pc_info.uuid.is_empty()
-            && !self.pc_info.mac_address.is_empty()
-            && !self.pc_info.network_type.is_empty()
-            && !self.pc_info.ip_address.is_empty()
-            && !self.pc_info.os.is_empty()
-            && !self.pc_info.os_version.is_empty()
-            && !self.pc_info.model_name.is_empty()
-    }
 }
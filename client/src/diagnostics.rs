@@ -0,0 +1,465 @@
+//! 診断（diagnose サブコマンド）
+//!
+//! 設定ファイル・WMI の各クエリ・ネットワークインターフェース・サーバーとの通信を個別に確認し、
+//! 結果をレポートにまとめます。ある項目が失敗しても残りの項目は確認を続けるため、
+//! 送信に失敗する原因を1回の実行で切り分けられます。
+//!
+//! レポートは Display で人が読む形式に、Serialize で JSON に出力できます。
+
+use crate::agent;
+use crate::api::{ApiClient, Transport};
+use crate::config::ClientConfig;
+use crate::error::Result;
+use crate::network::{AdapterSelection, NetworkDetector};
+use crate::state::{ClientState, StateStore};
+use crate::wmi::WmiCollector;
+use network_interface::Addr;
+use pc_inventory_protocol::HeartbeatRequest;
+use serde::Serialize;
+use std::fmt;
+
+/// 確認結果の状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    /// 送信は可能だが確認が必要
+    Warning,
+    Failed,
+    /// 前提となる項目が失敗したため確認していない
+    Skipped,
+}
+
+impl Status {
+    fn label(self) -> &'static str {
+        match self {
+            Status::Ok => "OK",
+            Status::Warning => "WARN",
+            Status::Failed => "FAIL",
+            Status::Skipped => "SKIP",
+        }
+    }
+}
+
+/// 1項目の確認結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Check {
+    pub name: String,
+    pub status: Status,
+    /// 取得した値・エラーの内容
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: &str, status: Status, detail: impl Into<String>) -> Self {
+        Self { name: name.to_string(), status, detail: detail.into().trim_end().to_string() }
+    }
+
+    /// 結果が Ok の場合は値の説明、Err の場合はエラーの内容を記録
+    fn from_result<T>(name: &str, result: &Result<T>, describe: impl FnOnce(&T) -> String) -> Self {
+        match result {
+            Ok(value) => Self::new(name, Status::Ok, describe(value)),
+            Err(e) => Self::new(name, Status::Failed, e.to_string()),
+        }
+    }
+}
+
+/// ネットワークインターフェースの確認結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InterfaceReport {
+    pub name: String,
+    pub index: u32,
+    pub mac_address: Option<String>,
+    pub addresses: Vec<String>,
+    /// ネットワークタイプ（内部インターフェースの場合は None）
+    pub network_type: Option<String>,
+    /// UP状態か（内部インターフェースの場合は None）
+    pub is_up: Option<bool>,
+    /// 代表アダプタとして送信されるか
+    pub is_primary: bool,
+    /// 代表アダプタの候補から除外された理由
+    pub rejected: Option<String>,
+}
+
+/// 診断結果
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub version: String,
+    pub build: Option<String>,
+    pub target: String,
+    pub config: Vec<Check>,
+    pub wmi: Vec<Check>,
+    pub network: Vec<Check>,
+    pub interfaces: Vec<InterfaceReport>,
+    pub server: Vec<Check>,
+}
+
+impl Report {
+    /// すべての項目を確認してレポートを作成
+    ///
+    /// サーバーへの送信はハートビート1件のみです（登録済みのPCは最終確認日時が更新されます）。
+    /// 状態ファイルは読み込みのみ行います。
+    ///
+    /// # 引数
+    /// * `config_path` - 設定ファイルのパス
+    pub async fn collect(config_path: &str) -> Self {
+        let loaded = ClientConfig::load(config_path);
+        let mut config_checks = vec![Check::from_result("Configuration file", &loaded, |config| {
+            format!("{} (server: {})", config_path, config.server.url)
+        })];
+        let config = loaded.ok();
+
+        let (selection, state) = match &config {
+            Some(config) => {
+                let (checks, selection, state) = check_config(config);
+                config_checks.extend(checks);
+                (selection, state)
+            }
+            None => {
+                for name in CONFIG_CHECKS {
+                    config_checks.push(Check::new(name, Status::Skipped, "configuration file could not be loaded"));
+                }
+                (None, None)
+            }
+        };
+
+        let (wmi, uuid) = check_wmi();
+        // アダプタ選択ルールが無効な場合も、既定のルールでインターフェースを確認する
+        let (network, interfaces) = check_network(&selection.unwrap_or_default());
+
+        let server = match &config {
+            Some(config) => {
+                let uuid = uuid.or_else(|| state.map(|s| s.pc_info.uuid).filter(|u| !u.is_empty()));
+                let heartbeat = uuid.map(|uuid| HeartbeatRequest { uuid, tags: config.pc_info.tags.clone() });
+                match ApiClient::new(config.server.url.clone(), config.server.request_timeout_secs) {
+                    Ok(mut client) => check_server(&mut client, heartbeat.as_ref()).await,
+                    Err(e) => vec![Check::new("API client", Status::Failed, e.to_string())],
+                }
+            }
+            None => SERVER_CHECKS
+                .iter()
+                .map(|name| Check::new(name, Status::Skipped, "server URL is unknown"))
+                .collect(),
+        };
+
+        Self {
+            version: agent::VERSION.to_string(),
+            build: agent::BUILD_HASH.map(str::to_string),
+            target: agent::TARGET.to_string(),
+            config: config_checks,
+            wmi,
+            network,
+            interfaces,
+            server,
+        }
+    }
+
+    /// 失敗した項目があるか
+    pub fn has_failures(&self) -> bool {
+        self.checks().any(|check| check.status == Status::Failed)
+    }
+
+    fn checks(&self) -> impl Iterator<Item = &Check> {
+        self.config.iter().chain(&self.wmi).chain(&self.network).chain(&self.server)
+    }
+}
+
+/// 設定ファイルを読み込めた場合に確認する項目
+const CONFIG_CHECKS: [&str; 3] = ["User name", "Adapter selection rules", "State file"];
+
+/// サーバーとの通信で確認する項目
+const SERVER_CHECKS: [&str; 2] = ["Server health (GET /api/capabilities)", "Test POST (heartbeat)"];
+
+/// 設定ファイルの内容と状態ファイルを確認
+///
+/// # 戻り値
+/// 確認結果（CONFIG_CHECKS の順）、アダプタ選択ルール、状態ファイルの内容
+fn check_config(config: &ClientConfig) -> (Vec<Check>, Option<AdapterSelection>, Option<ClientState>) {
+    let user_name = if config.pc_info.user_name.is_empty() {
+        Check::new(CONFIG_CHECKS[0], Status::Failed, "pc_info.user_name is not set (the client will not start)")
+    } else {
+        Check::new(CONFIG_CHECKS[0], Status::Ok, config.pc_info.user_name.clone())
+    };
+
+    let selection = AdapterSelection::from_config(config);
+    let selection_check = Check::from_result(CONFIG_CHECKS[1], &selection, |selection| match selection.egress_address {
+        Some(address) => format!("default route (source address: {})", address),
+        None => "priority order".to_string(),
+    });
+
+    let store = StateStore::new(&config.client.state_file);
+    let state = store.load();
+    let state_check = Check::from_result(CONFIG_CHECKS[2], &state, |state| {
+        let last_sent = if state.last_send_datetime.is_empty() { "never" } else { &state.last_send_datetime };
+        format!("{} (last sent: {})", store.path().display(), last_sent)
+    });
+
+    (vec![user_name, selection_check, state_check], selection.ok(), state.ok())
+}
+
+/// WMI の各クエリを実行
+///
+/// # 戻り値
+/// 確認結果と、取得できた場合はPCのUUID
+fn check_wmi() -> (Vec<Check>, Option<String>) {
+    let collector = WmiCollector::new();
+    let mut checks = vec![Check::from_result("WMI connection", &collector, |_| "connected".to_string())];
+
+    let mut uuid = None;
+    match collector {
+        Ok(collector) => {
+            let result = collector.get_uuid();
+            checks.push(Check::from_result("UUID (Win32_ComputerSystemProduct)", &result, String::clone));
+            uuid = result.ok();
+
+            checks.push(Check::from_result("Model name (Win32_ComputerSystem)", &collector.get_model_name(), String::clone));
+            checks.push(Check::from_result("Hardware (Win32_ComputerSystem)", &collector.get_hardware_info(), |hardware| {
+                format!("manufacturer: {}, memory: {} bytes, logical processors: {}",
+                    hardware.manufacturer.as_deref().unwrap_or("unknown"),
+                    hardware.total_memory_bytes.map_or("unknown".to_string(), |m| m.to_string()),
+                    hardware.logical_processors.map_or("unknown".to_string(), |p| p.to_string()))
+            }));
+            checks.push(Check::from_result("OS (Win32_OperatingSystem)", &collector.get_os_info(), |(os, version)| {
+                format!("{} {}", os, version)
+            }));
+            checks.push(Check::from_result("Hotfixes (Win32_QuickFixEngineering)", &collector.get_hotfixes(), |hotfixes| {
+                format!("{} installed", hotfixes.len())
+            }));
+        }
+        Err(_) => {
+            for name in ["UUID (Win32_ComputerSystemProduct)", "Model name (Win32_ComputerSystem)", "Hardware (Win32_ComputerSystem)",
+                "OS (Win32_OperatingSystem)", "Hotfixes (Win32_QuickFixEngineering)"]
+            {
+                checks.push(Check::new(name, Status::Skipped, "WMI connection failed"));
+            }
+        }
+    }
+
+    // WMI 接続を使用しない項目
+    checks.push(Check::from_result("OS build revision (registry UBR)", &WmiCollector::get_os_build_revision(), |ubr| {
+        ubr.map_or("not available".to_string(), |ubr| ubr.to_string())
+    }));
+    checks.push(Check::from_result("Login user (USERNAME)", &WmiCollector::get_user_name(), String::clone));
+
+    (checks, uuid)
+}
+
+/// すべてのネットワークインターフェースと、代表アダプタの候補から除外された理由を確認
+///
+/// # 戻り値
+/// 確認結果と、インターフェースごとの結果（インデックス順）
+fn check_network(selection: &AdapterSelection) -> (Vec<Check>, Vec<InterfaceReport>) {
+    let interfaces = match NetworkDetector::get_all_interfaces() {
+        Ok(interfaces) => interfaces,
+        Err(e) => return (vec![Check::new("Network interfaces", Status::Failed, e.to_string())], Vec::new()),
+    };
+    let adapters = match NetworkDetector::get_all_adapters(selection) {
+        Ok(adapters) => adapters,
+        Err(e) => return (vec![Check::new("Network interfaces", Status::Failed, e.to_string())], Vec::new()),
+    };
+
+    // 環境によってはアドレスごとに別のエントリとして返されるため、名前ごとにまとめる
+    let mut reports: Vec<InterfaceReport> = Vec::new();
+    for iface in &interfaces {
+        let addresses = iface.addr.iter().map(|addr| match addr {
+            Addr::V4(v4) => v4.ip.to_string(),
+            Addr::V6(v6) => v6.ip.to_string(),
+        });
+        if let Some(report) = reports.iter_mut().find(|r| r.name == iface.name) {
+            report.addresses.extend(addresses);
+            continue;
+        }
+
+        let adapter = adapters.iter().find(|a| a.name == iface.name);
+        let rejected = match adapter {
+            Some(adapter) => NetworkDetector::candidate_rejection(adapter, &selection.filter).map(|r| r.to_string()),
+            None => Some("internal interface".to_string()),
+        };
+        reports.push(InterfaceReport {
+            name: iface.name.clone(),
+            index: iface.index,
+            mac_address: adapter.and_then(|a| a.mac_address.clone()).or_else(|| iface.mac_addr.clone()),
+            addresses: addresses.collect(),
+            network_type: adapter.map(|a| a.network_type.to_string()),
+            is_up: adapter.map(|a| a.is_up),
+            is_primary: adapter.is_some_and(|a| a.is_primary),
+            rejected,
+        });
+    }
+    reports.sort_by(|a, b| a.index.cmp(&b.index).then_with(|| a.name.cmp(&b.name)));
+
+    let mut checks = vec![Check::new("Network interfaces", Status::Ok, format!("{} found", reports.len()))];
+    checks.push(match adapters.iter().find(|a| a.is_primary) {
+        Some(primary) => Check::new("Primary adapter", Status::Ok, format!("{} ({})", primary.name, primary.network_type)),
+        None => Check::new("Primary adapter", Status::Failed, "no interface is eligible (see the interface list)"),
+    });
+    (checks, reports)
+}
+
+/// サーバーの対応状況を取得し、ハートビートを送信
+///
+/// # 引数
+/// * `transport` - 送信手段
+/// * `heartbeat` - 送信するハートビート（PCのUUIDが分からない場合は None）
+///
+/// # 戻り値
+/// 確認結果（SERVER_CHECKS の順）
+pub async fn check_server<T: Transport>(transport: &mut T, heartbeat: Option<&HeartbeatRequest>) -> Vec<Check> {
+    let capabilities = match transport.capabilities().await {
+        Ok(capabilities) => capabilities,
+        Err(e) => {
+            return vec![
+                Check::new(SERVER_CHECKS[0], Status::Failed, e.to_string()),
+                Check::new(SERVER_CHECKS[1], Status::Skipped, "server is not reachable"),
+            ];
+        }
+    };
+    let version = capabilities.negotiate();
+    transport.use_api_version(version);
+
+    let versions: Vec<&str> = capabilities.api_versions.iter().map(|v| v.as_str()).collect();
+    let sections: Vec<&str> = capabilities.sections.iter().map(|s| s.as_str()).collect();
+    let health = Check::new(SERVER_CHECKS[0], Status::Ok, format!("API versions: [{}], sections: [{}], using: {}",
+        versions.join(", "), sections.join(", "), version.map_or("unversioned", |v| v.as_str())));
+
+    let post = match heartbeat {
+        None => Check::new(SERVER_CHECKS[1], Status::Skipped, "PC UUID is unknown"),
+        Some(heartbeat) => match transport.send_heartbeat(heartbeat).await {
+            Ok(Some(response)) => Check::new(SERVER_CHECKS[1], Status::Ok,
+                format!("heartbeat accepted (id: {}, last_seen_at: {})", response.id, response.last_seen_at)),
+            Ok(None) => Check::new(SERVER_CHECKS[1], Status::Warning,
+                format!("PC {} is not registered yet (it will be registered on the next full send)", heartbeat.uuid)),
+            Err(e) => Check::new(SERVER_CHECKS[1], Status::Failed, e.to_string()),
+        },
+    };
+
+    vec![health, post]
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "pc-inventory-client {} (build: {}, target: {})",
+            self.version, self.build.as_deref().unwrap_or("unknown"), self.target)?;
+
+        for (title, checks) in [("Configuration", &self.config), ("WMI", &self.wmi), ("Network", &self.network)] {
+            write_section(f, title, checks)?;
+        }
+
+        if !self.interfaces.is_empty() {
+            writeln!(f, "  Interfaces:")?;
+        }
+        for iface in &self.interfaces {
+            let marker = if iface.is_primary { "*" } else { "-" };
+            let state = match (&iface.network_type, iface.is_up) {
+                (Some(network_type), Some(is_up)) => format!("{}, {}", network_type, if is_up { "up" } else { "down" }),
+                _ => "internal".to_string(),
+            };
+            writeln!(f, "    {} {} (index {}, {})", marker, iface.name, iface.index, state)?;
+            writeln!(f, "        MAC: {}", iface.mac_address.as_deref().unwrap_or("none"))?;
+            if !iface.addresses.is_empty() {
+                writeln!(f, "        Addresses: {}", iface.addresses.join(", "))?;
+            }
+            match &iface.rejected {
+                Some(reason) => writeln!(f, "        Rejected: {}", reason)?,
+                None if iface.is_primary => writeln!(f, "        Selected as primary adapter")?,
+                None => writeln!(f, "        Candidate (lower priority than the primary adapter)")?,
+            }
+        }
+
+        write_section(f, "Server", &self.server)
+    }
+}
+
+fn write_section(f: &mut fmt::Formatter<'_>, title: &str, checks: &[Check]) -> fmt::Result {
+    writeln!(f)?;
+    writeln!(f, "{}", title)?;
+    for check in checks {
+        writeln!(f, "  [{:<4}] {}: {}", check.status.label(), check.name, check.detail)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{MemoryTransport, MemoryTransportMode};
+    use pc_inventory_protocol::{NetworkType, PcInfoRequest};
+
+    fn heartbeat(uuid: &str) -> HeartbeatRequest {
+        HeartbeatRequest { uuid: uuid.to_string(), tags: Vec::new() }
+    }
+
+    #[tokio::test]
+    async fn test_check_server() {
+        let mut transport = MemoryTransport::new();
+
+        // 未登録のPC
+        let checks = check_server(&mut transport, Some(&heartbeat("UUID-1"))).await;
+        assert_eq!(checks.iter().map(|c| c.status).collect::<Vec<_>>(), [Status::Ok, Status::Warning]);
+        assert!(checks[0].detail.contains("using: v2"));
+        assert_eq!(transport.heartbeats(), ["UUID-1"]);
+
+        // 登録済みのPC
+        let request = PcInfoRequest {
+            uuid: "UUID-1".to_string(),
+            mac_address: "00:11:22:33:44:55".to_string(),
+            network_type: NetworkType::Wired,
+            user_name: "user".to_string(),
+            ip_address: "10.0.0.5".to_string(),
+            ipv6_address: None,
+            os: "Windows 11 Pro".to_string(),
+            os_version: "10.0.22631".to_string(),
+            model_name: "Model".to_string(),
+            hardware: None,
+            agent: None,
+            tags: Vec::new(),
+        };
+        transport.send_pc_info(&request).await.unwrap();
+        let checks = check_server(&mut transport, Some(&heartbeat("UUID-1"))).await;
+        assert_eq!(checks[1].status, Status::Ok);
+
+        // UUID が分からない場合は送信しない
+        let checks = check_server(&mut transport, None).await;
+        assert_eq!(checks[1].status, Status::Skipped);
+        assert_eq!(transport.heartbeats().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_check_server_unreachable() {
+        let mut transport = MemoryTransport::new();
+        transport.set_mode(MemoryTransportMode::Unreachable);
+
+        let checks = check_server(&mut transport, Some(&heartbeat("UUID-1"))).await;
+        assert_eq!(checks.iter().map(|c| c.status).collect::<Vec<_>>(), [Status::Failed, Status::Skipped]);
+        assert!(transport.heartbeats().is_empty());
+    }
+
+    #[test]
+    fn test_report_display() {
+        let report = Report {
+            version: "1.0.0".to_string(),
+            build: None,
+            target: "x86_64-pc-windows-msvc".to_string(),
+            config: vec![Check::new("Configuration file", Status::Ok, "config.toml")],
+            wmi: vec![Check::new("WMI connection", Status::Failed, "WMI error")],
+            network: Vec::new(),
+            interfaces: vec![InterfaceReport {
+                name: "vEthernet (Default Switch)".to_string(),
+                index: 2,
+                mac_address: Some("00:15:5d:00:00:01".to_string()),
+                addresses: vec!["172.20.0.1".to_string()],
+                network_type: Some("Virtual".to_string()),
+                is_up: Some(true),
+                is_primary: false,
+                rejected: Some("filtered by exclude_interfaces ('vEthernet*')".to_string()),
+            }],
+            server: Vec::new(),
+        };
+
+        let text = report.to_string();
+        assert!(text.contains("  [FAIL] WMI connection: WMI error\n"));
+        assert!(text.contains("    - vEthernet (Default Switch) (index 2, Virtual, up)\n"));
+        assert!(text.contains("        Rejected: filtered by exclude_interfaces ('vEthernet*')\n"));
+        assert!(report.has_failures());
+    }
+}
@@ -1,3 +1,4 @@
+use pc_inventory_protocol::ValidationError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -8,9 +9,18 @@ pub enum ClientError {
     #[error("WMI error: {0}")]
     WmiError(String),
 
+    #[error("Software inventory error: {0}")]
+    SoftwareError(String),
+
+    #[error("Client state error: {0}")]
+    StateError(String),
+
     #[error("Network detection error: {0}")]
     NetworkError(String),
 
+    #[error("Server unreachable: {0}")]
+    ServerUnreachable(String),
+
     #[error("API error: {0}")]
     ApiError(#[from] reqwest::Error),
 
@@ -28,6 +38,24 @@ pub enum ClientError {
 
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
+
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+
+    #[error("Self-update error: {0}")]
+    UpdateError(String),
+
+    #[error("Windows service error: {0}")]
+    ServiceError(String),
+}
+
+impl From<ValidationError> for ClientError {
+    fn from(e: ValidationError) -> Self {
+        match e {
+            ValidationError::MissingField(field) => ClientError::MissingField(field.to_string()),
+            ValidationError::InvalidValue(message) => ClientError::InvalidData(message),
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, ClientError>;
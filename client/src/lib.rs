@@ -0,0 +1,22 @@
+//! PC情報収集クライアント
+//!
+//! PC情報（WMI・ネットワーク・ソフトウェア・更新プログラム）を収集し、サーバーに送信します。
+//! 実行ファイル（main.rs）は設定の読み込みとログの初期化のみを行い、
+//! 送信処理は scheduler::Scheduler に委ねます。
+
+pub mod agent;
+pub mod api;
+pub mod config;
+pub mod diagnostics;
+pub mod error;
+pub mod network;
+pub mod scheduler;
+pub mod service;
+pub mod software;
+pub mod state;
+pub mod storage;
+pub mod systemd;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testing;
+pub mod update;
+pub mod wmi;
@@ -1,389 +1,388 @@
-mod api;
-mod config;
-mod error;
-mod network;
-mod wmi;
-
-use api::{ApiClient, PcInfoData};
-use config::ClientConfig;
-use network::NetworkDetector;
-use wmi::WmiCollector;
-use chrono::Utc;
-use tokio::time::{interval, sleep, Duration};
-use tracing::{info, warn, error, debug};
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-use std::sync::Arc;
-use tokio::sync::Mutex;
-
-/// リトライ状態
-#[derive(Debug, Clone, Copy)]
-enum RetryState {
-    FirstRetry,
-    SecondRetry,
+use clap::{ArgGroup, Args, Parser, Subcommand};
+use pc_inventory_client::agent;
+use pc_inventory_client::api::ApiClient;
+use pc_inventory_client::config::ClientConfig;
+use pc_inventory_client::diagnostics::Report;
+use pc_inventory_client::network::{AdapterSelection, NetworkDetector, NetworkWatcher};
+use pc_inventory_client::scheduler::{Scheduler, SchedulerState, SystemClock, SystemCollector};
+use pc_inventory_client::service::{self, Exit, ServiceDefinition, StopSignal};
+use pc_inventory_client::state::StateStore;
+use pc_inventory_client::systemd::{self, Notification};
+use pc_inventory_client::update::{self, Installer};
+use std::time::Duration;
+use tracing::{info, warn, error};
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::{layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Registry};
+
+/// PC情報収集クライアント（サブコマンドを省略した場合はコンソールで実行）
+#[derive(Parser)]
+#[command(about, disable_version_flag = true, args_conflicts_with_subcommands = true)]
+#[command(group(ArgGroup::new("mode").args(["once", "dry_run", "validate_config", "print_systemd_unit", "version"])))]
+struct Cli {
+    #[command(flatten)]
+    options: RunOptions,
+
+    /// 設定ファイルを検証して終了
+    #[arg(long)]
+    validate_config: bool,
+
+    /// systemd のユニットファイルを出力して終了（Linux）
+    #[arg(long)]
+    print_systemd_unit: bool,
+
+    /// バージョンを表示して終了
+    #[arg(short = 'V', long)]
+    version: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // 設定ファイルのパス（環境変数またはデフォルト）
-    let config_path = std::env::var("CONFIG_PATH").unwrap_or_else(|_| "config.toml".to_string());
+/// クライアントの実行方法
+#[derive(Args, Clone)]
+struct RunOptions {
+    /// 設定ファイルのパス
+    #[arg(long, value_name = "PATH", env = "CONFIG_PATH", default_value = "config.toml", global = true)]
+    config: String,
 
-    // 設定読み込み
-    let mut config = ClientConfig::load(&config_path)?;
-
-    // ログ初期化
-    init_logging(&config);
-    info!("PC Inventory Client starting...");
-    info!("Configuration loaded from: {}", config_path);
-    info!("Server URL: {}", config.server.url);
-
-    // ユーザー名チェック
-    if config.pc_info.user_name.is_empty() {
-        error!("User name is not set in config.toml");
-        error!("Please set your name in the [pc_info] section and restart the client");
-        return Err("User name is required".into());
-    }
-
-    // リトライ中フラグ（スレッド間で共有）
-    let is_retrying = Arc::new(Mutex::new(false));
+    /// ログレベル（config.toml・環境変数 RUST_LOG より優先）
+    #[arg(long, value_name = "LEVEL", value_parser = ["trace", "debug", "info", "warn", "error"], global = true)]
+    log_level: Option<String>,
 
-    // 起動時処理
-    if let Err(e) = initial_process(&mut config, &config_path).await {
-        error!("Initial process failed: {}", e);
-        // 送信失敗時はリトライサイクルを開始
-        start_retry_cycle(is_retrying.clone(), config_path.clone()).await;
-    }
+    /// 1回だけ収集・送信して終了（送信に失敗した場合は終了コード 1）
+    #[arg(long)]
+    once: bool,
 
-    // 定期チェックタイマー
-    info!("Starting periodic check timer (interval: {}s)", config.client.check_interval_secs);
-    let mut check_timer = interval(Duration::from_secs(config.client.check_interval_secs));
+    /// 送信内容を収集して表示し、送信せずに終了
+    #[arg(long)]
+    dry_run: bool,
+}
 
-    loop {
-        check_timer.tick().await;
-        debug!("Periodic check triggered");
+#[derive(Subcommand)]
+enum Command {
+    /// Windowsサービスとして登録して開始（管理者権限が必要）
+    Install,
+    /// Windowsサービスを停止して削除（管理者権限が必要）
+    Uninstall,
+    /// 設定・WMI・ネットワーク・サーバーとの通信を確認して結果を表示（失敗した項目がある場合は終了コード 1）
+    Diagnose {
+        /// 結果を JSON で出力
+        #[arg(long)]
+        json: bool,
+    },
+    /// サービス制御マネージャーからの起動（install で登録され、直接は使用しない）
+    #[command(name = service::RUN_SERVICE_COMMAND, hide = true)]
+    RunService,
+}
 
-        // 設定を再読み込み（変更を反映）
-        match ClientConfig::load(&config_path) {
-            Ok(new_config) => {
-                config = new_config;
-                debug!("Configuration reloaded");
-            }
-            Err(e) => {
-                warn!("Failed to reload configuration: {}", e);
-                // エラーでも既存の設定で継続
-            }
+fn main() {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Some(Command::Install) => install(),
+        Some(Command::Uninstall) => uninstall(),
+        Some(Command::Diagnose { json }) => diagnose(&cli.options, json),
+        Some(Command::RunService) => run_as_service(cli.options),
+        None if cli.version => {
+            print_version();
+            Ok(())
         }
-
-        // 送信が必要かチェック
-        if should_send(&config).await {
-            info!("Send interval elapsed, sending PC info");
-            if let Err(e) = periodic_check(&mut config, &config_path).await {
-                error!("Periodic check failed: {}", e);
-                // 送信失敗時はリトライサイクルを開始
-                start_retry_cycle(is_retrying.clone(), config_path.clone()).await;
+        None if cli.print_systemd_unit => print_systemd_unit(),
+        None if cli.validate_config => validate_config(&cli.options.config),
+        None if cli.options.dry_run => dry_run(&cli.options),
+        None => {
+            let exit = run(&cli.options, StopSignal::new());
+            if exit == Exit::Restart {
+                // 実行ファイルを置き換えた（または元に戻した）ため、サービスマネージャーに再起動させる
+                info!("Exiting with code {} to restart", update::RESTART_EXIT_CODE);
             }
-        } else {
-            debug!("Send interval not elapsed yet, skipping");
+            std::process::exit(exit.code());
         }
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(Exit::Failed.code());
     }
 }
 
-/// ログ初期化
-fn init_logging(config: &ClientConfig) {
-    let file_appender = tracing_appender::rolling::never(".", &config.logging.file);
-
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| config.logging.level.clone().into()),
-        )
-        .with(tracing_subscriber::fmt::layer().with_writer(file_appender))
-        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stdout))
-        .init();
+/// バージョン・ビルド元のコミット・ビルド対象を表示
+fn print_version() {
+    println!("pc-inventory-client {} (build: {}, target: {})",
+        agent::VERSION, agent::BUILD_HASH.unwrap_or("unknown"), agent::TARGET);
 }
 
-/// 起動時処理
+/// 設定ファイルを検証
 ///
-/// WMI情報とネットワーク情報を取得し、設定ファイルを更新してサーバーに送信します。
-async fn initial_process(config: &mut ClientConfig, config_path: &str) -> Result<(), Box<dyn std::error::Error>> {
-    info!("Running initial process");
-
-    // WMI情報取得
-    info!("Collecting WMI information");
-    let wmi_collector = WmiCollector::new()?;
-    let wmi_data = wmi_collector.collect_all()?;
-
-    info!("WMI information collected:");
-    info!("  UUID: {}", wmi_data.uuid);
-    info!("  Model: {}", wmi_data.model_name);
-    info!("  OS: {} ({})", wmi_data.os, wmi_data.os_version);
-    info!("  User: {}", wmi_data.user_name);
-
-    // ネットワーク情報取得
-    info!("Detecting network information");
-    let network_info = NetworkDetector::get_active_adapter()?;
-
-    info!("Network information detected:");
-    info!("  IP: {}", network_info.ip_address);
-    info!("  MAC: {}", network_info.mac_address);
-    info!("  Type: {}", network_info.network_type);
-
-    // 設定ファイルを更新
-    config.update_pc_info(
-        wmi_data.uuid.clone(),
-        network_info.mac_address.clone(),
-        network_info.network_type.clone(),
-        network_info.ip_address.clone(),
-        wmi_data.os.clone(),
-        wmi_data.os_version.clone(),
-        wmi_data.model_name.clone(),
-    );
-
-    // 設定ファイルを保存
-    config.save(config_path)?;
-    info!("Configuration updated and saved");
-
-    // サーバーに送信
-    send_to_server(config, config_path).await?;
-
+/// 起動時と同じ検証に加えて、アダプタ選択ルールを確認します。
+fn validate_config(config_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let config = ClientConfig::load(config_path)?;
+    AdapterSelection::from_config(&config)?;
+
+    println!("Configuration OK: {}", config_path);
+    println!("  Server URL: {}", config.server.url);
+    println!("  User name: {}", config.pc_info.user_name);
+    println!("  State file: {}", config.client.state_file);
     Ok(())
 }
 
-/// 定期チェック処理
+/// 送信内容を収集して JSON で表示（送信・状態ファイルへの保存は行わない）
 ///
-/// ネットワーク情報を再取得し、サーバーに送信します。
-async fn periodic_check(config: &mut ClientConfig, config_path: &str) -> Result<(), Box<dyn std::error::Error>> {
-    info!("Running periodic check");
-
-    // ネットワーク情報を再取得
-    let network_info = NetworkDetector::get_active_adapter()?;
-
-    debug!("Network information updated:");
-    debug!("  IP: {}", network_info.ip_address);
-    debug!("  MAC: {}", network_info.mac_address);
-    debug!("  Type: {}", network_info.network_type);
+/// ログは標準出力に混ざらないよう標準エラーに出力します。
+fn dry_run(options: &RunOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let config = ClientConfig::load(&options.config)?;
+    init_logging(&config, options.log_level.as_deref(), Console::Stderr);
 
-    // 設定ファイルのネットワーク情報を更新
-    config.pc_info.ip_address = network_info.ip_address;
-    config.pc_info.mac_address = network_info.mac_address;
-    config.pc_info.network_type = network_info.network_type;
+    // 状態ファイルは読み込みのみ（配布された設定の反映に使用）
+    let store = StateStore::new(&config.client.state_file);
+    let state = store.load()?;
 
-    // サーバーに送信
-    send_to_server(config, config_path).await?;
+    let scheduler = Scheduler::new(config, store, state, SystemClock, SystemCollector, |config: &ClientConfig| {
+        ApiClient::new(config.server.url.clone(), config.server.request_timeout_secs)
+    });
+    let runtime = tokio::runtime::Runtime::new()?;
+    let payload = runtime.block_on(scheduler.preview())?;
 
+    println!("{}", serde_json::to_string_pretty(&payload)?);
     Ok(())
 }
 
-/// サーバーに送信
-async fn send_to_server(config: &mut ClientConfig, config_path: &str) -> Result<(), Box<dyn std::error::Error>> {
-    // PC情報が完全でない場合はスキップ
-    if !config.is_pc_info_complete() {
-        warn!("PC information is incomplete, skipping send");
-        return Ok(());
+/// 設定・WMI・ネットワーク・サーバーとの通信を確認して結果を表示
+///
+/// ログファイルには出力せず、--log-level を指定した場合のみ標準エラーに出力します。
+fn diagnose(options: &RunOptions, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(level) = &options.log_level {
+        tracing_subscriber::fmt().with_env_filter(EnvFilter::new(level)).with_writer(std::io::stderr).init();
     }
 
-    // APIクライアント作成
-    let api_client = ApiClient::new(
-        config.server.url.clone(),
-        config.server.request_timeout_secs,
-    )?;
-
-    // 送信データ作成
-    let data = PcInfoData {
-        uuid: config.pc_info.uuid.clone(),
-        mac_address: config.pc_info.mac_address.clone(),
-        network_type: config.pc_info.network_type.clone(),
-        user_name: config.pc_info.user_name.clone(),
-        ip_address: config.pc_info.ip_address.clone(),
-        os: config.pc_info.os.clone(),
-        os_version: config.pc_info.os_version.clone(),
-        model_name: config.pc_info.model_name.clone(),
-    };
+    let runtime = tokio::runtime::Runtime::new()?;
+    let report = runtime.block_on(Report::collect(&options.config));
 
-    // データ検証
-    data.validate()?;
-
-    // 送信
-    info!("Sending PC information to server");
-    let response = api_client.send_pc_info(&data).await?;
-
-    info!("Server response: {} (action: {}, id: {})",
-        response.status, response.action, response.id);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", report);
+    }
+    if report.has_failures() {
+        std::process::exit(Exit::Failed.code());
+    }
+    Ok(())
+}
 
-    // 最終送信日時を更新
-    let now = Utc::now().to_rfc3339();
-    config.update_last_send_datetime(now);
-    config.save(config_path)?;
+/// 現在の実行ファイルをWindowsサービスとして登録して開始
+fn install() -> Result<(), Box<dyn std::error::Error>> {
+    let definition = ServiceDefinition::for_executable(std::env::current_exe()?);
+    service::system_manager()?.install(&definition)?;
+    println!("Service {} installed and started ({})", definition.name, definition.executable.display());
+    Ok(())
+}
 
-    info!("Last send datetime updated in config");
+/// Windowsサービスを停止して削除
+fn uninstall() -> Result<(), Box<dyn std::error::Error>> {
+    service::system_manager()?.uninstall(service::SERVICE_NAME)?;
+    println!("Service {} uninstalled", service::SERVICE_NAME);
+    Ok(())
+}
 
+/// 現在の実行ファイルを起動する systemd のユニットファイルを出力
+fn print_systemd_unit() -> Result<(), Box<dyn std::error::Error>> {
+    let unit = systemd::unit_file(std::env::current_exe()?.canonicalize()?);
+    print!("{}", unit.render());
+    eprintln!("Save the output to /etc/systemd/system/{} and run: systemctl enable --now {}",
+        systemd::UNIT_NAME, systemd::UNIT_NAME);
     Ok(())
 }
 
-/// 送信が必要かチェック
-///
-/// 最終送信日時からsend_interval_secsが経過しているか確認します。
-async fn should_send(config: &ClientConfig) -> bool {
-    // 最終送信日時が空の場合は送信必要
-    if config.client.last_send_datetime.is_empty() {
-        debug!("No last send datetime, should send");
-        return true;
+/// サービス制御マネージャーから起動された場合の実行
+fn run_as_service(options: RunOptions) -> Result<(), Box<dyn std::error::Error>> {
+    // サービスはシステムディレクトリで起動されるため、config.toml・ログ・状態ファイルの
+    // 相対パスの基準を実行ファイルのディレクトリに合わせる
+    let executable = std::env::current_exe()?;
+    if let Some(dir) = executable.parent() {
+        std::env::set_current_dir(dir)?;
     }
 
-    // 最終送信日時をパース
-    match chrono::DateTime::parse_from_rfc3339(&config.client.last_send_datetime) {
-        Ok(last_send) => {
-            let now = Utc::now();
-            let elapsed = now.signed_duration_since(last_send.with_timezone(&Utc));
-            let elapsed_secs = elapsed.num_seconds();
-
-            debug!("Last send: {} ({} seconds ago)", last_send, elapsed_secs);
+    service::run_dispatcher(move |stop| run(&options, stop))?;
+    Ok(())
+}
 
-            elapsed_secs >= config.client.send_interval_secs as i64
+/// クライアントを実行（停止要求・Ctrl+C・再起動要求まで戻らない。--once の場合は1回の送信で戻る）
+fn run(options: &RunOptions, stop: StopSignal) -> Exit {
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Error: Failed to start async runtime: {}", e);
+            return Exit::Failed;
         }
+    };
+
+    match runtime.block_on(run_client(options, stop)) {
+        Ok(exit) => exit,
         Err(e) => {
-            warn!("Failed to parse last send datetime: {}", e);
-            // パースエラーの場合は送信する
-            true
+            error!("Client failed to start: {}", e);
+            eprintln!("Error: {}", e);
+            Exit::Failed
         }
     }
 }
 
-/// リトライサイクルを開始
-///
-/// 既にリトライ中でない場合のみ、新しいリトライタスクをspawnします。
-async fn start_retry_cycle(is_retrying: Arc<Mutex<bool>>, config_path: String) {
-    let mut retrying = is_retrying.lock().await;
-
-    // 既にリトライ中の場合は何もしない
-    if *retrying {
-        debug!("Already retrying, skipping new retry cycle");
-        return;
-    }
+async fn run_client(options: &RunOptions, stop: StopSignal) -> Result<Exit, Box<dyn std::error::Error>> {
+    // 設定読み込み
+    let config_path = options.config.clone();
+    let config = ClientConfig::load(&config_path)?;
 
-    // リトライ開始
-    *retrying = true;
-    drop(retrying); // ロックを解放
+    // ログ初期化
+    let log_filter = init_logging(&config, options.log_level.as_deref(), Console::Stdout);
+    info!("PC Inventory Client starting... (version: {}, build: {})",
+        agent::VERSION, agent::BUILD_HASH.unwrap_or("unknown"));
+    info!("Configuration loaded from: {}", config_path);
+    info!("Server URL: {}", config.server.url);
 
-    info!("Starting retry cycle");
+    // ユーザー名チェック
+    if config.pc_info.user_name.is_empty() {
+        error!("User name is not set in config.toml");
+        error!("Please set your name in the [pc_info] section and restart the client");
+        return Err("User name is required".into());
+    }
 
-    // リトライタスクをspawn
-    let is_retrying_clone = is_retrying.clone();
-    tokio::spawn(async move {
-        handle_retry_cycle(is_retrying_clone, config_path).await;
+    // 実行時状態の読み込み（旧バージョンの config.toml からの移行を含む）
+    // 状態ファイルへの書き込みはすべてこのインスタンスを経由する
+    let store = StateStore::new(&config.client.state_file);
+    let state = store.load_or_migrate(&config_path)?;
+    info!("Client state loaded from: {}", store.path().display());
+
+    // アダプタ選択ルールの確認（ログ出力のみ、名前解決を伴うためブロッキング処理用のスレッドで実行）
+    let selection_config = config.clone();
+    let preview = tokio::task::spawn_blocking(move || match AdapterSelection::from_config(&selection_config) {
+        Ok(selection) => {
+            if let Err(e) = NetworkDetector::preview_filter(&selection) {
+                warn!("Failed to preview adapter filter rules: {}", e);
+            }
+        }
+        Err(e) => warn!("Invalid adapter selection rules: {}", e),
     });
-}
+    if let Err(e) = preview.await {
+        warn!("Failed to preview adapter filter rules: {}", e);
+    }
 
-/// リトライサイクル処理
-///
-/// first_retry_delay_secs → second_retry_delay_secs を交互に繰り返します。
-async fn handle_retry_cycle(is_retrying: Arc<Mutex<bool>>, config_path: String) {
-    let mut state = RetryState::FirstRetry;
-
-    loop {
-        // 設定を読み込み
-        let config = match ClientConfig::load(&config_path) {
-            Ok(c) => c,
-            Err(e) => {
-                error!("Failed to load config during retry: {}", e);
-                sleep(Duration::from_secs(60)).await; // エラー時は1分待機
-                continue;
+    // 1回だけ送信（ネットワーク変更通知・自動更新は使用せず、試用中の更新の確定・取り消しは常駐しているクライアントに任せる）
+    if options.once {
+        let mut scheduler = Scheduler::new(config, store, state, SystemClock, SystemCollector, |config: &ClientConfig| {
+            ApiClient::new(config.server.url.clone(), config.server.request_timeout_secs)
+        });
+        return Ok(match scheduler.step().await {
+            SchedulerState::Idle => {
+                info!("Sent once, exiting");
+                Exit::Stopped
             }
-        };
-
-        // 待機時間を決定
-        let delay_secs = match state {
-            RetryState::FirstRetry => config.retry.first_retry_delay_secs,
-            RetryState::SecondRetry => config.retry.second_retry_delay_secs,
-        };
-
-        info!("Retry scheduled in {} seconds (state: {:?})", delay_secs, state);
-        sleep(Duration::from_secs(delay_secs)).await;
-
-        // リトライ送信
-        info!("Attempting retry send (state: {:?})", state);
-
-        match retry_send(&config, &config_path).await {
-            Ok(_) => {
-                info!("Retry send successful, exiting retry cycle");
-                // 成功したのでリトライフラグをfalseに
-                let mut retrying = is_retrying.lock().await;
-                *retrying = false;
-                break;
+            SchedulerState::Restart => Exit::Restart,
+            state => {
+                error!("Send failed ({:?}), exiting", state);
+                Exit::Failed
             }
+        });
+    }
+
+    // ネットワーク変更通知の購読
+    let network_watcher = if config.client.watch_network_changes {
+        match NetworkWatcher::start(Duration::from_secs(config.client.network_change_debounce_secs)) {
+            Ok(watcher) => Some(watcher),
             Err(e) => {
-                error!("Retry send failed: {}", e);
-                // 次の状態に遷移
-                state = match state {
-                    RetryState::FirstRetry => RetryState::SecondRetry,
-                    RetryState::SecondRetry => RetryState::FirstRetry,
-                };
+                warn!("Network change notifications unavailable, relying on periodic check: {}", e);
+                None
             }
         }
+    } else {
+        None
+    };
+
+    // 自動更新（無効の場合も、試用中の更新の確定・取り消しに使用する）
+    let installer = match Installer::current_exe() {
+        Ok(installer) => Some(installer),
+        Err(e) => {
+            warn!("Self-update unavailable (cannot locate executable): {}", e);
+            None
+        }
+    };
+    if config.update.enabled {
+        info!("Self-update enabled (channel: {}, target: {})", config.update.channel, agent::TARGET);
     }
-}
 
-/// リトライ送信
-///
-/// ネットワーク情報を再取得してサーバーに送信します。
-async fn retry_send(_config: &ClientConfig, config_path: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    info!("Collecting information for retry send");
-
-    // ネットワーク情報を再取得
-    let network_info = NetworkDetector::get_active_adapter()?;
-
-    debug!("Network information updated:");
-    debug!("  IP: {}", network_info.ip_address);
-    debug!("  MAC: {}", network_info.mac_address);
-    debug!("  Type: {}", network_info.network_type);
-
-    // 設定を再読み込みして最新の情報を取得
-    let mut config = ClientConfig::load(config_path)?;
-
-    // ネットワーク情報を更新
-    config.pc_info.ip_address = network_info.ip_address;
-    config.pc_info.mac_address = network_info.mac_address;
-    config.pc_info.network_type = network_info.network_type;
-
-    // PC情報が完全でない場合はエラー
-    if !config.is_pc_info_complete() {
-        warn!("PC information is incomplete, cannot retry send");
-        return Err("PC information is incomplete".into());
+    info!("Starting scheduler (check interval: {}s)", config.client.check_interval_secs);
+    let progress = systemd::Progress::new();
+    let mut scheduler = Scheduler::new(config, store, state, SystemClock, SystemCollector, |config: &ClientConfig| {
+        ApiClient::new(config.server.url.clone(), config.server.request_timeout_secs)
+    })
+    .with_config_reload(config_path)
+    .with_network_watcher(network_watcher)
+    .with_self_update(installer)
+    .with_progress(progress.clone());
+    if let Some(log_filter) = log_filter {
+        // 設定ファイルの再読み込み・サーバーから配布された設定によるログレベルの変更を反映
+        scheduler = scheduler.with_log_reload(move |level| {
+            if let Err(e) = log_filter.reload(EnvFilter::new(level)) {
+                warn!("Failed to change log level to {}: {}", level, e);
+            }
+        });
     }
 
-    // APIクライアント作成
-    let api_client = ApiClient::new(
-        config.server.url.clone(),
-        config.server.request_timeout_secs,
-    )?;
-
-    // 送信データ作成
-    let data = PcInfoData {
-        uuid: config.pc_info.uuid.clone(),
-        mac_address: config.pc_info.mac_address.clone(),
-        network_type: config.pc_info.network_type.clone(),
-        user_name: config.pc_info.user_name.clone(),
-        ip_address: config.pc_info.ip_address.clone(),
-        os: config.pc_info.os.clone(),
-        os_version: config.pc_info.os_version.clone(),
-        model_name: config.pc_info.model_name.clone(),
-    };
+    // コンソールでの実行時は Ctrl+C、systemd での実行時は SIGTERM で終了する
+    let signal_stop = stop.clone();
+    tokio::spawn(async move {
+        systemd::shutdown_signal().await;
+        signal_stop.request();
+    });
 
-    // データ検証
-    data.validate()?;
+    // 設定・状態ファイルを読み込めた時点で起動完了とする
+    systemd::notify(Notification::Ready);
+    // スケジューラの状態遷移が期限内に進んでいる間だけウォッチドッグに通知する
+    systemd::spawn_watchdog(move || std::future::ready(!progress.is_stalled()));
 
-    // 送信
-    info!("Sending PC information to server (retry)");
-    let response = api_client.send_pc_info(&data).await?;
+    let exit = service::run_until_stopped(&stop, scheduler.run()).await;
+    systemd::notify(Notification::Stopping);
+    Ok(exit)
+}
 
-    info!("Server response: {} (action: {}, id: {})",
-        response.status, response.action, response.id);
+/// コンソールへのログの出力先
+enum Console {
+    Stdout,
+    /// 標準出力を送信内容の表示に使用する場合（--dry-run）
+    Stderr,
+}
 
-    // 最終送信日時を更新
-    let now = Utc::now().to_rfc3339();
-    config.update_last_send_datetime(now);
-    config.save(config_path)?;
+/// ログを初期化
+///
+/// systemd のサービスとして起動された場合は、コンソールの代わりに journald に出力します。
+///
+/// # 引数
+/// * `level` - コマンドラインで指定したログレベル（環境変数 RUST_LOG・config.toml より優先）
+/// * `console` - コンソールへのログの出力先
+///
+/// # 戻り値
+/// ログレベルを変更するためのハンドル（コマンドライン・環境変数 RUST_LOG で指定した場合は変更しないため None）
+fn init_logging(config: &ClientConfig, level: Option<&str>, console: Console) -> Option<reload::Handle<EnvFilter, Registry>> {
+    let file_appender = tracing_appender::rolling::never(".", &config.logging.file);
 
-    info!("Last send datetime updated in config");
+    let fixed_filter = level.map(EnvFilter::new).or_else(|| EnvFilter::try_from_default_env().ok());
+    let reloadable = fixed_filter.is_none();
+    let (filter, handle) =
+        reload::Layer::new(fixed_filter.unwrap_or_else(|| EnvFilter::new(&config.logging.level)));
 
-    Ok(())
+    let journald = systemd::journald_layer();
+    let console = journald.is_none().then(|| {
+        let writer = match console {
+            Console::Stdout => BoxMakeWriter::new(std::io::stdout),
+            Console::Stderr => BoxMakeWriter::new(std::io::stderr),
+        };
+        tracing_subscriber::fmt::layer().with_writer(writer)
+    });
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer().with_writer(file_appender))
+        .with(journald)
+        .with(console)
+        .init();
+
+    reloadable.then_some(handle)
 }
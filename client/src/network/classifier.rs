@@ -0,0 +1,351 @@
+use crate::network::NetworkType;
+use std::collections::HashMap;
+
+/// インターフェース種別の判定構造体
+///
+/// インターフェース名ではなく、OSが管理するインターフェース種別から
+/// ネットワークタイプを判定します。
+/// * Windows: WMI の Win32_NetworkAdapter（NDISメディア種別・物理アダプタか）と
+///   MSFT_NetAdapter（IANA ifType・NDIS物理メディア）
+/// * Linux: /sys/class/net/<name>/ 配下の type・wireless・uevent など
+///
+/// Windows では生成時にWMIを一度だけ照会し、インターフェースインデックスごとの結果を保持します。
+#[derive(Debug, Default)]
+pub struct InterfaceClassifier {
+    /// インターフェースインデックスごとの判定結果（Windowsのみ使用）
+    #[cfg_attr(not(windows), allow(dead_code))]
+    by_index: HashMap<u32, NetworkType>,
+}
+
+/// Windows のアダプタ属性（WMIから取得）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WindowsAdapterAttributes {
+    /// IANA ifType（MSFT_NetAdapter.InterfaceType）
+    pub if_type: Option<u32>,
+    /// NDIS物理メディア（MSFT_NetAdapter.NdisPhysicalMedium）
+    pub ndis_physical_medium: Option<u32>,
+    /// NDISメディア種別（Win32_NetworkAdapter.AdapterTypeId）
+    pub adapter_type_id: Option<u16>,
+    /// 物理アダプタか（Win32_NetworkAdapter.PhysicalAdapter）
+    pub physical_adapter: Option<bool>,
+}
+
+/// Linux のインターフェース属性（/sys/class/net/<name>/ から取得）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SysfsAttributes {
+    /// ARPHRD_* の値（type）
+    pub arphrd: Option<u32>,
+    /// uevent の DEVTYPE（例: "wlan", "wwan", "bridge", "wireguard"）
+    pub devtype: Option<String>,
+    /// wireless ディレクトリが存在するか
+    pub wireless: bool,
+    /// device リンクが存在するか（物理デバイスに紐づくか）
+    pub has_device: bool,
+    /// tun_flags が存在するか（TUN/TAPデバイス）
+    pub tun: bool,
+}
+
+// IANA ifType
+const IF_TYPE_ETHERNET_CSMACD: u32 = 6;
+const IF_TYPE_PPP: u32 = 23;
+const IF_TYPE_PROP_VIRTUAL: u32 = 53;
+const IF_TYPE_IEEE80211: u32 = 71;
+const IF_TYPE_TUNNEL: u32 = 131;
+const IF_TYPE_WWANPP: u32 = 243;
+const IF_TYPE_WWANPP2: u32 = 244;
+
+// NDIS_PHYSICAL_MEDIUM
+const NDIS_PHYSICAL_MEDIUM_WIRELESS_LAN: u32 = 1;
+const NDIS_PHYSICAL_MEDIUM_WIRELESS_WAN: u32 = 8;
+const NDIS_PHYSICAL_MEDIUM_NATIVE_802_11: u32 = 9;
+
+// Win32_NetworkAdapter.AdapterTypeId
+const ADAPTER_TYPE_ETHERNET_802_3: u16 = 0;
+const ADAPTER_TYPE_WAN: u16 = 3;
+const ADAPTER_TYPE_WIRELESS: u16 = 9;
+
+// ARPHRD_*（linux/if_arp.h）
+const ARPHRD_ETHER: u32 = 1;
+const ARPHRD_PPP: u32 = 512;
+const ARPHRD_RAWIP: u32 = 519;
+const ARPHRD_TUNNEL: u32 = 768;
+const ARPHRD_TUNNEL6: u32 = 769;
+const ARPHRD_LOOPBACK: u32 = 772;
+const ARPHRD_SIT: u32 = 776;
+const ARPHRD_IPGRE: u32 = 778;
+const ARPHRD_IP6GRE: u32 = 823;
+const ARPHRD_NONE: u32 = 65534;
+
+impl InterfaceClassifier {
+    /// OSからインターフェース種別の情報を読み込んで判定器を生成
+    ///
+    /// WMIの照会に失敗した場合は警告を出力し、すべて Unknown と判定します。
+    pub fn load() -> Self {
+        #[cfg(windows)]
+        {
+            match wmi_query::query_adapter_attributes() {
+                Ok(attributes) => {
+                    let by_index = attributes
+                        .into_iter()
+                        .map(|(index, attrs)| (index, Self::classify_windows(&attrs)))
+                        .collect();
+                    return Self { by_index };
+                }
+                Err(e) => tracing::warn!("Failed to query network adapter types from WMI: {}", e),
+            }
+        }
+
+        Self::default()
+    }
+
+    /// インターフェースのネットワークタイプを判定
+    ///
+    /// # 引数
+    /// * `name` - インターフェース名（Linuxで /sys/class/net の参照に使用）
+    /// * `index` - インターフェースインデックス（WindowsでWMIの結果との照合に使用）
+    pub fn classify(&self, name: &str, index: u32) -> NetworkType {
+        #[cfg(windows)]
+        {
+            let _ = name;
+            self.by_index.get(&index).copied().unwrap_or_default()
+        }
+        #[cfg(target_os = "linux")]
+        {
+            let _ = index;
+            Self::classify_sysfs(&SysfsAttributes::read(name))
+        }
+        #[cfg(not(any(windows, target_os = "linux")))]
+        {
+            let _ = (name, index);
+            NetworkType::Unknown
+        }
+    }
+
+    /// Windows のアダプタ属性からネットワークタイプを判定
+    ///
+    /// IANA ifType と NDIS物理メディアを優先し、
+    /// 取得できない場合は Win32_NetworkAdapter の AdapterTypeId で判定します。
+    /// Ethernet 系のうち物理アダプタでないもの（Hyper-V の vEthernet など）は Virtual とします。
+    #[cfg_attr(not(windows), allow(dead_code))]
+    pub fn classify_windows(attrs: &WindowsAdapterAttributes) -> NetworkType {
+        if matches!(attrs.if_type, Some(IF_TYPE_IEEE80211))
+            || matches!(
+                attrs.ndis_physical_medium,
+                Some(NDIS_PHYSICAL_MEDIUM_WIRELESS_LAN | NDIS_PHYSICAL_MEDIUM_NATIVE_802_11)
+            )
+        {
+            return NetworkType::Wireless;
+        }
+
+        if matches!(attrs.if_type, Some(IF_TYPE_WWANPP | IF_TYPE_WWANPP2))
+            || attrs.ndis_physical_medium == Some(NDIS_PHYSICAL_MEDIUM_WIRELESS_WAN)
+        {
+            return NetworkType::Cellular;
+        }
+
+        match attrs.if_type {
+            Some(IF_TYPE_TUNNEL | IF_TYPE_PPP | IF_TYPE_PROP_VIRTUAL) => return NetworkType::Vpn,
+            Some(IF_TYPE_ETHERNET_CSMACD) => return Self::wired_or_virtual(attrs.physical_adapter),
+            _ => {}
+        }
+
+        match attrs.adapter_type_id {
+            Some(ADAPTER_TYPE_WIRELESS) => NetworkType::Wireless,
+            Some(ADAPTER_TYPE_WAN) => NetworkType::Vpn,
+            Some(ADAPTER_TYPE_ETHERNET_802_3) => Self::wired_or_virtual(attrs.physical_adapter),
+            _ => NetworkType::Unknown,
+        }
+    }
+
+    /// Ethernet 系アダプタを物理アダプタかどうかで Wired / Virtual に振り分け
+    fn wired_or_virtual(physical_adapter: Option<bool>) -> NetworkType {
+        match physical_adapter {
+            Some(false) => NetworkType::Virtual,
+            _ => NetworkType::Wired,
+        }
+    }
+
+    /// Linux のインターフェース属性からネットワークタイプを判定
+    ///
+    /// * wireless ディレクトリがある、または DEVTYPE=wlan → Wireless
+    /// * DEVTYPE=wwan、または ARPHRD_RAWIP → Cellular
+    /// * TUN/TAP、DEVTYPE=wireguard、PPP・トンネル系の ARPHRD → VPN
+    /// * ARPHRD_ETHER で物理デバイスに紐づく → Wired、紐づかない（bridge, veth など）→ Virtual
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    pub fn classify_sysfs(attrs: &SysfsAttributes) -> NetworkType {
+        let devtype = attrs.devtype.as_deref();
+
+        if attrs.wireless || devtype == Some("wlan") {
+            return NetworkType::Wireless;
+        }
+        if devtype == Some("wwan") || attrs.arphrd == Some(ARPHRD_RAWIP) {
+            return NetworkType::Cellular;
+        }
+        if attrs.tun || devtype == Some("wireguard") {
+            return NetworkType::Vpn;
+        }
+
+        match attrs.arphrd {
+            Some(
+                ARPHRD_PPP | ARPHRD_TUNNEL | ARPHRD_TUNNEL6 | ARPHRD_SIT | ARPHRD_IPGRE | ARPHRD_IP6GRE
+                | ARPHRD_NONE,
+            ) => NetworkType::Vpn,
+            Some(ARPHRD_LOOPBACK) => NetworkType::Virtual,
+            Some(ARPHRD_ETHER) if attrs.has_device => NetworkType::Wired,
+            Some(ARPHRD_ETHER) => NetworkType::Virtual,
+            _ => NetworkType::Unknown,
+        }
+    }
+}
+
+impl SysfsAttributes {
+    /// /sys/class/net/<name>/ から属性を読み込み
+    #[cfg(target_os = "linux")]
+    fn read(name: &str) -> Self {
+        use std::path::Path;
+
+        let base = Path::new("/sys/class/net").join(name);
+        let arphrd = std::fs::read_to_string(base.join("type"))
+            .ok()
+            .and_then(|t| t.trim().parse().ok());
+        let devtype = std::fs::read_to_string(base.join("uevent")).ok().and_then(|uevent| {
+            uevent
+                .lines()
+                .find_map(|line| line.strip_prefix("DEVTYPE="))
+                .map(|d| d.trim().to_string())
+        });
+
+        Self {
+            arphrd,
+            devtype,
+            wireless: base.join("wireless").is_dir() || base.join("phy80211").exists(),
+            has_device: base.join("device").exists(),
+            tun: base.join("tun_flags").exists(),
+        }
+    }
+}
+
+#[cfg(windows)]
+mod wmi_query {
+    use super::WindowsAdapterAttributes;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+    use wmi::{COMLibrary, WMIConnection};
+
+    /// ネットワークアダプタ情報（NDISメディア種別・物理アダプタか）
+    #[derive(Deserialize, Debug)]
+    #[serde(rename = "Win32_NetworkAdapter")]
+    #[serde(rename_all = "PascalCase")]
+    struct Win32NetworkAdapter {
+        interface_index: Option<u32>,
+        adapter_type_id: Option<u16>,
+        physical_adapter: Option<bool>,
+    }
+
+    /// ネットワークアダプタ情報（IANA ifType・NDIS物理メディア）
+    #[derive(Deserialize, Debug)]
+    #[serde(rename = "MSFT_NetAdapter")]
+    #[serde(rename_all = "PascalCase")]
+    struct MsftNetAdapter {
+        interface_index: Option<u32>,
+        interface_type: Option<u32>,
+        ndis_physical_medium: Option<u32>,
+    }
+
+    /// WMIからインターフェースインデックスごとのアダプタ属性を取得
+    ///
+    /// MSFT_NetAdapter（ROOT\StandardCimv2）は取得できない環境もあるため、
+    /// 失敗した場合は Win32_NetworkAdapter の情報のみを使用します。
+    pub(super) fn query_adapter_attributes() -> Result<HashMap<u32, WindowsAdapterAttributes>, wmi::WMIError> {
+        let com_con = COMLibrary::new()?;
+        let mut attributes: HashMap<u32, WindowsAdapterAttributes> = HashMap::new();
+
+        let cimv2 = WMIConnection::new(com_con)?;
+        let adapters: Vec<Win32NetworkAdapter> = cimv2.query()?;
+        for adapter in adapters {
+            if let Some(index) = adapter.interface_index {
+                let entry = attributes.entry(index).or_default();
+                entry.adapter_type_id = adapter.adapter_type_id;
+                entry.physical_adapter = adapter.physical_adapter;
+            }
+        }
+
+        let net_adapters: Result<Vec<MsftNetAdapter>, _> =
+            WMIConnection::with_namespace_path("ROOT\\StandardCimv2", com_con).and_then(|con| con.query());
+        match net_adapters {
+            Ok(net_adapters) => {
+                for adapter in net_adapters {
+                    if let Some(index) = adapter.interface_index {
+                        let entry = attributes.entry(index).or_default();
+                        entry.if_type = adapter.interface_type;
+                        entry.ndis_physical_medium = adapter.ndis_physical_medium;
+                    }
+                }
+            }
+            Err(e) => tracing::debug!("MSFT_NetAdapter not available: {}", e),
+        }
+
+        Ok(attributes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sysfs(arphrd: u32, devtype: Option<&str>) -> SysfsAttributes {
+        SysfsAttributes {
+            arphrd: Some(arphrd),
+            devtype: devtype.map(str::to_string),
+            has_device: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_classify_sysfs() {
+        assert_eq!(InterfaceClassifier::classify_sysfs(&sysfs(ARPHRD_ETHER, None)), NetworkType::Wired);
+        assert_eq!(InterfaceClassifier::classify_sysfs(&sysfs(ARPHRD_ETHER, Some("wlan"))), NetworkType::Wireless);
+        assert_eq!(InterfaceClassifier::classify_sysfs(&sysfs(ARPHRD_RAWIP, Some("wwan"))), NetworkType::Cellular);
+        assert_eq!(InterfaceClassifier::classify_sysfs(&sysfs(ARPHRD_NONE, Some("wireguard"))), NetworkType::Vpn);
+        assert_eq!(InterfaceClassifier::classify_sysfs(&sysfs(ARPHRD_PPP, None)), NetworkType::Vpn);
+
+        // wlan0 のように名前に依存せず wireless ディレクトリで判定する
+        let mut wireless = sysfs(ARPHRD_ETHER, None);
+        wireless.wireless = true;
+        assert_eq!(InterfaceClassifier::classify_sysfs(&wireless), NetworkType::Wireless);
+
+        // 物理デバイスに紐づかない Ethernet（docker0, veth など）は Virtual
+        let mut bridge = sysfs(ARPHRD_ETHER, Some("bridge"));
+        bridge.has_device = false;
+        assert_eq!(InterfaceClassifier::classify_sysfs(&bridge), NetworkType::Virtual);
+
+        let mut tap = sysfs(ARPHRD_ETHER, None);
+        tap.has_device = false;
+        tap.tun = true;
+        assert_eq!(InterfaceClassifier::classify_sysfs(&tap), NetworkType::Vpn);
+
+        assert_eq!(InterfaceClassifier::classify_sysfs(&SysfsAttributes::default()), NetworkType::Unknown);
+    }
+
+    #[test]
+    fn test_classify_windows() {
+        let attrs = |if_type, medium, adapter_type_id, physical| WindowsAdapterAttributes {
+            if_type,
+            ndis_physical_medium: medium,
+            adapter_type_id,
+            physical_adapter: physical,
+        };
+
+        assert_eq!(InterfaceClassifier::classify_windows(&attrs(Some(6), Some(14), Some(0), Some(true))), NetworkType::Wired);
+        assert_eq!(InterfaceClassifier::classify_windows(&attrs(Some(71), Some(9), Some(9), Some(true))), NetworkType::Wireless);
+        assert_eq!(InterfaceClassifier::classify_windows(&attrs(Some(243), Some(8), None, Some(true))), NetworkType::Cellular);
+        assert_eq!(InterfaceClassifier::classify_windows(&attrs(Some(53), Some(0), Some(0), Some(false))), NetworkType::Vpn);
+        assert_eq!(InterfaceClassifier::classify_windows(&attrs(Some(6), Some(0), Some(0), Some(false))), NetworkType::Virtual);
+
+        // MSFT_NetAdapter が取得できない場合は AdapterTypeId で判定
+        assert_eq!(InterfaceClassifier::classify_windows(&attrs(None, None, Some(9), Some(true))), NetworkType::Wireless);
+        assert_eq!(InterfaceClassifier::classify_windows(&attrs(None, None, Some(3), Some(false))), NetworkType::Vpn);
+        assert_eq!(InterfaceClassifier::classify_windows(&attrs(None, None, None, None)), NetworkType::Unknown);
+    }
+}
@@ -1,6 +1,11 @@
 use crate::error::{ClientError, Result};
+use crate::network::filter::Rejection;
+use crate::network::{AdapterFilter, AdapterSelection, InterfaceClassifier, NetworkType};
 use network_interface::{NetworkInterface, NetworkInterfaceConfig, Addr};
-use std::net::IpAddr;
+use pc_inventory_protocol::adapter::{is_valid_mac_address, MAX_IPV4_ADDRESSES_LENGTH, MAX_IPV6_ADDRESSES_LENGTH};
+use pc_inventory_protocol::AdapterItem;
+use std::fmt;
+use std::net::{Ipv4Addr, Ipv6Addr};
 
 /// ネットワーク情報検出構造体
 ///
@@ -12,23 +17,112 @@ pub struct NetworkDetector;
 #[derive(Debug, Clone)]
 pub struct NetworkInfo {
     /// IPアドレス（例: "192.168.1.100"）
+    ///
+    /// IPv4アドレスを優先し、IPv4を持たないアダプタの場合はグローバルIPv6アドレス
     pub ip_address: String,
 
+    /// グローバルIPv6アドレス（例: "2001:db8::10"、無い場合は空文字列）
+    pub ipv6_address: String,
+
     /// MACアドレス（例: "00:11:22:33:44:55"）
     pub mac_address: String,
 
-    /// ネットワークタイプ（"Ethernet" または "Wi-Fi"）
-    pub network_type: String,
+    /// ネットワークタイプ
+    pub network_type: NetworkType,
+}
+
+/// ネットワークアダプタ情報
+///
+/// システム上のすべてのアダプタについて、アドレス・種類・状態を保持します。
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdapterInfo {
+    /// インターフェース名（例: "Ethernet", "eth0"）
+    pub name: String,
+
+    /// MACアドレス（存在しないアダプタは None）
+    pub mac_address: Option<String>,
+
+    /// IPv4アドレスの一覧
+    pub ipv4_addresses: Vec<String>,
+
+    /// IPv6アドレスの一覧
+    pub ipv6_addresses: Vec<String>,
+
+    /// ネットワークタイプ
+    pub network_type: NetworkType,
+
+    /// UP状態か
+    pub is_up: bool,
+
+    /// 代表アダプタか（select_primary で決定）
+    pub is_primary: bool,
+
+    /// インターフェースインデックス（代表アダプタ選択の順序付けに使用）
+    pub index: u32,
+}
+
+/// 代表アダプタの候補から除外された理由（NetworkDetector::candidate_rejection）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CandidateRejection {
+    /// UP状態ではない
+    Down,
+    /// MACアドレスが無い
+    NoMacAddress,
+    /// 絞り込みルールで除外された
+    Filtered(Rejection),
+    /// 利用可能なIPv4アドレス・グローバルIPv6アドレスが無い
+    NoUsableAddress,
+}
+
+impl fmt::Display for CandidateRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CandidateRejection::Down => write!(f, "interface is down"),
+            CandidateRejection::NoMacAddress => write!(f, "no MAC address"),
+            CandidateRejection::Filtered(rejection) => write!(f, "filtered by {}", rejection),
+            CandidateRejection::NoUsableAddress => write!(f, "no usable IPv4 or global IPv6 address"),
+        }
+    }
+}
+
+impl From<AdapterInfo> for AdapterItem {
+    /// 送信形式に変換（インターフェースインデックスは送信しない）
+    ///
+    /// サーバーで保存できない値は送信しません（6バイト以外のMACアドレスは None、
+    /// IPアドレスは保存できる長さに収まる分のみ）。
+    fn from(adapter: AdapterInfo) -> Self {
+        Self {
+            name: adapter.name,
+            mac_address: adapter.mac_address.filter(|mac| is_valid_mac_address(mac)),
+            ipv4_addresses: fit_addresses(adapter.ipv4_addresses, MAX_IPV4_ADDRESSES_LENGTH),
+            ipv6_addresses: fit_addresses(adapter.ipv6_addresses, MAX_IPV6_ADDRESSES_LENGTH),
+            network_type: adapter.network_type,
+            is_up: adapter.is_up,
+            is_primary: adapter.is_primary,
+        }
+    }
+}
+
+/// カンマ区切りにした長さが max_length 以下になるよう、先頭から収まる分のアドレスを残す
+fn fit_addresses(addresses: Vec<String>, max_length: usize) -> Vec<String> {
+    let mut length = 0;
+    addresses
+        .into_iter()
+        .take_while(|address| {
+            length += address.len() + usize::from(length > 0);
+            length <= max_length
+        })
+        .collect()
 }
 
 impl NetworkDetector {
     /// アクティブなネットワークアダプタを検出して情報を取得
     ///
-    /// 以下の条件でアクティブなアダプタを判定します：
-    /// 1. UP状態のインターフェース
-    /// 2. IPv4アドレスが設定されている
-    /// 3. ループバックでない（127.x.x.x以外）
-    /// 4. リンクローカルアドレスでない（169.254.x.x以外）
+    /// get_all_adapters で取得したアダプタのうち、
+    /// select_primary で選ばれた代表アダプタの情報を返します。
+    ///
+    /// # 引数
+    /// * `selection` - 代表アダプタの選択条件
     ///
     /// # 戻り値
     /// NetworkInfo構造体
@@ -36,67 +130,279 @@ impl NetworkDetector {
     /// # エラー
     /// - ネットワークインターフェースの取得に失敗した場合
     /// - アクティブなアダプタが見つからなかった場合
-    pub fn get_active_adapter() -> Result<NetworkInfo> {
+    pub fn get_active_adapter(selection: &AdapterSelection) -> Result<NetworkInfo> {
         tracing::debug!("Detecting active network adapter");
 
+        let adapters = Self::get_all_adapters(selection)?;
+
+        let primary = adapters
+            .iter()
+            .find(|a| a.is_primary)
+            .ok_or_else(|| ClientError::NetworkError("No active network adapter found".to_string()))?;
+
+        let ipv6_address = Self::global_ipv6_addresses(primary)
+            .next()
+            .cloned()
+            .unwrap_or_default();
+        // デフォルトルート方式で送信元アドレスがこのアダプタのIPv4アドレスの場合はそれを優先
+        let egress_ipv4 = selection
+            .egress_address
+            .filter(|ip| ip.is_ipv4())
+            .map(|ip| ip.to_string())
+            .filter(|ip| primary.ipv4_addresses.contains(ip));
+        let ip_address = egress_ipv4
+            .or_else(|| Self::usable_ipv4_addresses(primary).next().cloned())
+            .unwrap_or_else(|| ipv6_address.clone());
+        let mac_address = primary.mac_address.clone().unwrap_or_default();
+
+        tracing::info!("Active network adapter detected:");
+        tracing::info!("  Interface: {}", primary.name);
+        tracing::info!("  IP Address: {}", ip_address);
+        tracing::info!("  IPv6 Address: {}", ipv6_address);
+        tracing::info!("  MAC Address: {}", mac_address);
+        tracing::info!("  Network Type: {}", primary.network_type);
+
+        Ok(NetworkInfo {
+            ip_address,
+            ipv6_address,
+            mac_address,
+            network_type: primary.network_type,
+        })
+    }
+
+    /// すべてのネットワークアダプタの情報を取得
+    ///
+    /// ループバックを除くすべてのアダプタを、インターフェースインデックス順に返します。
+    /// 代表アダプタ（is_primary）は select_primary の規則で1つだけ設定されます。
+    /// 絞り込みルールで除外されたアダプタも一覧には含まれます（代表にはなりません）。
+    ///
+    /// # 引数
+    /// * `selection` - 代表アダプタの選択条件
+    ///
+    /// # 戻り値
+    /// AdapterInfo のベクター
+    ///
+    /// # エラー
+    /// ネットワークインターフェースの取得に失敗した場合
+    pub fn get_all_adapters(selection: &AdapterSelection) -> Result<Vec<AdapterInfo>> {
         let network_interfaces = NetworkInterface::show()
             .map_err(|e| ClientError::NetworkError(format!("Failed to get network interfaces: {}", e)))?;
 
         tracing::debug!("Found {} network interfaces", network_interfaces.len());
 
-        // アクティブなインターフェースを探す
-        for iface in network_interfaces {
-            // デバッグ情報出力
-            tracing::debug!("Checking interface: {} ({})", iface.name,
-                if iface.addr.is_empty() { "no addresses" } else { "has addresses" });
-
-            // MACアドレスが存在するか確認
-            let mac = if let Some(mac_addr) = iface.mac_addr {
-                Self::format_mac_address(&mac_addr)
-            } else {
-                tracing::debug!("  Skipping: No MAC address");
-                continue;
-            };
-
-            // IPアドレスを探す
-            for addr in &iface.addr {
-                if let Addr::V4(v4_addr) = addr {
-                    let ip = v4_addr.ip;
-                    let ip_str = ip.to_string();
-
-                    // ループバックアドレスをスキップ
-                    if ip.is_loopback() {
-                        tracing::debug!("  Skipping loopback address: {}", ip_str);
-                        continue;
-                    }
+        let classifier = InterfaceClassifier::load();
+        let mut adapters: Vec<AdapterInfo> = network_interfaces
+            .iter()
+            .filter(|iface| {
+                if iface.internal {
+                    tracing::debug!("Skipping internal interface: {}", iface.name);
+                }
+                !iface.internal
+            })
+            .map(|iface| Self::to_adapter_info(iface, &classifier))
+            .collect();
+
+        adapters.sort_by(|a, b| a.index.cmp(&b.index).then_with(|| a.name.cmp(&b.name)));
+        Self::select_primary(&mut adapters, selection);
+
+        for adapter in &adapters {
+            tracing::debug!(
+                "Adapter: {} (MAC: {:?}, IPv4: {:?}, IPv6: {:?}, type: {}, up: {}, primary: {})",
+                adapter.name, adapter.mac_address, adapter.ipv4_addresses, adapter.ipv6_addresses,
+                adapter.network_type, adapter.is_up, adapter.is_primary
+            );
+        }
 
-                    // リンクローカルアドレス（169.254.x.x）をスキップ
-                    if ip_str.starts_with("169.254.") {
-                        tracing::debug!("  Skipping link-local address: {}", ip_str);
-                        continue;
-                    }
+        Ok(adapters)
+    }
 
-                    // ネットワークタイプを判定
-                    let network_type = Self::detect_network_type(&iface.name);
+    /// 代表アダプタを決定
+    ///
+    /// UP状態かつMACアドレスと、利用可能なIPv4アドレス（ループバック・リンクローカル以外）
+    /// またはグローバルIPv6アドレスを持つアダプタのうち、以下の順で最初のものを代表とします：
+    /// 1. 設定の preferred_adapters で先に指定されたアダプタ
+    /// 2. IPv4アドレスを持つアダプタ（IPv6のみのアダプタより優先）
+    /// 3. ネットワークタイプ（有線 → 無線 → モバイル回線 → 不明 → VPN → 仮想）
+    /// 4. インターフェースインデックスの小さい順
+    /// 5. インターフェース名の辞書順
+    ///
+    /// 絞り込みルールで除外されたアダプタは候補になりません。
+    /// デフォルトルート方式で送信元アドレス（egress_address）を持つアダプタが候補にある場合は、
+    /// 上記の順序に関わらずそのアダプタを代表とします。
+    ///
+    /// # 戻り値
+    /// 代表アダプタのインデックス（該当なしの場合は None）
+    pub fn select_primary(adapters: &mut [AdapterInfo], selection: &AdapterSelection) -> Option<usize> {
+        for adapter in adapters.iter_mut() {
+            adapter.is_primary = false;
+        }
 
-                    tracing::info!("Active network adapter detected:");
-                    tracing::info!("  Interface: {}", iface.name);
-                    tracing::info!("  IP Address: {}", ip_str);
-                    tracing::info!("  MAC Address: {}", mac);
-                    tracing::info!("  Network Type: {}", network_type);
+        let filter = &selection.filter;
+        let is_candidate = |a: &AdapterInfo| Self::candidate_rejection(a, filter).is_none();
 
-                    return Ok(NetworkInfo {
-                        ip_address: ip_str,
-                        mac_address: mac.clone(),
-                        network_type,
-                    });
-                }
+        let egress = selection.egress_address.and_then(|egress| {
+            let egress = egress.to_string();
+            let found = adapters.iter().position(|a| {
+                is_candidate(a) && a.ipv4_addresses.iter().chain(&a.ipv6_addresses).any(|ip| *ip == egress)
+            });
+            if found.is_none() {
+                tracing::debug!("Egress address {} is not on a candidate adapter, falling back to priority", egress);
             }
+            found
+        });
+
+        let primary = egress.or_else(|| {
+            adapters
+                .iter()
+                .enumerate()
+                .filter(|(_, a)| is_candidate(a))
+                .min_by(|(_, a), (_, b)| {
+                    let a_ipv6_only = Self::usable_ipv4_addresses(a).next().is_none();
+                    let b_ipv6_only = Self::usable_ipv4_addresses(b).next().is_none();
+                    filter
+                        .preference(a)
+                        .cmp(&filter.preference(b))
+                        .then_with(|| a_ipv6_only.cmp(&b_ipv6_only))
+                        .then_with(|| Self::network_type_priority(a.network_type).cmp(&Self::network_type_priority(b.network_type)))
+                        .then_with(|| a.index.cmp(&b.index))
+                        .then_with(|| a.name.cmp(&b.name))
+                })
+                .map(|(i, _)| i)
+        });
+
+        if let Some(i) = primary {
+            adapters[i].is_primary = true;
         }
 
-        Err(ClientError::NetworkError(
-            "No active network adapter found".to_string(),
-        ))
+        primary
+    }
+
+    /// アダプタが代表アダプタの候補から除外される理由
+    ///
+    /// select_primary と同じ条件（UP状態・MACアドレス・絞り込みルール・利用可能なアドレス）を
+    /// この順に確認します。
+    ///
+    /// # 戻り値
+    /// 除外される理由（候補になる場合は None）
+    pub fn candidate_rejection(adapter: &AdapterInfo, filter: &AdapterFilter) -> Option<CandidateRejection> {
+        if !adapter.is_up {
+            return Some(CandidateRejection::Down);
+        }
+        if adapter.mac_address.is_none() {
+            return Some(CandidateRejection::NoMacAddress);
+        }
+        if let Err(rejection) = filter.check(adapter) {
+            return Some(CandidateRejection::Filtered(rejection));
+        }
+        if Self::usable_ipv4_addresses(adapter).next().is_none() && Self::global_ipv6_addresses(adapter).next().is_none() {
+            return Some(CandidateRejection::NoUsableAddress);
+        }
+        None
+    }
+
+    /// 利用可能なIPv4アドレス（ループバック・リンクローカル以外）を列挙
+    fn usable_ipv4_addresses(adapter: &AdapterInfo) -> impl Iterator<Item = &String> {
+        adapter.ipv4_addresses.iter().filter(|ip| {
+            ip.parse::<Ipv4Addr>()
+                .map(|ip| !ip.is_loopback() && !ip.is_link_local())
+                .unwrap_or(false)
+        })
+    }
+
+    /// グローバルIPv6アドレスを列挙
+    fn global_ipv6_addresses(adapter: &AdapterInfo) -> impl Iterator<Item = &String> {
+        adapter.ipv6_addresses.iter().filter(|ip| {
+            ip.parse::<Ipv6Addr>()
+                .map(|ip| Self::is_global_ipv6(&ip))
+                .unwrap_or(false)
+        })
+    }
+
+    /// IPv6アドレスがグローバルスコープか判定
+    ///
+    /// 未指定（::）、ループバック（::1）、リンクローカル（fe80::/10）、
+    /// マルチキャスト（ff00::/8）以外をグローバルスコープとみなします。
+    /// 社内のデュアルスタック環境で使用されるユニークローカル（fc00::/7）も含みます。
+    fn is_global_ipv6(ip: &Ipv6Addr) -> bool {
+        !ip.is_unspecified()
+            && !ip.is_loopback()
+            && !ip.is_multicast()
+            && (ip.segments()[0] & 0xffc0) != 0xfe80
+    }
+
+    /// 代表アダプタ選択時のネットワークタイプの優先度（小さいほど優先）
+    ///
+    /// 物理回線（有線 → 無線 → モバイル回線）を優先し、VPN・仮想アダプタは最後にします。
+    fn network_type_priority(network_type: NetworkType) -> u8 {
+        match network_type {
+            NetworkType::Wired => 0,
+            NetworkType::Wireless => 1,
+            NetworkType::Cellular => 2,
+            NetworkType::Unknown => 3,
+            NetworkType::Vpn => 4,
+            NetworkType::Virtual => 5,
+        }
+    }
+
+    /// NetworkInterface を AdapterInfo に変換
+    fn to_adapter_info(iface: &NetworkInterface, classifier: &InterfaceClassifier) -> AdapterInfo {
+        let mut ipv4_addresses = Vec::new();
+        let mut ipv6_addresses = Vec::new();
+
+        for addr in &iface.addr {
+            match addr {
+                Addr::V4(v4_addr) => ipv4_addresses.push(v4_addr.ip.to_string()),
+                Addr::V6(v6_addr) => ipv6_addresses.push(v6_addr.ip.to_string()),
+            }
+        }
+
+        let mac_address = iface
+            .mac_addr
+            .as_deref()
+            .map(Self::format_mac_address)
+            .filter(|mac| mac != "00:00:00:00:00:00");
+
+        let has_usable_address = ipv4_addresses
+            .iter()
+            .filter_map(|ip| ip.parse::<Ipv4Addr>().ok())
+            .any(|ip| !ip.is_loopback() && !ip.is_link_local())
+            || ipv6_addresses
+                .iter()
+                .filter_map(|ip| ip.parse::<Ipv6Addr>().ok())
+                .any(|ip| Self::is_global_ipv6(&ip));
+
+        AdapterInfo {
+            name: iface.name.clone(),
+            mac_address,
+            ipv4_addresses,
+            ipv6_addresses,
+            network_type: classifier.classify(&iface.name, iface.index),
+            is_up: Self::is_interface_up(&iface.name, has_usable_address),
+            is_primary: false,
+            index: iface.index,
+        }
+    }
+
+    /// インターフェースがUP状態か判定
+    ///
+    /// Linux では /sys/class/net/<name>/operstate を参照します。
+    /// 取得できない場合や他のOSでは、利用可能なIPアドレスを持つかで判定します。
+    fn is_interface_up(interface_name: &str, has_usable_address: bool) -> bool {
+        #[cfg(target_os = "linux")]
+        {
+            let path = format!("/sys/class/net/{}/operstate", interface_name);
+            if let Ok(state) = std::fs::read_to_string(path) {
+                return match state.trim() {
+                    "up" => true,
+                    "unknown" => has_usable_address,
+                    _ => false,
+                };
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        let _ = interface_name;
+
+        has_usable_address
     }
 
     /// MACアドレスをフォーマット
@@ -114,29 +420,17 @@ impl NetworkDetector {
         mac.to_string()
     }
 
-    /// ネットワークタイプを判定
+    /// 絞り込みルールの適用結果をログに出力
     ///
-    /// インターフェース名から、Ethernet か Wi-Fi かを判定します。
-    ///
-    /// # 引数
-    /// * `interface_name` - インターフェース名（例: "Ethernet", "Wi-Fi", "eth0"）
+    /// 各ルールに一致したアダプタと、最終的に選ばれる代表アダプタを確認するためのプレビューです。
+    /// 送信内容には影響しません。
     ///
-    /// # 戻り値
-    /// "Ethernet" または "Wi-Fi"
-    fn detect_network_type(interface_name: &str) -> String {
-        let name_lower = interface_name.to_lowercase();
-
-        // Wi-Fiの判定
-        if name_lower.contains("wi-fi")
-            || name_lower.contains("wifi")
-            || name_lower.contains("wireless")
-            || name_lower.contains("wlan") {
-            "Wi-Fi".to_string()
-        }
-        // それ以外はEthernetとみなす
-        else {
-            "Ethernet".to_string()
-        }
+    /// # エラー
+    /// ネットワークインターフェースの取得に失敗した場合
+    pub fn preview_filter(selection: &AdapterSelection) -> Result<()> {
+        let adapters = Self::get_all_adapters(selection)?;
+        selection.filter.log_preview(&adapters);
+        Ok(())
     }
 
     /// すべてのネットワークインターフェース情報を取得（デバッグ用）
@@ -149,7 +443,6 @@ impl NetworkDetector {
     ///
     /// # エラー
     /// ネットワークインターフェースの取得に失敗した場合
-    #[allow(dead_code)]
     pub fn get_all_interfaces() -> Result<Vec<NetworkInterface>> {
         let interfaces = NetworkInterface::show()
             .map_err(|e| ClientError::NetworkError(format!("Failed to get network interfaces: {}", e)))?;
@@ -189,10 +482,19 @@ impl NetworkDetector {
                 for addr in &iface.addr {
                     if let Addr::V4(v4_addr) = addr {
                         let ip_str = v4_addr.ip.to_string();
-                        let network_type = Self::detect_network_type(&iface.name);
+                        let network_type = InterfaceClassifier::load().classify(&iface.name, iface.index);
+
+                        let ipv6_address = iface.addr.iter()
+                            .filter_map(|a| match a {
+                                Addr::V6(v6_addr) if Self::is_global_ipv6(&v6_addr.ip) => Some(v6_addr.ip.to_string()),
+                                _ => None,
+                            })
+                            .next()
+                            .unwrap_or_default();
 
                         return Ok(NetworkInfo {
                             ip_address: ip_str,
+                            ipv6_address,
                             mac_address: mac,
                             network_type,
                         });
@@ -211,19 +513,153 @@ impl NetworkDetector {
 mod tests {
     use super::*;
 
+    fn adapter(name: &str, index: u32, network_type: NetworkType, ipv4: &[&str], is_up: bool) -> AdapterInfo {
+        AdapterInfo {
+            name: name.to_string(),
+            mac_address: Some("00:11:22:33:44:55".to_string()),
+            ipv4_addresses: ipv4.iter().map(|s| s.to_string()).collect(),
+            ipv6_addresses: Vec::new(),
+            network_type,
+            is_up,
+            is_primary: false,
+            index,
+        }
+    }
+
+    #[test]
+    fn test_select_primary_prefers_wired() {
+        let mut adapters = vec![
+            adapter("Wi-Fi", 3, NetworkType::Wireless, &["192.168.1.20"], true),
+            adapter("Ethernet", 7, NetworkType::Wired, &["10.0.0.5"], true),
+        ];
+
+        assert_eq!(NetworkDetector::select_primary(&mut adapters, &AdapterSelection::default()), Some(1));
+        assert!(adapters[1].is_primary);
+        assert!(!adapters[0].is_primary);
+    }
+
+    #[test]
+    fn test_select_primary_skips_unusable() {
+        let mut adapters = vec![
+            adapter("Ethernet", 2, NetworkType::Wired, &["169.254.10.1"], true),
+            adapter("Ethernet 2", 4, NetworkType::Wired, &["10.0.0.5"], false),
+            adapter("Ethernet 3", 9, NetworkType::Wired, &["10.0.0.6"], true),
+        ];
+
+        assert_eq!(NetworkDetector::select_primary(&mut adapters, &AdapterSelection::default()), Some(2));
+    }
+
+    #[test]
+    fn test_select_primary_orders_by_index() {
+        let mut adapters = vec![
+            adapter("eth1", 5, NetworkType::Wired, &["10.0.0.6"], true),
+            adapter("eth0", 2, NetworkType::Wired, &["10.0.0.5"], true),
+        ];
+
+        assert_eq!(NetworkDetector::select_primary(&mut adapters, &AdapterSelection::default()), Some(1));
+    }
+
+    #[test]
+    fn test_select_primary_falls_back_to_ipv6_only() {
+        let mut ipv6_only = adapter("Ethernet", 2, NetworkType::Wired, &[], true);
+        ipv6_only.ipv6_addresses = vec!["fe80::1".to_string(), "2001:db8::10".to_string()];
+        let mut adapters = vec![
+            ipv6_only,
+            adapter("Wi-Fi", 5, NetworkType::Wireless, &["192.168.1.20"], true),
+        ];
+
+        // IPv4を持つアダプタが優先される
+        assert_eq!(NetworkDetector::select_primary(&mut adapters, &AdapterSelection::default()), Some(1));
+
+        adapters.remove(1);
+        assert_eq!(NetworkDetector::select_primary(&mut adapters, &AdapterSelection::default()), Some(0));
+        assert_eq!(NetworkDetector::global_ipv6_addresses(&adapters[0]).next().map(String::as_str), Some("2001:db8::10"));
+    }
+
+    #[test]
+    fn test_select_primary_applies_filter() {
+        let mut adapters = vec![
+            adapter("vEthernet (Default Switch)", 2, NetworkType::Wired, &["172.20.0.1"], true),
+            adapter("Wi-Fi", 5, NetworkType::Wireless, &["192.168.1.20"], true),
+            adapter("Ethernet", 9, NetworkType::Wired, &["10.0.0.5"], true),
+        ];
+        let selection = AdapterSelection {
+            filter: AdapterFilter::from_settings(&crate::config::NetworkSettings {
+                exclude_interfaces: vec!["vEthernet*".to_string()],
+                preferred_adapters: vec!["Wi-Fi".to_string()],
+                ..Default::default()
+            })
+            .unwrap(),
+            egress_address: None,
+        };
+
+        // 除外されたアダプタは候補にならず、preferred_adapters がネットワークタイプより優先される
+        assert_eq!(NetworkDetector::select_primary(&mut adapters, &selection), Some(1));
+        assert!(!adapters[0].is_primary);
+    }
+
+    #[test]
+    fn test_select_primary_prefers_egress_adapter() {
+        let mut adapters = vec![
+            adapter("Ethernet", 2, NetworkType::Wired, &["10.0.0.5"], true),
+            adapter("Wi-Fi", 5, NetworkType::Wireless, &["192.168.1.20"], true),
+        ];
+        let mut selection = AdapterSelection {
+            egress_address: Some("192.168.1.20".parse().unwrap()),
+            ..Default::default()
+        };
+
+        assert_eq!(NetworkDetector::select_primary(&mut adapters, &selection), Some(1));
+
+        // 送信元アドレスのアダプタが除外されている場合は優先順位で選択
+        selection.filter = AdapterFilter::from_settings(&crate::config::NetworkSettings {
+            exclude_interfaces: vec!["Wi-Fi".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(NetworkDetector::select_primary(&mut adapters, &selection), Some(0));
+    }
+
+    #[test]
+    fn test_candidate_rejection() {
+        let filter = AdapterFilter::from_settings(&crate::config::NetworkSettings {
+            exclude_interfaces: vec!["vEthernet*".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+        let rejection = |a: &AdapterInfo| NetworkDetector::candidate_rejection(a, &filter);
+
+        assert_eq!(rejection(&adapter("Ethernet", 2, NetworkType::Wired, &["10.0.0.5"], true)), None);
+        assert_eq!(rejection(&adapter("Ethernet", 2, NetworkType::Wired, &["10.0.0.5"], false)), Some(CandidateRejection::Down));
+        assert_eq!(rejection(&adapter("Ethernet", 2, NetworkType::Wired, &["169.254.10.1"], true)), Some(CandidateRejection::NoUsableAddress));
+
+        let mut no_mac = adapter("Ethernet", 2, NetworkType::Wired, &["10.0.0.5"], true);
+        no_mac.mac_address = None;
+        assert_eq!(rejection(&no_mac), Some(CandidateRejection::NoMacAddress));
+
+        let excluded = rejection(&adapter("vEthernet (Default Switch)", 2, NetworkType::Virtual, &["172.20.0.1"], true)).unwrap();
+        assert!(matches!(excluded, CandidateRejection::Filtered(ref r) if r.rule == "exclude_interfaces"));
+        assert_eq!(excluded.to_string(), "filtered by exclude_interfaces ('vEthernet*')");
+    }
+
     #[test]
-    fn test_detect_network_type_wifi() {
-        assert_eq!(NetworkDetector::detect_network_type("Wi-Fi"), "Wi-Fi");
-        assert_eq!(NetworkDetector::detect_network_type("wifi"), "Wi-Fi");
-        assert_eq!(NetworkDetector::detect_network_type("wlan0"), "Wi-Fi");
-        assert_eq!(NetworkDetector::detect_network_type("Wireless"), "Wi-Fi");
+    fn test_is_global_ipv6() {
+        assert!(NetworkDetector::is_global_ipv6(&"2001:db8::10".parse().unwrap()));
+        assert!(NetworkDetector::is_global_ipv6(&"fd12:3456::1".parse().unwrap()));
+        assert!(!NetworkDetector::is_global_ipv6(&"fe80::1".parse().unwrap()));
+        assert!(!NetworkDetector::is_global_ipv6(&"::1".parse().unwrap()));
+        assert!(!NetworkDetector::is_global_ipv6(&"ff02::1".parse().unwrap()));
     }
 
     #[test]
-    fn test_detect_network_type_ethernet() {
-        assert_eq!(NetworkDetector::detect_network_type("Ethernet"), "Ethernet");
-        assert_eq!(NetworkDetector::detect_network_type("eth0"), "Ethernet");
-        assert_eq!(NetworkDetector::detect_network_type("Local Area Connection"), "Ethernet");
+    fn test_adapter_item_fits_server_limits() {
+        let mut info = adapter("ib0", 4, NetworkType::Wired, &["192.168.100.100"; 20], true);
+        info.mac_address = Some("80:00:02:08:fe:80:00:00:00:00:00:00".to_string());
+
+        let item = AdapterItem::from(info);
+        assert_eq!(item.mac_address, None);
+        assert_eq!(item.ipv4_addresses.len(), 16);
+        assert!(item.validate().is_ok());
     }
 
     #[test]
@@ -235,18 +671,18 @@ mod tests {
     #[test]
     #[ignore] // 実際のネットワーク環境でのみ動作
     fn test_get_active_adapter() {
-        let result = NetworkDetector::get_active_adapter();
+        let result = NetworkDetector::get_active_adapter(&AdapterSelection::default());
         assert!(result.is_ok());
 
         let info = result.unwrap();
         println!("Active Adapter:");
         println!("  IP: {}", info.ip_address);
+        println!("  IPv6: {}", info.ipv6_address);
         println!("  MAC: {}", info.mac_address);
         println!("  Type: {}", info.network_type);
 
         assert!(!info.ip_address.is_empty());
         assert!(!info.mac_address.is_empty());
-        assert!(!info.network_type.is_empty());
     }
 
     #[test]
@@ -263,8 +699,9 @@ mod tests {
                 println!("    MAC: {}", mac);
             }
             for addr in iface.addr {
-                if let Addr::V4(v4_addr) = addr {
-                    println!("    IPv4: {}", v4_addr.ip);
+                match addr {
+                    Addr::V4(v4_addr) => println!("    IPv4: {}", v4_addr.ip),
+                    Addr::V6(v6_addr) => println!("    IPv6: {}", v6_addr.ip),
                 }
             }
         }
@@ -0,0 +1,398 @@
+use crate::config::NetworkSettings;
+use crate::error::{ClientError, Result};
+use crate::network::AdapterInfo;
+use std::fmt;
+use std::net::IpAddr;
+
+/// アダプタの絞り込みルール
+///
+/// config.toml の [network] セクションから生成し、
+/// 代表アダプタの候補から仮想アダプタやVPNアダプタを除外するために使用します。
+/// include 系のルールは指定された場合のみ適用され、いずれかに一致したアダプタだけを候補とします。
+#[derive(Debug, Clone, Default)]
+pub struct AdapterFilter {
+    include_interfaces: Vec<String>,
+    exclude_interfaces: Vec<String>,
+    include_mac_prefixes: Vec<String>,
+    exclude_mac_prefixes: Vec<String>,
+    include_subnets: Vec<Subnet>,
+    exclude_subnets: Vec<Subnet>,
+    preferred_adapters: Vec<String>,
+}
+
+/// アダプタが候補から除外された理由
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    /// ルール名（例: "exclude_interfaces"）
+    pub rule: &'static str,
+    /// 一致したパターン（include 系で一致しなかった場合は None）
+    pub pattern: Option<String>,
+}
+
+/// サブネット（CIDR表記）
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Subnet {
+    source: String,
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl AdapterFilter {
+    /// 設定からルールを生成
+    ///
+    /// # エラー
+    /// MACアドレスのプレフィックスまたはサブネットの形式が不正な場合
+    pub fn from_settings(settings: &NetworkSettings) -> Result<Self> {
+        Ok(Self {
+            include_interfaces: settings.include_interfaces.clone(),
+            exclude_interfaces: settings.exclude_interfaces.clone(),
+            include_mac_prefixes: parse_mac_prefixes(&settings.include_mac_prefixes)?,
+            exclude_mac_prefixes: parse_mac_prefixes(&settings.exclude_mac_prefixes)?,
+            include_subnets: parse_subnets(&settings.include_subnets)?,
+            exclude_subnets: parse_subnets(&settings.exclude_subnets)?,
+            preferred_adapters: settings.preferred_adapters.clone(),
+        })
+    }
+
+    /// ルールが1つも設定されていないか
+    pub fn is_empty(&self) -> bool {
+        self.include_interfaces.is_empty()
+            && self.exclude_interfaces.is_empty()
+            && self.include_mac_prefixes.is_empty()
+            && self.exclude_mac_prefixes.is_empty()
+            && self.include_subnets.is_empty()
+            && self.exclude_subnets.is_empty()
+            && self.preferred_adapters.is_empty()
+    }
+
+    /// アダプタが代表アダプタの候補になるか判定
+    ///
+    /// # 戻り値
+    /// * `Ok(())` - 候補になる場合
+    /// * `Err(Rejection)` - 除外された場合（最初に該当したルール）
+    pub fn check(&self, adapter: &AdapterInfo) -> std::result::Result<(), Rejection> {
+        let mac = adapter.mac_address.as_deref().map(normalize_mac).unwrap_or_default();
+        let addresses: Vec<IpAddr> = adapter
+            .ipv4_addresses
+            .iter()
+            .chain(&adapter.ipv6_addresses)
+            .filter_map(|ip| ip.parse().ok())
+            .collect();
+
+        if let Some(pattern) = self.exclude_interfaces.iter().find(|p| glob_match(p, &adapter.name)) {
+            return Err(Rejection::matched("exclude_interfaces", pattern));
+        }
+        if let Some(prefix) = self.exclude_mac_prefixes.iter().find(|p| !mac.is_empty() && mac.starts_with(p.as_str())) {
+            return Err(Rejection::matched("exclude_mac_prefixes", prefix));
+        }
+        if let Some(subnet) = self.exclude_subnets.iter().find(|s| addresses.iter().any(|ip| s.contains(ip))) {
+            return Err(Rejection::matched("exclude_subnets", &subnet.source));
+        }
+
+        if !self.include_interfaces.is_empty() && !self.include_interfaces.iter().any(|p| glob_match(p, &adapter.name)) {
+            return Err(Rejection::unmatched("include_interfaces"));
+        }
+        if !self.include_mac_prefixes.is_empty() && !self.include_mac_prefixes.iter().any(|p| mac.starts_with(p.as_str())) {
+            return Err(Rejection::unmatched("include_mac_prefixes"));
+        }
+        if !self.include_subnets.is_empty()
+            && !self.include_subnets.iter().any(|s| addresses.iter().any(|ip| s.contains(ip)))
+        {
+            return Err(Rejection::unmatched("include_subnets"));
+        }
+
+        Ok(())
+    }
+
+    /// preferred_adapters での優先順位（小さいほど優先、一致しない場合はルール数）
+    pub fn preference(&self, adapter: &AdapterInfo) -> usize {
+        self.preferred_adapters
+            .iter()
+            .position(|p| glob_match(p, &adapter.name))
+            .unwrap_or(self.preferred_adapters.len())
+    }
+
+    /// 各ルールがどのアダプタに一致したかをログに出力（設定確認用のプレビュー）
+    ///
+    /// # 引数
+    /// * `adapters` - get_all_adapters で取得したアダプタ（is_primary 設定済み）
+    pub fn log_preview(&self, adapters: &[AdapterInfo]) {
+        if self.is_empty() {
+            tracing::debug!("No adapter filter rules configured");
+            return;
+        }
+
+        let names = |pred: &dyn Fn(&AdapterInfo) -> bool| -> String {
+            let matched: Vec<&str> = adapters.iter().filter(|a| pred(a)).map(|a| a.name.as_str()).collect();
+            if matched.is_empty() {
+                "(none)".to_string()
+            } else {
+                matched.join(", ")
+            }
+        };
+        let mac_of = |a: &AdapterInfo| a.mac_address.as_deref().map(normalize_mac).unwrap_or_default();
+        let has_address_in = |a: &AdapterInfo, subnet: &Subnet| {
+            a.ipv4_addresses
+                .iter()
+                .chain(&a.ipv6_addresses)
+                .filter_map(|ip| ip.parse::<IpAddr>().ok())
+                .any(|ip| subnet.contains(&ip))
+        };
+
+        tracing::info!("Adapter filter preview:");
+        for pattern in &self.include_interfaces {
+            tracing::info!("  include_interfaces '{}' -> {}", pattern, names(&|a| glob_match(pattern, &a.name)));
+        }
+        for pattern in &self.exclude_interfaces {
+            tracing::info!("  exclude_interfaces '{}' -> {}", pattern, names(&|a| glob_match(pattern, &a.name)));
+        }
+        for prefix in &self.include_mac_prefixes {
+            tracing::info!("  include_mac_prefixes '{}' -> {}", prefix, names(&|a| mac_of(a).starts_with(prefix.as_str())));
+        }
+        for prefix in &self.exclude_mac_prefixes {
+            tracing::info!("  exclude_mac_prefixes '{}' -> {}", prefix, names(&|a| mac_of(a).starts_with(prefix.as_str())));
+        }
+        for subnet in &self.include_subnets {
+            tracing::info!("  include_subnets '{}' -> {}", subnet.source, names(&|a| has_address_in(a, subnet)));
+        }
+        for subnet in &self.exclude_subnets {
+            tracing::info!("  exclude_subnets '{}' -> {}", subnet.source, names(&|a| has_address_in(a, subnet)));
+        }
+        for (i, pattern) in self.preferred_adapters.iter().enumerate() {
+            tracing::info!("  preferred_adapters[{}] '{}' -> {}", i, pattern, names(&|a| glob_match(pattern, &a.name)));
+        }
+
+        for adapter in adapters {
+            match self.check(adapter) {
+                Ok(()) => tracing::info!("  {}: candidate", adapter.name),
+                Err(rejection) => tracing::info!("  {}: rejected by {}", adapter.name, rejection),
+            }
+        }
+
+        match adapters.iter().find(|a| a.is_primary) {
+            Some(primary) => tracing::info!("  => selected primary adapter: {}", primary.name),
+            None => tracing::warn!("  => no adapter selected"),
+        }
+    }
+}
+
+impl Rejection {
+    fn matched(rule: &'static str, pattern: &str) -> Self {
+        Self {
+            rule,
+            pattern: Some(pattern.to_string()),
+        }
+    }
+
+    fn unmatched(rule: &'static str) -> Self {
+        Self { rule, pattern: None }
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.pattern {
+            Some(pattern) => write!(f, "{} ('{}')", self.rule, pattern),
+            None => write!(f, "{} (no pattern matched)", self.rule),
+        }
+    }
+}
+
+impl Subnet {
+    /// "192.168.0.0/16" や "2001:db8::/32" 形式の文字列を解析
+    fn parse(source: &str) -> Option<Self> {
+        let (addr, prefix) = source.trim().split_once('/')?;
+        let network: IpAddr = addr.trim().parse().ok()?;
+        let prefix_len: u8 = prefix.trim().parse().ok()?;
+        let max_len = if network.is_ipv4() { 32 } else { 128 };
+        if prefix_len > max_len {
+            return None;
+        }
+
+        Some(Self {
+            source: source.trim().to_string(),
+            network,
+            prefix_len,
+        })
+    }
+
+    /// アドレスがサブネットに含まれるか
+    fn contains(&self, ip: &IpAddr) -> bool {
+        match (self.network, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix_len as u32).unwrap_or(0);
+                u32::from(network) & mask == u32::from(*ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix_len as u32).unwrap_or(0);
+                u128::from(network) & mask == u128::from(*ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// MACアドレスを区切り文字なしの大文字16進数に正規化（例: "00155D"）
+fn normalize_mac(mac: &str) -> String {
+    mac.chars()
+        .filter(|c| c.is_ascii_hexdigit())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// MACアドレスのプレフィックス（OUI）一覧を検証・正規化
+fn parse_mac_prefixes(prefixes: &[String]) -> Result<Vec<String>> {
+    prefixes
+        .iter()
+        .map(|prefix| {
+            let valid = prefix.chars().all(|c| c.is_ascii_hexdigit() || matches!(c, ':' | '-'));
+            let normalized = normalize_mac(prefix);
+            if !valid || normalized.is_empty() || normalized.len() > 12 || !normalized.len().is_multiple_of(2) {
+                return Err(ClientError::InvalidConfig(format!("Invalid MAC address prefix: '{}'", prefix)));
+            }
+            Ok(normalized)
+        })
+        .collect()
+}
+
+/// サブネット一覧を検証・解析
+fn parse_subnets(subnets: &[String]) -> Result<Vec<Subnet>> {
+    subnets
+        .iter()
+        .map(|s| Subnet::parse(s).ok_or_else(|| ClientError::InvalidConfig(format!("Invalid subnet: '{}'", s))))
+        .collect()
+}
+
+/// ワイルドカード（"*" と "?"）によるインターフェース名の照合
+///
+/// Windows のインターフェース名は大文字小文字を区別しないため、大文字小文字を区別せずに照合します。
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = backtrack {
+            p = star_p + 1;
+            n = star_n + 1;
+            backtrack = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::NetworkType;
+
+    fn adapter(name: &str, mac: &str, ipv4: &str) -> AdapterInfo {
+        AdapterInfo {
+            name: name.to_string(),
+            mac_address: Some(mac.to_string()),
+            ipv4_addresses: vec![ipv4.to_string()],
+            ipv6_addresses: Vec::new(),
+            network_type: NetworkType::Wired,
+            is_up: true,
+            is_primary: false,
+            index: 1,
+        }
+    }
+
+    fn settings() -> NetworkSettings {
+        NetworkSettings::default()
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("vEthernet*", "vEthernet (Default Switch)"));
+        assert!(glob_match("veth*", "VETH1234"));
+        assert!(glob_match("eth?", "eth0"));
+        assert!(glob_match("*VPN*", "Cisco AnyConnect VPN Adapter"));
+        assert!(!glob_match("eth?", "eth10"));
+        assert!(!glob_match("docker*", "br-docker0"));
+    }
+
+    #[test]
+    fn test_exclude_rules() {
+        let filter = AdapterFilter::from_settings(&NetworkSettings {
+            exclude_interfaces: vec!["vEthernet*".to_string()],
+            exclude_mac_prefixes: vec!["08:00:27".to_string()],
+            exclude_subnets: vec!["172.16.0.0/12".to_string()],
+            ..settings()
+        })
+        .unwrap();
+
+        let rejection = filter.check(&adapter("vEthernet (WSL)", "00:15:5D:01:02:03", "172.20.0.1")).unwrap_err();
+        assert_eq!(rejection.rule, "exclude_interfaces");
+        assert_eq!(
+            filter.check(&adapter("Ethernet 2", "08:00:27:AA:BB:CC", "192.168.56.1")).unwrap_err().rule,
+            "exclude_mac_prefixes"
+        );
+        assert_eq!(
+            filter.check(&adapter("docker0", "02:42:AC:11:00:01", "172.17.0.1")).unwrap_err().rule,
+            "exclude_subnets"
+        );
+        assert!(filter.check(&adapter("Ethernet", "4C:23:38:FB:41:C5", "192.168.3.3")).is_ok());
+    }
+
+    #[test]
+    fn test_include_rules() {
+        let filter = AdapterFilter::from_settings(&NetworkSettings {
+            include_subnets: vec!["10.0.0.0/8".to_string()],
+            ..settings()
+        })
+        .unwrap();
+
+        assert!(filter.check(&adapter("Ethernet", "4C:23:38:FB:41:C5", "10.1.2.3")).is_ok());
+        let rejection = filter.check(&adapter("Wi-Fi", "4C:23:38:FB:41:C6", "192.168.1.20")).unwrap_err();
+        assert_eq!(rejection, Rejection::unmatched("include_subnets"));
+    }
+
+    #[test]
+    fn test_preference() {
+        let filter = AdapterFilter::from_settings(&NetworkSettings {
+            preferred_adapters: vec!["Ethernet 2".to_string(), "Ethernet*".to_string()],
+            ..settings()
+        })
+        .unwrap();
+
+        assert_eq!(filter.preference(&adapter("Ethernet 2", "4C:23:38:FB:41:C5", "10.0.0.2")), 0);
+        assert_eq!(filter.preference(&adapter("Ethernet", "4C:23:38:FB:41:C6", "10.0.0.1")), 1);
+        assert_eq!(filter.preference(&adapter("Wi-Fi", "4C:23:38:FB:41:C7", "10.0.0.3")), 2);
+    }
+
+    #[test]
+    fn test_invalid_settings() {
+        let invalid_mac = NetworkSettings {
+            exclude_mac_prefixes: vec!["00:15:5".to_string()],
+            ..settings()
+        };
+        assert!(AdapterFilter::from_settings(&invalid_mac).is_err());
+
+        let invalid_subnet = NetworkSettings {
+            include_subnets: vec!["10.0.0.0/33".to_string()],
+            ..settings()
+        };
+        assert!(AdapterFilter::from_settings(&invalid_subnet).is_err());
+    }
+
+    #[test]
+    fn test_subnet_contains_ipv6() {
+        let subnet = Subnet::parse("2001:db8::/32").unwrap();
+        assert!(subnet.contains(&"2001:db8:1::10".parse().unwrap()));
+        assert!(!subnet.contains(&"2001:db9::10".parse().unwrap()));
+        assert!(!subnet.contains(&"10.0.0.1".parse().unwrap()));
+    }
+}
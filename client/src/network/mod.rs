@@ -1,3 +1,12 @@
+pub mod classifier;
 pub mod detector;
+pub mod filter;
+pub mod selection;
+pub mod watcher;
 
-pub use detector::{NetworkDetector, NetworkInfo};
+pub use classifier::InterfaceClassifier;
+pub use detector::{AdapterInfo, CandidateRejection, NetworkDetector};
+pub use filter::AdapterFilter;
+pub use pc_inventory_protocol::NetworkType;
+pub use selection::AdapterSelection;
+pub use watcher::NetworkWatcher;
@@ -0,0 +1,118 @@
+use crate::config::{ClientConfig, SelectionMode};
+use crate::error::{ClientError, Result};
+use crate::network::AdapterFilter;
+use reqwest::Url;
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs, UdpSocket};
+
+/// 代表アダプタの選択条件
+///
+/// 絞り込みルールと、デフォルトルート方式の場合はサーバーへの送信元アドレスを保持します。
+#[derive(Debug, Clone, Default)]
+pub struct AdapterSelection {
+    /// 絞り込みルール
+    pub filter: AdapterFilter,
+
+    /// サーバーへの通信に使われる送信元アドレス（デフォルトルート方式のみ）
+    pub egress_address: Option<IpAddr>,
+}
+
+impl AdapterSelection {
+    /// 設定から選択条件を生成
+    ///
+    /// selection_mode が default_route の場合は server.url への送信元アドレスを解決します。
+    /// 解決できない場合（名前解決の失敗、経路なしなど）は警告を出力し、
+    /// 優先順位による選択にフォールバックします。
+    ///
+    /// # エラー
+    /// 絞り込みルールの形式が不正な場合
+    pub fn from_config(config: &ClientConfig) -> Result<Self> {
+        let filter = AdapterFilter::from_settings(&config.network)?;
+
+        let egress_address = match config.network.selection_mode {
+            SelectionMode::Priority => None,
+            SelectionMode::DefaultRoute => match resolve_egress_address(&config.server.url) {
+                Ok(address) => {
+                    tracing::debug!("Egress address for {}: {}", config.server.url, address);
+                    Some(address)
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to resolve egress address, falling back to priority selection: {}", e);
+                    None
+                }
+            },
+        };
+
+        Ok(Self { filter, egress_address })
+    }
+}
+
+/// サーバーURLへの通信に使われる送信元アドレスを解決
+///
+/// UDPソケットをサーバーのアドレスに connect し、OSが経路表から選んだ
+/// ローカルアドレスを取得します。connect は経路を決定するだけで、パケットは送信されません。
+/// 名前解決は完了まで待機するため、非同期処理からはブロッキング処理用のスレッドで呼び出してください。
+///
+/// # 引数
+/// * `server_url` - サーバーのURL（例: "http://192.168.1.10:8080/api/pc-info"）
+///
+/// # 戻り値
+/// 送信元のIPアドレス
+///
+/// # エラー
+/// URLの解析・名前解決に失敗した場合、またはサーバーへの経路が無い場合
+pub fn resolve_egress_address(server_url: &str) -> Result<IpAddr> {
+    let url = Url::parse(server_url)
+        .map_err(|e| ClientError::NetworkError(format!("Invalid server URL '{}': {}", server_url, e)))?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| ClientError::NetworkError(format!("Server URL has no host: {}", server_url)))?;
+    let port = url.port_or_known_default().unwrap_or(80);
+
+    // IPv6リテラルは "[::1]" の形式で返されるため角括弧を外す
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let targets: Vec<SocketAddr> = (host, port)
+        .to_socket_addrs()
+        .map_err(|e| ClientError::NetworkError(format!("Failed to resolve server host '{}': {}", host, e)))?
+        .collect();
+
+    let mut last_error = None;
+    for target in targets {
+        let bind_addr: SocketAddr = if target.is_ipv4() {
+            ([0, 0, 0, 0], 0).into()
+        } else {
+            (std::net::Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+
+        let result = UdpSocket::bind(bind_addr)
+            .and_then(|socket| socket.connect(target).map(|_| socket))
+            .and_then(|socket| socket.local_addr());
+        match result {
+            Ok(local) => return Ok(local.ip()),
+            Err(e) => {
+                tracing::debug!("No route to {}: {}", target, e);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    Err(ClientError::NetworkError(match last_error {
+        Some(e) => format!("No route to server '{}': {}", host, e),
+        None => format!("Server host '{}' resolved to no addresses", host),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_egress_address_loopback() {
+        let address = resolve_egress_address("http://127.0.0.1:8080/api/pc-info").unwrap();
+        assert_eq!(address, "127.0.0.1".parse::<IpAddr>().unwrap());
+    }
+
+    #[test]
+    fn test_resolve_egress_address_invalid_url() {
+        assert!(resolve_egress_address("not a url").is_err());
+    }
+}
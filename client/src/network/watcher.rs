@@ -0,0 +1,296 @@
+use crate::error::{ClientError, Result};
+use crate::network::detector::NetworkInfo;
+use crate::state::DetectedPcInfo;
+use tokio::sync::mpsc;
+use tokio::time::{timeout, Duration};
+
+/// ネットワーク変更の監視構造体
+///
+/// OSのネットワーク変更通知を購読し、IPアドレスやリンク状態が変化したことを通知します。
+/// * Windows: NotifyIpInterfaceChange / NotifyUnicastIpAddressChange
+/// * Linux: netlink（RTMGRP_LINK, RTMGRP_IPV4_IFADDR, RTMGRP_IPV6_IFADDR）
+///
+/// 通知はまとめて扱われ、debounce 期間内に続いた通知は1回の変更とみなします。
+pub struct NetworkWatcher {
+    rx: mpsc::Receiver<()>,
+    debounce: Duration,
+    /// 最初の通知を受信済みで debounce 待ちの状態か
+    pending: bool,
+}
+
+impl NetworkWatcher {
+    /// OSのネットワーク変更通知の購読を開始
+    ///
+    /// # 引数
+    /// * `debounce` - 最後の通知からこの期間、新たな通知が無ければ変更確定とみなす
+    ///
+    /// # エラー
+    /// 通知の購読に失敗した場合、または未対応のOSの場合
+    pub fn start(debounce: Duration) -> Result<Self> {
+        let (tx, rx) = mpsc::channel(1);
+        platform::subscribe(tx)?;
+
+        tracing::info!("Network change notifications subscribed (debounce: {}s)", debounce.as_secs());
+        Ok(Self::from_receiver(rx, debounce))
+    }
+
+    /// 受信側チャネルから生成（テスト用）
+    fn from_receiver(rx: mpsc::Receiver<()>, debounce: Duration) -> Self {
+        Self {
+            rx,
+            debounce,
+            pending: false,
+        }
+    }
+
+    /// ネットワーク変更を待機
+    ///
+    /// 通知を受信した後、debounce 期間新たな通知が来なくなるまで待ってから戻ります。
+    /// リンクのフラッピングなどで通知が連続しても、呼び出し側には1回だけ通知されます。
+    /// tokio::select! でキャンセルされても、受信済みの通知は次回の呼び出しに引き継がれます。
+    ///
+    /// # 戻り値
+    /// * `true` - 変更あり
+    /// * `false` - 通知元が終了した場合（以降は通知されない）
+    pub async fn changed(&mut self) -> bool {
+        if !self.pending {
+            if self.rx.recv().await.is_none() {
+                return false;
+            }
+            self.pending = true;
+        }
+
+        // debounce 期間内に続いた通知は読み捨てる
+        while let Ok(Some(())) = timeout(self.debounce, self.rx.recv()).await {}
+
+        self.pending = false;
+        true
+    }
+}
+
+impl NetworkInfo {
+    /// 状態ファイルに保存されている送信済みの情報と比べて、
+    /// IPアドレス・MACアドレス・ネットワークタイプのいずれかが異なるか
+    pub fn differs_from(&self, pc_info: &DetectedPcInfo) -> bool {
+        self.ip_address != pc_info.ip_address
+            || self.ipv6_address != pc_info.ipv6_address
+            || self.mac_address != pc_info.mac_address
+            || self.network_type.to_string() != pc_info.network_type
+    }
+}
+
+/// 通知を送る（受信側が未処理の通知を持っている場合はまとめる）
+#[cfg_attr(not(any(windows, target_os = "linux")), allow(dead_code))]
+fn notify(tx: &mpsc::Sender<()>) {
+    let _ = tx.try_send(());
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::{notify, ClientError, Result};
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+    use tokio::sync::mpsc;
+
+    /// netlink ソケットでアドレス・リンクの変更を購読し、専用スレッドで受信する
+    pub(super) fn subscribe(tx: mpsc::Sender<()>) -> Result<()> {
+        // SAFETY: 引数は定数のみで、戻り値は直後に検証する
+        let fd = unsafe { libc::socket(libc::AF_NETLINK, libc::SOCK_RAW | libc::SOCK_CLOEXEC, libc::NETLINK_ROUTE) };
+        if fd < 0 {
+            return Err(ClientError::NetworkError(format!(
+                "Failed to open netlink socket: {}",
+                std::io::Error::last_os_error()
+            )));
+        }
+        // SAFETY: fd は直前に作成した有効なソケットで、所有権をここで一度だけ移す
+        let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        // SAFETY: sockaddr_nl はゼロ初期化が有効な C 構造体
+        let mut addr: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+        addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        addr.nl_groups = (libc::RTMGRP_LINK | libc::RTMGRP_IPV4_IFADDR | libc::RTMGRP_IPV6_IFADDR) as u32;
+
+        // SAFETY: addr は有効な sockaddr_nl で、長さも一致している
+        let result = unsafe {
+            libc::bind(
+                socket.as_raw_fd(),
+                &addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+                std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+            )
+        };
+        if result < 0 {
+            return Err(ClientError::NetworkError(format!(
+                "Failed to bind netlink socket: {}",
+                std::io::Error::last_os_error()
+            )));
+        }
+
+        std::thread::Builder::new()
+            .name("netlink-watcher".to_string())
+            .spawn(move || {
+                let mut buf = [0u8; 8192];
+                loop {
+                    // SAFETY: buf は有効な書き込み先で、長さを正しく渡している
+                    let received = unsafe {
+                        libc::recv(socket.as_raw_fd(), buf.as_mut_ptr() as *mut libc::c_void, buf.len(), 0)
+                    };
+                    if received < 0 {
+                        let error = std::io::Error::last_os_error();
+                        if error.kind() == std::io::ErrorKind::Interrupted {
+                            continue;
+                        }
+                        tracing::warn!("Netlink receive failed, stopping network watcher: {}", error);
+                        break;
+                    }
+
+                    tracing::debug!("Netlink notification received ({} bytes)", received);
+                    if tx.is_closed() {
+                        break;
+                    }
+                    notify(&tx);
+                }
+            })
+            .map_err(|e| ClientError::NetworkError(format!("Failed to start netlink watcher thread: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use super::{notify, ClientError, Result};
+    use std::ffi::c_void;
+    use tokio::sync::mpsc;
+    use windows_sys::Win32::Foundation::{HANDLE, NO_ERROR};
+    use windows_sys::Win32::NetworkManagement::IpHelper::{
+        NotifyIpInterfaceChange, NotifyUnicastIpAddressChange, MIB_IPINTERFACE_ROW, MIB_NOTIFICATION_TYPE,
+        MIB_UNICASTIPADDRESS_ROW,
+    };
+    use windows_sys::Win32::Networking::WinSock::AF_UNSPEC;
+
+    unsafe extern "system" fn on_interface_change(
+        context: *const c_void,
+        _row: *const MIB_IPINTERFACE_ROW,
+        _notification_type: MIB_NOTIFICATION_TYPE,
+    ) {
+        // SAFETY: context は subscribe でリークさせた Sender を指し、プロセス終了まで有効
+        let tx = unsafe { &*(context as *const mpsc::Sender<()>) };
+        tracing::debug!("IP interface change notification received");
+        notify(tx);
+    }
+
+    unsafe extern "system" fn on_address_change(
+        context: *const c_void,
+        _row: *const MIB_UNICASTIPADDRESS_ROW,
+        _notification_type: MIB_NOTIFICATION_TYPE,
+    ) {
+        // SAFETY: context は subscribe でリークさせた Sender を指し、プロセス終了まで有効
+        let tx = unsafe { &*(context as *const mpsc::Sender<()>) };
+        tracing::debug!("Unicast IP address change notification received");
+        notify(tx);
+    }
+
+    /// IP Helper API の変更通知を登録する
+    ///
+    /// 通知はプロセス終了まで有効なため、コールバックに渡す Sender はリークさせる。
+    pub(super) fn subscribe(tx: mpsc::Sender<()>) -> Result<()> {
+        let context = Box::into_raw(Box::new(tx)) as *const c_void;
+
+        // SAFETY: HANDLE はゼロ初期化が有効
+        let mut interface_handle: HANDLE = unsafe { std::mem::zeroed() };
+        // SAFETY: コールバックと context はプロセス終了まで有効
+        let result = unsafe {
+            NotifyIpInterfaceChange(AF_UNSPEC, Some(on_interface_change), context, false.into(), &mut interface_handle)
+        };
+        if result != NO_ERROR {
+            return Err(ClientError::NetworkError(format!(
+                "NotifyIpInterfaceChange failed: {}",
+                std::io::Error::from_raw_os_error(result as i32)
+            )));
+        }
+
+        // SAFETY: HANDLE はゼロ初期化が有効
+        let mut address_handle: HANDLE = unsafe { std::mem::zeroed() };
+        // SAFETY: コールバックと context はプロセス終了まで有効
+        let result = unsafe {
+            NotifyUnicastIpAddressChange(AF_UNSPEC, Some(on_address_change), context, false.into(), &mut address_handle)
+        };
+        if result != NO_ERROR {
+            return Err(ClientError::NetworkError(format!(
+                "NotifyUnicastIpAddressChange failed: {}",
+                std::io::Error::from_raw_os_error(result as i32)
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+mod platform {
+    use super::{ClientError, Result};
+    use tokio::sync::mpsc;
+
+    pub(super) fn subscribe(_tx: mpsc::Sender<()>) -> Result<()> {
+        Err(ClientError::NetworkError(
+            "Network change notifications are not supported on this platform".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::NetworkType;
+
+    #[tokio::test]
+    async fn test_changed_debounces_bursts() {
+        let (tx, rx) = mpsc::channel(1);
+        let mut watcher = NetworkWatcher::from_receiver(rx, Duration::from_millis(50));
+
+        let sender = tokio::spawn(async move {
+            for _ in 0..5 {
+                let _ = tx.try_send(());
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            tx
+        });
+
+        assert!(watcher.changed().await);
+        let tx = sender.await.unwrap();
+
+        // 連続した通知は1回にまとめられ、残りの通知は無い
+        assert!(timeout(Duration::from_millis(100), watcher.changed()).await.is_err());
+
+        drop(tx);
+        assert!(!watcher.changed().await);
+    }
+
+    #[test]
+    fn test_differs_from() {
+        let info = NetworkInfo {
+            ip_address: "192.168.1.100".to_string(),
+            ipv6_address: String::new(),
+            mac_address: "00:11:22:33:44:55".to_string(),
+            network_type: NetworkType::Wired,
+        };
+        let mut pc_info = DetectedPcInfo {
+            uuid: "test-uuid".to_string(),
+            mac_address: "00:11:22:33:44:55".to_string(),
+            network_type: "Wired".to_string(),
+            ip_address: "192.168.1.100".to_string(),
+            ipv6_address: String::new(),
+            os: "Windows 11 Pro".to_string(),
+            os_version: "10.0.22631".to_string(),
+            model_name: "Test Model".to_string(),
+            hardware: None,
+        };
+        assert!(!info.differs_from(&pc_info));
+
+        pc_info.ip_address = "192.168.1.101".to_string();
+        assert!(info.differs_from(&pc_info));
+
+        pc_info.ip_address = "192.168.1.100".to_string();
+        pc_info.network_type = "Wireless".to_string();
+        assert!(info.differs_from(&pc_info));
+    }
+}
@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use std::future::Future;
+use std::time::Duration;
+
+/// 現在時刻と待機の取得元
+///
+/// 送信スケジュールの判定と待機はすべてこのトレイトを介して行います。
+/// テストでは待機せずに時刻だけを進める実装に差し替えます。
+pub trait Clock {
+    /// 現在時刻
+    fn now(&self) -> DateTime<Utc>;
+
+    /// 指定時間待機
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send;
+}
+
+/// システム時計（tokio のタイマーで待機）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+        tokio::time::sleep(duration)
+    }
+}
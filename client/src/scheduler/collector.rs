@@ -0,0 +1,72 @@
+use crate::config::ClientConfig;
+use crate::error::Result;
+use crate::network::detector::NetworkInfo;
+use crate::network::{AdapterInfo, AdapterSelection, NetworkDetector};
+use crate::software::{SoftwareCollector, SoftwareInfo};
+use crate::wmi::collector::PcInfoData as SystemInfo;
+use crate::wmi::{HotfixInfo, WmiCollector};
+use pc_inventory_protocol::Section;
+
+/// PC情報の収集元
+///
+/// 送信処理（scheduler）はこのトレイトを介してPC情報を収集します。
+/// 実運用では SystemCollector（WMI・ネットワーク・ソフトウェア）を使用し、
+/// テストでは固定の値を返す実装に差し替えます。
+///
+/// WMI の問い合わせやサーバーの名前解決は完了まで待機するため、送信処理は
+/// 収集元を複製してブロッキング処理用のスレッド（tokio::task::spawn_blocking）で呼び出します。
+pub trait Collector: Clone + Send + 'static {
+    /// UUID・機種名・OS情報（WMI）
+    fn system_info(&self) -> Result<SystemInfo>;
+
+    /// 代表アダプタのネットワーク情報
+    ///
+    /// 失敗した場合、送信処理はオフライン状態として扱います。
+    fn network_info(&self, config: &ClientConfig) -> Result<NetworkInfo>;
+
+    /// すべてのネットワークアダプタ
+    fn adapters(&self, config: &ClientConfig) -> Result<Vec<AdapterInfo>>;
+
+    /// インストール済みソフトウェア
+    fn software(&self) -> Result<Vec<SoftwareInfo>>;
+
+    /// OSのビルドリビジョン（UBR）とインストール済みの更新プログラム
+    fn updates(&self) -> Result<(Option<u32>, Vec<HotfixInfo>)>;
+
+    /// 収集できる項目（サーバーに送信するクライアントの情報に含める）
+    ///
+    /// ソフトウェア一覧は設定で有効にした場合のみ収集します。
+    fn capabilities(&self, config: &ClientConfig) -> Vec<Section> {
+        Section::ALL
+            .into_iter()
+            .filter(|section| *section != Section::Software || config.software.enabled)
+            .collect()
+    }
+}
+
+/// 実際のシステムから収集する実装
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemCollector;
+
+impl Collector for SystemCollector {
+    fn system_info(&self) -> Result<SystemInfo> {
+        WmiCollector::new()?.collect_all()
+    }
+
+    fn network_info(&self, config: &ClientConfig) -> Result<NetworkInfo> {
+        NetworkDetector::get_active_adapter(&AdapterSelection::from_config(config)?)
+    }
+
+    fn adapters(&self, config: &ClientConfig) -> Result<Vec<AdapterInfo>> {
+        NetworkDetector::get_all_adapters(&AdapterSelection::from_config(config)?)
+    }
+
+    fn software(&self) -> Result<Vec<SoftwareInfo>> {
+        SoftwareCollector::collect()
+    }
+
+    fn updates(&self) -> Result<(Option<u32>, Vec<HotfixInfo>)> {
+        let wmi_collector = WmiCollector::new()?;
+        Ok((WmiCollector::get_os_build_revision()?, wmi_collector.get_hotfixes()?))
+    }
+}
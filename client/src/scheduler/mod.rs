@@ -0,0 +1,1511 @@
+pub mod clock;
+pub mod collector;
+
+pub use clock::{Clock, SystemClock};
+pub use collector::{Collector, SystemCollector};
+
+use crate::agent::{self, agent_info};
+use crate::api::{content_hash, Transport};
+use crate::config::ClientConfig;
+use crate::error::{ClientError, Result};
+use crate::network::NetworkWatcher;
+use crate::software::SoftwareSnapshot;
+use crate::state::{ClientState, DetectedPcInfo, StateStore};
+use crate::systemd::Progress;
+use crate::update::{Installer, PendingUpdate, UpdateState, MAX_START_ATTEMPTS};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use pc_inventory_protocol::{
+    compare_versions, AdapterItem, ApiVersion, CapabilitiesResponse, ConfigOverrides, HeartbeatRequest, PcAdapterRequest, PcInfoRequest, PcSoftwareRequest,
+    PcUpdateRequest, ReleaseKey, Section,
+};
+use std::cmp::Ordering;
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+/// 1回の送信で行うHTTPリクエストの最大数
+/// （対応状況・ハートビート・PC情報・付随情報3件・新しいバージョンの確認・ダウンロード）
+const MAX_REQUESTS_PER_SEND: u32 = 8;
+
+/// 状態遷移の待機・通信以外（情報の収集・設定の再読み込みなど）に許容する時間
+const STEP_GRACE: Duration = Duration::from_secs(300);
+
+/// 送信スケジューラの状態
+///
+/// ```text
+///            ┌──────────── 送信成功 ─────────────┐
+///            ▼                                   │
+///   Idle ──(定期チェック / ネットワーク変更)──▶ Sending ──(サーバーエラー等)──▶ Backoff
+///                                              ▲   │                             │
+///                                              │   └──(到達不能)──▶ Offline      │
+///                                              │                      │          │
+///                                              └──(待機時間経過 / ネットワーク変更)┘
+///
+///   Sending ──(新しいバージョンに置き換え / 試用中のバージョンの送信失敗で元に戻す)──▶ Restart
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedulerState {
+    /// 次の定期チェックまたはネットワーク変更を待機中
+    Idle,
+    /// PC情報を収集して送信する
+    Sending,
+    /// 送信に失敗し、リトライを待機中（attempt: 連続失敗回数）
+    Backoff { attempt: u32 },
+    /// ネットワークまたはサーバーに到達できず、リトライを待機中（ネットワーク変更で即時再送）
+    Offline { attempt: u32 },
+    /// 実行ファイルを置き換えた（または元に戻した）ため、サービスマネージャーによる再起動を待つ（終了状態）
+    Restart,
+}
+
+/// 送信失敗の分類
+enum SendFailure {
+    /// ネットワーク未接続、またはサーバーに到達できない
+    Offline(ClientError),
+    /// サーバーエラー、情報の収集失敗など
+    Failed(ClientError),
+}
+
+impl From<ClientError> for SendFailure {
+    fn from(e: ClientError) -> Self {
+        match e {
+            ClientError::ServerUnreachable(_) => SendFailure::Offline(e),
+            _ => SendFailure::Failed(e),
+        }
+    }
+}
+
+/// 送信する内容（Scheduler::preview）
+#[derive(Debug, Clone, Serialize)]
+pub struct Payload {
+    pub pc_info: PcInfoRequest,
+    pub adapters: PcAdapterRequest,
+    /// ソフトウェア一覧（収集が無効の場合は None）
+    pub software: Option<PcSoftwareRequest>,
+    pub updates: PcUpdateRequest,
+}
+
+/// ログレベルを変更する関数
+type LogReload = Box<dyn Fn(&str)>;
+
+/// PC情報の送信スケジューラ
+///
+/// 起動時の送信、定期送信、ネットワーク変更時の送信、失敗時のリトライを
+/// 1つの状態機械（SchedulerState）で扱います。送信処理は send の1か所のみです。
+///
+/// 時刻・情報収集・送信手段はトレイトで差し替えられるため、
+/// テストでは実際に待機・通信することなく状態遷移を確認できます。
+pub struct Scheduler<C, D, F> {
+    /// 使用する設定（ローカルの設定にサーバーから配布された設定を重ねたもの）
+    config: ClientConfig,
+    /// ローカルの設定（config.toml）
+    local_config: ClientConfig,
+    /// 設定ファイルのパス（指定した場合、待機から戻るたびに再読み込み）
+    config_path: Option<String>,
+    /// ログレベルを変更する関数（設定ファイル・配布された設定でログレベルが変わった場合に呼び出す）
+    log_reload: Option<LogReload>,
+    store: StateStore,
+    client_state: ClientState,
+    clock: C,
+    collector: D,
+    /// 設定から送信手段を生成（server.url の変更を反映するため送信のたびに呼び出す）
+    connect: F,
+    watcher: Option<NetworkWatcher>,
+    state: SchedulerState,
+    /// 連続失敗回数
+    failures: u32,
+    /// 次の送信でWMI情報も収集するか（起動後、収集に成功するまで）
+    collect_system_info: bool,
+    /// サーバーの対応状況（送信に失敗するか server.url が変更されるまで再利用）
+    capabilities: Option<CapabilitiesResponse>,
+    /// 自動更新で置き換える実行ファイル（None の場合は自動更新を行わない）
+    installer: Option<Installer>,
+    /// 試用中の更新の起動回数を確認済みか
+    update_resumed: bool,
+    /// 最後に新しいバージョンを確認した日時
+    last_update_check: Option<DateTime<Utc>>,
+    /// 処理の進行状況（状態遷移のたびに次の期限を設定し、ウォッチドッグが確認する）
+    progress: Option<Progress>,
+}
+
+impl<C, D, T, F> Scheduler<C, D, F>
+where
+    C: Clock,
+    D: Collector,
+    T: Transport,
+    F: Fn(&ClientConfig) -> Result<T>,
+{
+    /// スケジューラを作成
+    ///
+    /// 初期状態は Sending（起動直後に送信）です。
+    ///
+    /// # 引数
+    /// * `config` - クライアント設定
+    /// * `store` - 状態ファイルの書き込み窓口
+    /// * `client_state` - 読み込み済みの実行時状態
+    /// * `clock` - 時刻の取得元
+    /// * `collector` - PC情報の収集元
+    /// * `connect` - 設定から送信手段を生成する関数
+    pub fn new(config: ClientConfig, store: StateStore, client_state: ClientState, clock: C, collector: D, connect: F) -> Self {
+        Self {
+            config: effective_config(&config, &client_state),
+            local_config: config,
+            config_path: None,
+            log_reload: None,
+            store,
+            client_state,
+            clock,
+            collector,
+            connect,
+            watcher: None,
+            state: SchedulerState::Sending,
+            failures: 0,
+            collect_system_info: true,
+            capabilities: None,
+            installer: None,
+            update_resumed: false,
+            last_update_check: None,
+            progress: None,
+        }
+    }
+
+    /// 待機から戻るたびに設定ファイルを再読み込みする
+    pub fn with_config_reload(mut self, config_path: impl Into<String>) -> Self {
+        self.config_path = Some(config_path.into());
+        self
+    }
+
+    /// ログレベルの変更を反映する（呼び出し時に現在のログレベルも適用）
+    pub fn with_log_reload(mut self, reload: impl Fn(&str) + 'static) -> Self {
+        reload(&self.config.logging.level);
+        self.log_reload = Some(Box::new(reload));
+        self
+    }
+
+    /// ネットワーク変更通知を購読する
+    pub fn with_network_watcher(mut self, watcher: Option<NetworkWatcher>) -> Self {
+        self.watcher = watcher;
+        self
+    }
+
+    /// 自動更新を有効にする（update.enabled が true の場合のみ新しいバージョンを確認）
+    pub fn with_self_update(mut self, installer: Option<Installer>) -> Self {
+        self.installer = installer;
+        self
+    }
+
+    /// 状態遷移のたびに進行状況の期限を設定する（systemd のウォッチドッグで停止を検出）
+    pub fn with_progress(mut self, progress: Progress) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// 現在の状態
+    pub fn state(&self) -> SchedulerState {
+        self.state
+    }
+
+    /// 現在の実行時状態
+    pub fn client_state(&self) -> &ClientState {
+        &self.client_state
+    }
+
+    /// 再起動が必要になる（Restart に遷移する）まで状態遷移を繰り返す
+    pub async fn run(&mut self) {
+        while self.step().await != SchedulerState::Restart {}
+    }
+
+    /// 状態を1回遷移させる
+    ///
+    /// # 戻り値
+    /// 遷移後の状態
+    pub async fn step(&mut self) -> SchedulerState {
+        if let Some(progress) = &self.progress {
+            progress.expect_within(self.step_timeout());
+        }
+
+        self.state = match self.state {
+            SchedulerState::Idle => self.wait_for_trigger().await,
+            SchedulerState::Sending => self.send_or_restart().await,
+            SchedulerState::Backoff { attempt } => {
+                let delay = self.retry_delay(attempt);
+                info!("Retry scheduled in {} seconds (attempt: {})", delay.as_secs(), attempt);
+                self.clock.sleep(delay).await;
+                self.reload_config();
+                SchedulerState::Sending
+            }
+            SchedulerState::Offline { attempt } => {
+                let delay = self.retry_delay(attempt);
+                info!("Offline, retrying in {} seconds or on network change (attempt: {})", delay.as_secs(), attempt);
+                tokio::select! {
+                    _ = self.clock.sleep(delay) => {}
+                    _ = wait_network_change(&mut self.watcher) => {
+                        info!("Network change detected while offline, retrying now");
+                    }
+                }
+                self.reload_config();
+                SchedulerState::Sending
+            }
+            SchedulerState::Restart => SchedulerState::Restart,
+        };
+
+        debug!("Scheduler state: {:?}", self.state);
+        self.state
+    }
+
+    /// 現在の状態からの遷移にかかる時間の上限
+    ///
+    /// 待機時間（送信の場合はすべてのリクエストのタイムアウト）に STEP_GRACE を加えた時間です。
+    /// これを過ぎても次の遷移が始まらない場合は、スケジューラが停止しているとみなします。
+    fn step_timeout(&self) -> Duration {
+        let wait = match self.state {
+            SchedulerState::Idle => Duration::from_secs(self.config.client.check_interval_secs),
+            SchedulerState::Sending => Duration::from_secs(self.config.server.request_timeout_secs) * MAX_REQUESTS_PER_SEND,
+            SchedulerState::Backoff { attempt } | SchedulerState::Offline { attempt } => self.retry_delay(attempt),
+            SchedulerState::Restart => Duration::ZERO,
+        };
+        wait + STEP_GRACE
+    }
+
+    /// Idle: 定期チェックまたはネットワーク変更を待ち、送信が必要か判定
+    async fn wait_for_trigger(&mut self) -> SchedulerState {
+        let check_interval = Duration::from_secs(self.config.client.check_interval_secs);
+        let network_changed = tokio::select! {
+            _ = self.clock.sleep(check_interval) => {
+                debug!("Periodic check triggered");
+                false
+            }
+            changed = wait_network_change(&mut self.watcher) => {
+                debug!("Network change notification received");
+                changed
+            }
+        };
+
+        self.reload_config();
+
+        let send_reason = if network_changed {
+            if self.has_network_changed().await {
+                Some("Network information changed")
+            } else {
+                debug!("Reported network information unchanged, skipping");
+                None
+            }
+        } else if self.should_send() {
+            Some("Send interval elapsed")
+        } else {
+            debug!("Send interval not elapsed yet, skipping");
+            None
+        };
+
+        match send_reason {
+            Some(reason) => {
+                info!("{}, sending PC info", reason);
+                SchedulerState::Sending
+            }
+            None => SchedulerState::Idle,
+        }
+    }
+
+    /// PC情報を収集（起動後の初回はWMI情報も収集し、それ以外は送信済みの値を使用）
+    ///
+    /// ネットワーク情報を取得できない場合はオフラインとして扱います。
+    async fn detect_pc_info(&self) -> std::result::Result<DetectedPcInfo, SendFailure> {
+        let mut pc_info = self.client_state.pc_info.clone();
+
+        // 起動後の初回はWMI情報も収集
+        if self.collect_system_info {
+            info!("Collecting WMI information");
+            let system_info = self.collect(|collector, _| collector.system_info()).await?;
+
+            info!("WMI information collected:");
+            info!("  UUID: {}", system_info.uuid);
+            info!("  Model: {}", system_info.model_name);
+            info!("  Hardware: {:?}", system_info.hardware);
+            info!("  OS: {} ({})", system_info.os, system_info.os_version);
+            info!("  User: {}", system_info.user_name);
+
+            pc_info.uuid = system_info.uuid;
+            pc_info.model_name = system_info.model_name;
+            pc_info.hardware = Some(system_info.hardware);
+            pc_info.os = system_info.os;
+            pc_info.os_version = system_info.os_version;
+        }
+
+        // ネットワーク情報を取得（取得できない場合はオフライン）
+        let network_info = self
+            .collect(|collector, config| collector.network_info(config))
+            .await
+            .map_err(SendFailure::Offline)?;
+
+        debug!("Network information detected:");
+        debug!("  IP: {}", network_info.ip_address);
+        debug!("  IPv6: {}", network_info.ipv6_address);
+        debug!("  MAC: {}", network_info.mac_address);
+        debug!("  Type: {}", network_info.network_type);
+
+        pc_info.ip_address = network_info.ip_address;
+        pc_info.ipv6_address = network_info.ipv6_address;
+        pc_info.mac_address = network_info.mac_address;
+        pc_info.network_type = network_info.network_type.to_string();
+
+        Ok(pc_info)
+    }
+
+    /// 収集元の処理をブロッキング処理用のスレッドで実行
+    ///
+    /// WMI の問い合わせ・サーバーの名前解決（デフォルトルート方式）・パッケージ一覧の取得は
+    /// 完了まで待機するため、非同期ランタイムのスレッドを占有しないよう別のスレッドで実行します。
+    async fn collect<R>(&self, f: impl FnOnce(&D, &ClientConfig) -> Result<R> + Send + 'static) -> Result<R>
+    where
+        R: Send + 'static,
+    {
+        let collector = self.collector.clone();
+        let config = self.config.clone();
+        tokio::task::spawn_blocking(move || f(&collector, &config))
+            .await
+            .map_err(|e| ClientError::InvalidData(format!("Collector task failed: {}", e)))?
+    }
+
+    /// 送信するPC情報を作成して検証
+    fn pc_info_request(&self, pc_info: &DetectedPcInfo) -> Result<PcInfoRequest> {
+        let mut data = build_pc_info_data(&self.config, pc_info);
+        data.agent = Some(agent_info(self.collector.capabilities(&self.config)));
+        data.validate()?;
+        Ok(data)
+    }
+
+    /// Sending: PC情報を収集してサーバーに送信
+    ///
+    /// 前回送信した内容から変化が無ければハートビートのみを送信します。
+    /// 送信に成功した場合、送信したPC情報・最終送信日時・送信内容のハッシュを状態ファイルに保存し、
+    /// 付随情報（アダプタ・ソフトウェア・更新プログラム）を同期します。
+    async fn send(&mut self) -> std::result::Result<(), SendFailure> {
+        let pc_info = self.detect_pc_info().await?;
+
+        // 起動時に収集した情報は送信前に保存
+        if self.collect_system_info {
+            let detected = pc_info.clone();
+            self.client_state = self.store.update(|state| state.pc_info = detected)?;
+            self.collect_system_info = false;
+            info!("Client state updated and saved");
+        }
+
+        let mut data = self.pc_info_request(&pc_info)?;
+
+        let mut transport = (self.connect)(&self.config)?;
+        let capabilities = self.negotiate(&mut transport).await?;
+
+        // ハードウェア情報・クライアントの情報は v2 以降の項目
+        // （ハードウェア情報はサーバーが受け付ける場合のみ送信）
+        if capabilities.negotiate() < Some(ApiVersion::V2) {
+            data.hardware = None;
+            data.agent = None;
+        } else if !capabilities.accepts(Section::Hardware) {
+            data.hardware = None;
+        }
+
+        // 前回送信時から変化が無ければハートビートのみ送信
+        let hash = content_hash(&data);
+        if hash == self.client_state.last_sent_hash {
+            info!("PC information unchanged since last send, sending heartbeat");
+            let heartbeat = HeartbeatRequest {
+                uuid: data.uuid.clone(),
+                tags: data.tags.clone(),
+            };
+            match transport.send_heartbeat(&heartbeat).await? {
+                Some(response) => {
+                    info!("Server response: {} (id: {}, last_seen_at: {})",
+                        response.status, response.id, response.last_seen_at);
+
+                    let now = self.clock.now().to_rfc3339();
+                    self.client_state = self.store.update(|state| state.update_last_send_datetime(now))?;
+                    self.apply_server_config(response.config);
+
+                    self.sync_additional_info(&transport, &capabilities, &data.uuid).await;
+                    return Ok(());
+                }
+                None => warn!("Heartbeat rejected, sending full PC information"),
+            }
+        }
+
+        info!("Sending PC information to server");
+        let response = transport.send_pc_info(&data).await?;
+
+        info!("Server response: {} (action: {}, id: {})",
+            response.status, response.action, response.id);
+
+        // 送信したPC情報・最終送信日時・送信内容のハッシュを保存
+        let now = self.clock.now().to_rfc3339();
+        self.client_state = self.store.update(|state| {
+            state.pc_info = pc_info;
+            state.update_last_send_datetime(now);
+            state.update_last_sent_hash(hash);
+        })?;
+
+        info!("Last send datetime updated in client state");
+        self.apply_server_config(response.config);
+
+        // サーバーに新しく登録された場合（PC情報の削除・統合、データベースの初期化、送信先の変更）は
+        // サーバーにソフトウェア一覧が無いため、スナップショットを削除して完全な一覧を送信する
+        if response.action == "created" {
+            let snapshot_path = &self.config.software.snapshot_file;
+            match SoftwareSnapshot::remove(snapshot_path) {
+                Ok(()) => debug!("Registered as a new PC, software snapshot cleared: {}", snapshot_path),
+                Err(e) => warn!("Failed to remove software snapshot '{}': {}", snapshot_path, e),
+            }
+        }
+
+        // 付随情報を同期（失敗しても PC 情報の送信は成功扱い）
+        self.sync_additional_info(&transport, &capabilities, &data.uuid).await;
+
+        Ok(())
+    }
+
+    /// サーバーの対応状況を取得し、使用するAPIバージョンを送信手段に指定
+    ///
+    /// 取得済みの対応状況があれば再利用します。
+    async fn negotiate(&mut self, transport: &mut T) -> Result<CapabilitiesResponse> {
+        let capabilities = match &self.capabilities {
+            Some(capabilities) => capabilities.clone(),
+            None => {
+                let capabilities = transport.capabilities().await?;
+                info!("Server capabilities: API versions {:?}, sections {:?}",
+                    capabilities.api_versions, capabilities.sections);
+                self.capabilities = Some(capabilities.clone());
+                capabilities
+            }
+        };
+
+        let version = capabilities.negotiate();
+        debug!("Using API version: {}", version.map_or("unversioned", |v| v.as_str()));
+        transport.use_api_version(version);
+
+        Ok(capabilities)
+    }
+
+    /// 付随情報をサーバーに同期
+    ///
+    /// PC情報の送信成功後に呼び出します。サーバーが受け付けない項目は送信しません。
+    /// 各項目の送信失敗は警告ログのみとし、PC情報の送信自体は成功扱いとします。
+    async fn sync_additional_info(&self, transport: &T, capabilities: &CapabilitiesResponse, uuid: &str) {
+        if !capabilities.accepts(Section::Adapters) {
+            debug!("Server does not accept network adapters, skipping");
+        } else if let Err(e) = self.sync_adapters(transport, uuid).await {
+            warn!("Network adapter sync failed: {}", e);
+        }
+
+        if !capabilities.accepts(Section::Software) {
+            debug!("Server does not accept software inventory, skipping");
+        } else if let Err(e) = self.sync_software(transport, uuid).await {
+            warn!("Software inventory sync failed: {}", e);
+        }
+
+        if !capabilities.accepts(Section::Updates) {
+            debug!("Server does not accept update status, skipping");
+        } else if let Err(e) = self.sync_updates(transport, uuid).await {
+            warn!("Update status sync failed: {}", e);
+        }
+    }
+
+    /// ネットワークアダプタ一覧をサーバーに送信
+    ///
+    /// 代表アダプタ以外も含むすべてのアダプタを、毎回完全な一覧として送信します。
+    async fn sync_adapters(&self, transport: &T, uuid: &str) -> Result<()> {
+        let data = self.adapter_request(uuid).await?;
+
+        let response = transport.send_adapters(&data).await?;
+        info!("Network adapters synced: {} (id: {}, adapters: {})",
+            response.status, response.id, response.adapters);
+
+        Ok(())
+    }
+
+    /// ソフトウェア一覧をサーバーに同期
+    ///
+    /// 前回送信したスナップショットと比較し、変更があった場合のみ差分を送信します。
+    /// スナップショットが無い場合（初回、サーバーに新しく登録された場合）は完全な一覧を送信します。
+    /// 送信に成功した場合のみスナップショットを更新するため、
+    /// 失敗した差分は次回の送信時に改めて送られます。
+    async fn sync_software(&self, transport: &T, uuid: &str) -> Result<()> {
+        if !self.config.software.enabled {
+            debug!("Software inventory collection is disabled");
+            return Ok(());
+        }
+
+        let current = self.collect(|collector, _| collector.software()).await?;
+        let snapshot_path = &self.config.software.snapshot_file;
+
+        let data = match SoftwareSnapshot::load(snapshot_path)? {
+            Some(snapshot) => {
+                let diff = snapshot.diff(&current);
+                if diff.is_empty() {
+                    debug!("Software inventory unchanged, skipping send");
+                    return Ok(());
+                }
+                PcSoftwareRequest {
+                    uuid: uuid.to_string(),
+                    full_sync: false,
+                    added: diff.added,
+                    removed: diff.removed,
+                }
+            }
+            None => PcSoftwareRequest {
+                uuid: uuid.to_string(),
+                full_sync: true,
+                added: current.clone(),
+                removed: Vec::new(),
+            },
+        };
+
+        let response = transport.send_software(&data).await?;
+        info!("Software inventory synced: {} (id: {}, added: {}, removed: {})",
+            response.status, response.id, response.added, response.removed);
+
+        SoftwareSnapshot { software: current }.save(snapshot_path)?;
+        debug!("Software snapshot saved to: {}", snapshot_path);
+
+        Ok(())
+    }
+
+    /// Windows Update適用状況をサーバーに送信
+    ///
+    /// UBR（ビルドリビジョン）と Win32_QuickFixEngineering の更新プログラム一覧を送信します。
+    /// 一覧は小さいため、差分ではなく毎回完全な一覧を送信します。
+    async fn sync_updates(&self, transport: &T, uuid: &str) -> Result<()> {
+        let data = self.update_request(uuid).await?;
+
+        let response = transport.send_updates(&data).await?;
+        info!("Update status synced: {} (id: {}, hotfixes: {})",
+            response.status, response.id, response.hotfixes);
+
+        Ok(())
+    }
+
+    /// 送信するネットワークアダプタ一覧を収集
+    async fn adapter_request(&self, uuid: &str) -> Result<PcAdapterRequest> {
+        Ok(PcAdapterRequest {
+            uuid: uuid.to_string(),
+            adapters: self
+                .collect(|collector, config| collector.adapters(config))
+                .await?
+                .into_iter()
+                .map(AdapterItem::from)
+                .collect(),
+        })
+    }
+
+    /// 送信するWindows Update適用状況を収集
+    async fn update_request(&self, uuid: &str) -> Result<PcUpdateRequest> {
+        let (os_build_revision, hotfixes) = self.collect(|collector, _| collector.updates()).await?;
+
+        Ok(PcUpdateRequest {
+            uuid: uuid.to_string(),
+            os_build_revision,
+            hotfixes,
+        })
+    }
+
+    /// 送信する内容を収集（送信・状態ファイルへの保存は行わない）
+    ///
+    /// 起動直後の送信と同じ手順で収集します。サーバーとは通信しないため、
+    /// サーバーが受け付けない項目も含み、ソフトウェア一覧は差分ではなく完全な一覧とします。
+    ///
+    /// # エラー
+    /// 情報の収集に失敗した場合、または送信内容が不正な場合
+    pub async fn preview(&self) -> Result<Payload> {
+        let pc_info = self.detect_pc_info().await.map_err(|failure| match failure {
+            SendFailure::Offline(e) | SendFailure::Failed(e) => e,
+        })?;
+
+        let software = if self.config.software.enabled {
+            Some(PcSoftwareRequest {
+                uuid: pc_info.uuid.clone(),
+                full_sync: true,
+                added: self.collect(|collector, _| collector.software()).await?,
+                removed: Vec::new(),
+            })
+        } else {
+            None
+        };
+
+        Ok(Payload {
+            pc_info: self.pc_info_request(&pc_info)?,
+            adapters: self.adapter_request(&pc_info.uuid).await?,
+            software,
+            updates: self.update_request(&pc_info.uuid).await?,
+        })
+    }
+
+    /// 送信し、結果に応じて次の状態を決定
+    ///
+    /// 試用中の更新の確認・確定・取り消しと、新しいバージョンの確認もここで行います。
+    /// 置き換える実行ファイルが無い場合（--once など with_self_update を指定しない場合）は、
+    /// 試用中の更新には触れず、常駐しているクライアントに任せます。
+    /// 試用中の更新は、サーバーに到達したうえで送信に失敗した場合のみ取り消します。
+    async fn send_or_restart(&mut self) -> SchedulerState {
+        if self.resume_update() {
+            return SchedulerState::Restart;
+        }
+
+        match self.send().await {
+            Ok(()) => {
+                self.failures = 0;
+                self.confirm_update();
+                if self.check_for_update().await {
+                    SchedulerState::Restart
+                } else {
+                    SchedulerState::Idle
+                }
+            }
+            Err(SendFailure::Offline(e)) => {
+                // サーバーに到達できない場合は新しいバージョンの問題とは限らないため、試用中の更新は取り消さない
+                // （到達できないまま再起動を繰り返した場合は resume_update の起動回数の上限で取り消す）
+                self.failures += 1;
+                self.capabilities = None;
+                warn!("Server unreachable: {}", e);
+                SchedulerState::Offline { attempt: self.failures }
+            }
+            Err(SendFailure::Failed(e)) => {
+                self.failures += 1;
+                self.capabilities = None;
+                error!("Send failed: {}", e);
+                if self.rollback_update() {
+                    SchedulerState::Restart
+                } else {
+                    SchedulerState::Backoff { attempt: self.failures }
+                }
+            }
+        }
+    }
+
+    /// 起動後最初の送信の前に、試用中の更新の起動回数を確認
+    ///
+    /// 最初の送信に至らずに起動を繰り返している（異常終了している）場合は元のバージョンに戻します。
+    ///
+    /// # 戻り値
+    /// 元のバージョンに戻した場合は true（再起動が必要）
+    fn resume_update(&mut self) -> bool {
+        if std::mem::replace(&mut self.update_resumed, true) || self.installer.is_none() {
+            return false;
+        }
+        let Some(pending) = self.client_state.update.pending.clone() else {
+            return false;
+        };
+
+        if pending.version != agent::VERSION {
+            // 置き換え後のバージョンが起動していない（置き換えの途中で終了した場合など）
+            warn!("Pending update to {} is not running (current: {}), discarding", pending.version, agent::VERSION);
+            self.save_update_state(UpdateState::reject_pending);
+            return false;
+        }
+
+        if pending.attempts >= MAX_START_ATTEMPTS {
+            error!("Updated version {} did not complete its first send after {} starts", pending.version, pending.attempts);
+            return self.rollback_update();
+        }
+
+        info!("Running updated version {} (previous: {}, start: {})",
+            pending.version, pending.previous_version, pending.attempts + 1);
+        self.save_update_state(|update| {
+            if let Some(pending) = update.pending.as_mut() {
+                pending.attempts += 1;
+            }
+        });
+        false
+    }
+
+    /// 試用中の更新を確定（置き換え後の最初の送信に成功した場合）
+    fn confirm_update(&mut self) {
+        let (Some(pending), Some(installer)) = (&self.client_state.update.pending, &self.installer) else {
+            return;
+        };
+
+        info!("Update to {} confirmed (previous: {})", pending.version, pending.previous_version);
+        if let Err(e) = installer.commit() {
+            warn!("Failed to remove previous executable: {}", e);
+        }
+        self.save_update_state(|update| update.pending = None);
+    }
+
+    /// 試用中の更新を取り消し、置き換え前の実行ファイルに戻す
+    ///
+    /// 戻したバージョンは rejected_versions に記録し、再び適用しません。
+    ///
+    /// # 戻り値
+    /// 元に戻した場合は true（再起動が必要）
+    fn rollback_update(&mut self) -> bool {
+        let Some(pending) = self.client_state.update.pending.clone() else {
+            return false;
+        };
+        let Some(installer) = &self.installer else {
+            return false;
+        };
+
+        if let Err(e) = installer.rollback() {
+            // 元に戻せない場合は置き換え後のバージョンで動作を続ける
+            error!("Failed to roll back update to {}: {}", pending.version, e);
+            self.save_update_state(|update| update.pending = None);
+            return false;
+        }
+
+        warn!("Update to {} rolled back to {}, restarting", pending.version, pending.previous_version);
+        self.save_update_state(UpdateState::reject_pending);
+        true
+    }
+
+    /// 新しいバージョンを確認し、あれば実行ファイルを置き換える
+    ///
+    /// update.check_interval_secs ごとに、送信に成功した後で確認します。
+    /// 確認・置き換えに失敗しても送信には影響しません（警告ログのみ）。
+    ///
+    /// # 戻り値
+    /// 置き換えた場合は true（再起動が必要）
+    async fn check_for_update(&mut self) -> bool {
+        if !self.config.update.enabled || self.installer.is_none() {
+            return false;
+        }
+
+        let now = self.clock.now();
+        if let Some(last_check) = self.last_update_check {
+            let elapsed = now.signed_duration_since(last_check).num_seconds();
+            if elapsed < self.config.update.check_interval_secs as i64 {
+                return false;
+            }
+        }
+        self.last_update_check = Some(now);
+
+        match self.install_update().await {
+            Ok(installed) => installed,
+            Err(e) => {
+                warn!("Self-update failed: {}", e);
+                false
+            }
+        }
+    }
+
+    /// 配布チャネルの最新のバージョンを取得し、署名を検証して実行ファイルを置き換える
+    ///
+    /// # 戻り値
+    /// * `Ok(true)` - 置き換えた場合
+    /// * `Ok(false)` - 新しいバージョンが無い場合（元に戻したバージョンを含む）
+    async fn install_update(&mut self) -> Result<bool> {
+        let key = ReleaseKey::parse(&self.config.update.public_key)?;
+        let transport = (self.connect)(&self.config)?;
+
+        let channel = &self.config.update.channel;
+        let Some(release) = transport.latest_release(channel, agent::TARGET).await? else {
+            debug!("No client release published to channel '{}' for {}", channel, agent::TARGET);
+            return Ok(false);
+        };
+
+        if compare_versions(&release.version, agent::VERSION) != Ordering::Greater {
+            debug!("Client is up to date (current: {}, latest: {})", agent::VERSION, release.version);
+            return Ok(false);
+        }
+
+        if self.client_state.update.rejected_versions.contains(&release.version) {
+            debug!("Skipping version {} (rolled back previously)", release.version);
+            return Ok(false);
+        }
+
+        if release.target != agent::TARGET {
+            return Err(ClientError::UpdateError(format!(
+                "Release {} is built for {}, not {}",
+                release.version, release.target, agent::TARGET
+            )));
+        }
+
+        info!("New client version available: {} (current: {})", release.version, agent::VERSION);
+        let binary = transport.download_release(&release).await?;
+        release.verify(&key, channel, &binary)?;
+        info!("Release {} signature verified", release.version);
+
+        // 置き換える前に試用中として記録（置き換えに失敗した場合は取り消す）
+        let pending = PendingUpdate {
+            version: release.version.clone(),
+            previous_version: agent::VERSION.to_string(),
+            attempts: 0,
+        };
+        self.client_state = self.store.update(|state| state.update.pending = Some(pending))?;
+
+        if let Some(installer) = &self.installer {
+            if let Err(e) = installer.install(&binary) {
+                self.save_update_state(|update| update.pending = None);
+                return Err(e);
+            }
+        }
+
+        info!("Client updated to {}, restarting", release.version);
+        Ok(true)
+    }
+
+    /// 自動更新の状態を保存（失敗した場合はエラーログのみ）
+    fn save_update_state(&mut self, f: impl FnOnce(&mut UpdateState)) {
+        match self.store.update(|state| f(&mut state.update)) {
+            Ok(state) => self.client_state = state,
+            Err(e) => error!("Failed to save update state: {}", e),
+        }
+    }
+
+    /// 送信済みのネットワーク情報（client_state.pc_info）から変化したかチェック
+    ///
+    /// IPアドレス・MACアドレス・ネットワークタイプのいずれかが異なる場合に true を返します。
+    async fn has_network_changed(&self) -> bool {
+        match self.collect(|collector, config| collector.network_info(config)).await {
+            Ok(network_info) => network_info.differs_from(&self.client_state.pc_info),
+            Err(e) => {
+                warn!("Failed to detect network after change notification: {}", e);
+                false
+            }
+        }
+    }
+
+    /// 送信が必要かチェック
+    ///
+    /// 最終送信日時から send_interval_secs が経過しているか確認します。
+    fn should_send(&self) -> bool {
+        // 最終送信日時が空の場合は送信必要
+        if self.client_state.last_send_datetime.is_empty() {
+            debug!("No last send datetime, should send");
+            return true;
+        }
+
+        // 最終送信日時をパース
+        match chrono::DateTime::parse_from_rfc3339(&self.client_state.last_send_datetime) {
+            Ok(last_send) => {
+                let elapsed = self.clock.now().signed_duration_since(last_send.with_timezone(&Utc));
+                let elapsed_secs = elapsed.num_seconds();
+
+                debug!("Last send: {} ({} seconds ago)", last_send, elapsed_secs);
+
+                elapsed_secs >= self.config.client.send_interval_secs as i64
+            }
+            Err(e) => {
+                warn!("Failed to parse last send datetime: {}", e);
+                // パースエラーの場合は送信する
+                true
+            }
+        }
+    }
+
+    /// リトライまでの待機時間
+    ///
+    /// first_retry_delay_secs → second_retry_delay_secs を交互に繰り返します。
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        let secs = if attempt.is_multiple_of(2) {
+            self.config.retry.second_retry_delay_secs
+        } else {
+            self.config.retry.first_retry_delay_secs
+        };
+        Duration::from_secs(secs)
+    }
+
+    /// 設定ファイルを再読み込み（失敗した場合は既存の設定で継続）
+    fn reload_config(&mut self) {
+        let Some(path) = &self.config_path else {
+            return;
+        };
+
+        match ClientConfig::load(path) {
+            Ok(config) => {
+                self.local_config = config;
+                self.refresh_config();
+                debug!("Configuration reloaded");
+            }
+            Err(e) => warn!("Failed to reload configuration: {}", e),
+        }
+    }
+
+    /// サーバーから配布された設定を保存して反映
+    ///
+    /// 配布された設定が無い場合（None）はローカルの設定に戻します。
+    /// 不正な値を含む設定は適用せず、それまでの設定を使い続けます。
+    fn apply_server_config(&mut self, config: Option<ConfigOverrides>) {
+        let config = config.filter(|config| !config.is_empty());
+        if config == self.client_state.server_config {
+            return;
+        }
+
+        if let Some(config) = &config {
+            if let Err(e) = config.validate() {
+                warn!("Ignoring invalid client config from server: {}", e);
+                return;
+            }
+            info!("Client config received from server: {:?}", config);
+        } else {
+            info!("Client config from server cleared, using local configuration");
+        }
+
+        match self.store.update(|state| state.server_config = config) {
+            Ok(state) => self.client_state = state,
+            Err(e) => {
+                error!("Failed to save client config from server: {}", e);
+                return;
+            }
+        }
+        self.refresh_config();
+    }
+
+    /// ローカルの設定にサーバーから配布された設定を重ねて、使用する設定を更新
+    fn refresh_config(&mut self) {
+        let config = effective_config(&self.local_config, &self.client_state);
+
+        if config.server.url != self.config.server.url {
+            // 送信先が変わった場合は対応状況を取得し直す
+            self.capabilities = None;
+        }
+
+        if config.logging.level != self.config.logging.level {
+            info!("Log level changed: {} -> {}", self.config.logging.level, config.logging.level);
+            if let Some(reload) = &self.log_reload {
+                reload(&config.logging.level);
+            }
+        }
+
+        self.config = config;
+    }
+}
+
+/// ローカルの設定に、実行時状態に保存した配布された設定を重ねる
+fn effective_config(local: &ClientConfig, client_state: &ClientState) -> ClientConfig {
+    let mut config = local.clone();
+    if let Some(overrides) = &client_state.server_config {
+        config.apply_overrides(overrides);
+    }
+    config
+}
+
+/// 送信データを作成
+fn build_pc_info_data(config: &ClientConfig, pc_info: &DetectedPcInfo) -> PcInfoRequest {
+    PcInfoRequest {
+        uuid: pc_info.uuid.clone(),
+        mac_address: pc_info.mac_address.clone(),
+        network_type: pc_info.network_type.parse().unwrap_or_default(),
+        user_name: config.pc_info.user_name.clone(),
+        ip_address: pc_info.ip_address.clone(),
+        ipv6_address: Some(pc_info.ipv6_address.clone()).filter(|ip| !ip.is_empty()),
+        os: pc_info.os.clone(),
+        os_version: pc_info.os_version.clone(),
+        model_name: pc_info.model_name.clone(),
+        hardware: pc_info.hardware.clone(),
+        agent: None,
+        tags: config.pc_info.tags.clone(),
+    }
+}
+
+/// ネットワーク変更通知を待機
+///
+/// 通知を購読していない場合、または通知元が終了した場合は永久に待機します
+/// （定期チェックのみで動作）。
+async fn wait_network_change(watcher: &mut Option<NetworkWatcher>) -> bool {
+    let Some(active) = watcher.as_mut() else {
+        return std::future::pending().await;
+    };
+
+    if active.changed().await {
+        return true;
+    }
+
+    warn!("Network change notifications stopped, relying on periodic check");
+    *watcher = None;
+    std::future::pending().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{MemoryTransport, MemoryTransportMode};
+    use crate::testing::{FakeClock, FakeCollector};
+    use pc_inventory_protocol::release::sha256_hex;
+    use pc_inventory_protocol::{ReleaseInfo, ReleaseSigner};
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    fn test_config() -> ClientConfig {
+        toml::from_str(
+            r#"
+            [server]
+            url = "http://127.0.0.1:8080/api/pc-info"
+            request_timeout_secs = 5
+
+            [client]
+            check_interval_secs = 3600
+            send_interval_secs = 21600
+            watch_network_changes = false
+
+            [retry]
+            first_retry_delay_secs = 900
+            second_retry_delay_secs = 3600
+
+            [pc_info]
+            user_name = "testuser"
+
+            [logging]
+            level = "info"
+            file = "client.log"
+            max_file_size_mb = 50
+            max_backup_files = 3
+
+            [software]
+            enabled = false
+            "#,
+        )
+        .unwrap()
+    }
+
+    struct Fixture {
+        clock: FakeClock,
+        collector: FakeCollector,
+        transport: MemoryTransport,
+        state_path: PathBuf,
+    }
+
+    impl Fixture {
+        fn new(name: &str) -> Self {
+            Self {
+                clock: FakeClock::new(),
+                collector: FakeCollector::new(),
+                transport: MemoryTransport::new(),
+                state_path: std::env::temp_dir().join(format!("scheduler_{}_{}.json", name, std::process::id())),
+            }
+        }
+
+        fn scheduler(
+            &self,
+        ) -> Scheduler<FakeClock, FakeCollector, impl Fn(&ClientConfig) -> Result<MemoryTransport>> {
+            self.scheduler_with(test_config(), ClientState::default())
+        }
+
+        fn scheduler_with(
+            &self,
+            config: ClientConfig,
+            state: ClientState,
+        ) -> Scheduler<FakeClock, FakeCollector, impl Fn(&ClientConfig) -> Result<MemoryTransport>> {
+            let transport = self.transport.clone();
+            Scheduler::new(
+                config,
+                StateStore::new(&self.state_path),
+                state,
+                self.clock.clone(),
+                self.collector.clone(),
+                move |_: &ClientConfig| Ok(transport.clone()),
+            )
+        }
+
+        /// 置き換え対象の実行ファイル（内容は "current"）
+        fn installer(&self) -> Installer {
+            let path = self.state_path.with_extension("exe");
+            std::fs::write(&path, b"current").unwrap();
+            Installer::new(path)
+        }
+
+        /// 現在のバージョンに置き換えた直後（試用中）の状態を保存
+        fn save_pending_update(&self) -> ClientState {
+            StateStore::new(&self.state_path)
+                .update(|state| {
+                    state.update.pending = Some(PendingUpdate {
+                        version: agent::VERSION.to_string(),
+                        previous_version: "0.0.1".to_string(),
+                        attempts: 0,
+                    })
+                })
+                .unwrap()
+        }
+
+        fn read_exe(&self, extension: &str) -> Option<Vec<u8>> {
+            std::fs::read(self.state_path.with_extension(extension)).ok()
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.state_path);
+            let _ = std::fs::remove_file(self.state_path.with_extension("json.lock"));
+            for extension in ["exe", "exe.new", "exe.old", "exe.failed", "software.json"] {
+                let _ = std::fs::remove_file(self.state_path.with_extension(extension));
+            }
+        }
+    }
+
+    fn update_config(signer: &ReleaseSigner) -> ClientConfig {
+        let mut config = test_config();
+        config.update.enabled = true;
+        config.update.public_key = signer.public_key().to_string();
+        config
+    }
+
+    fn signed_release(signer: &ReleaseSigner, version: &str, binary: &[u8]) -> ReleaseInfo {
+        ReleaseInfo {
+            id: 1,
+            channel: "stable".to_string(),
+            target: agent::TARGET.to_string(),
+            version: version.to_string(),
+            sha256: sha256_hex(binary),
+            size: binary.len() as u64,
+            signature: signer.sign("stable", agent::TARGET, version, binary),
+            published_at: Utc::now(),
+        }
+    }
+
+
+    #[tokio::test]
+    async fn test_startup_send_then_heartbeat_after_interval() {
+        let fixture = Fixture::new("heartbeat");
+        let mut scheduler = fixture.scheduler();
+
+        // 起動直後に送信
+        assert_eq!(scheduler.state(), SchedulerState::Sending);
+        assert_eq!(scheduler.step().await, SchedulerState::Idle);
+        assert_eq!(fixture.transport.sent_pc_info().len(), 1);
+
+        let saved = StateStore::new(&fixture.state_path).load().unwrap();
+        assert_eq!(saved.pc_info.uuid, "test-uuid");
+        assert!(!saved.last_sent_hash.is_empty());
+
+        // 送信間隔（6時間）が経過するまでは定期チェックのみ
+        for _ in 0..5 {
+            assert_eq!(scheduler.step().await, SchedulerState::Idle);
+        }
+        assert_eq!(scheduler.step().await, SchedulerState::Sending);
+        assert_eq!(fixture.clock.elapsed_secs(), 21600);
+
+        // 内容に変化が無いためハートビートのみ
+        assert_eq!(scheduler.step().await, SchedulerState::Idle);
+        assert_eq!(fixture.transport.sent_pc_info().len(), 1);
+        assert_eq!(fixture.transport.heartbeats().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_changed_info_sends_full_data() {
+        let fixture = Fixture::new("changed");
+        let mut scheduler = fixture.scheduler();
+        scheduler.step().await;
+
+        fixture.collector.set_ip_address("192.168.1.101");
+        for _ in 0..6 {
+            scheduler.step().await;
+        }
+        assert_eq!(scheduler.state(), SchedulerState::Sending);
+        assert_eq!(scheduler.step().await, SchedulerState::Idle);
+
+        assert_eq!(fixture.transport.sent_pc_info().len(), 2);
+        assert_eq!(fixture.transport.heartbeats().len(), 0);
+        assert_eq!(scheduler.client_state().pc_info.ip_address, "192.168.1.101");
+    }
+
+    #[tokio::test]
+    async fn test_new_registration_sends_full_software() {
+        let fixture = Fixture::new("software_full_sync");
+        let mut config = test_config();
+        config.software.enabled = true;
+        config.software.snapshot_file = fixture.state_path.with_extension("software.json").to_string_lossy().into_owned();
+        let mut scheduler = fixture.scheduler_with(config, ClientState::default());
+
+        assert_eq!(scheduler.step().await, SchedulerState::Idle);
+        let sent = fixture.transport.sent_software();
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].full_sync);
+
+        // ソフトウェア一覧に変化が無ければ送信しない
+        for _ in 0..7 {
+            scheduler.step().await;
+        }
+        assert_eq!(fixture.transport.heartbeats().len(), 1);
+        assert_eq!(fixture.transport.sent_software().len(), 1);
+
+        // サーバーでPC情報が削除された場合、ハートビートが拒否されて新しく登録され、完全な一覧を送信する
+        fixture.transport.forget_registrations();
+        for _ in 0..7 {
+            scheduler.step().await;
+        }
+        assert_eq!(fixture.transport.heartbeats().len(), 2);
+        assert_eq!(fixture.transport.sent_pc_info().len(), 2);
+        let sent = fixture.transport.sent_software();
+        assert_eq!(sent.len(), 2);
+        assert!(sent[1].full_sync);
+        assert_eq!(sent[1].added.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_step_sets_progress_deadline() {
+        let fixture = Fixture::new("progress");
+        fixture.transport.set_mode(MemoryTransportMode::ServerError);
+        let progress = Progress::new();
+        let mut scheduler = fixture.scheduler().with_progress(progress.clone());
+
+        // 遷移のたびに、次の状態の待機時間に応じた期限を設定する
+        assert_eq!(scheduler.step_timeout(), Duration::from_secs(5 * 8) + STEP_GRACE);
+        assert_eq!(scheduler.step().await, SchedulerState::Backoff { attempt: 1 });
+        assert!(!progress.is_stalled());
+        assert_eq!(scheduler.step_timeout(), Duration::from_secs(900) + STEP_GRACE);
+
+        fixture.transport.set_mode(MemoryTransportMode::Online);
+        scheduler.step().await;
+        assert_eq!(scheduler.step().await, SchedulerState::Idle);
+        assert_eq!(scheduler.step_timeout(), Duration::from_secs(3600) + STEP_GRACE);
+
+        // 遷移が始まらないまま期限を過ぎた場合は停止とみなす
+        progress.expect_within(Duration::ZERO);
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(progress.is_stalled());
+        scheduler.step().await;
+        assert!(!progress.is_stalled());
+    }
+
+    #[tokio::test]
+    async fn test_server_error_backs_off_alternately() {
+        let fixture = Fixture::new("backoff");
+        fixture.transport.set_mode(MemoryTransportMode::ServerError);
+        let mut scheduler = fixture.scheduler();
+
+        assert_eq!(scheduler.step().await, SchedulerState::Backoff { attempt: 1 });
+        assert_eq!(scheduler.step().await, SchedulerState::Sending);
+        assert_eq!(fixture.clock.elapsed_secs(), 900);
+
+        assert_eq!(scheduler.step().await, SchedulerState::Backoff { attempt: 2 });
+        assert_eq!(scheduler.step().await, SchedulerState::Sending);
+        assert_eq!(fixture.clock.elapsed_secs(), 900 + 3600);
+
+        // 復旧後は送信に成功して Idle に戻り、失敗回数はリセットされる
+        fixture.transport.set_mode(MemoryTransportMode::Online);
+        assert_eq!(scheduler.step().await, SchedulerState::Idle);
+        assert_eq!(fixture.transport.sent_pc_info().len(), 1);
+
+        let saved = StateStore::new(&fixture.state_path).load().unwrap();
+        assert_eq!(saved.last_send_datetime, fixture.clock.now().to_rfc3339());
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_goes_offline() {
+        let fixture = Fixture::new("offline");
+        fixture.transport.set_mode(MemoryTransportMode::Unreachable);
+        let mut scheduler = fixture.scheduler();
+
+        assert_eq!(scheduler.step().await, SchedulerState::Offline { attempt: 1 });
+        assert_eq!(scheduler.step().await, SchedulerState::Sending);
+
+        // ネットワークアダプタが無い場合もオフライン
+        fixture.transport.set_mode(MemoryTransportMode::Online);
+        fixture.collector.disconnect();
+        assert_eq!(scheduler.step().await, SchedulerState::Offline { attempt: 2 });
+        assert_eq!(fixture.transport.sent_pc_info().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_sends_only_sections_server_accepts() {
+        let fixture = Fixture::new("capabilities_current");
+        let mut scheduler = fixture.scheduler();
+        assert_eq!(scheduler.step().await, SchedulerState::Idle);
+        let sent = &fixture.transport.sent_pc_info()[0];
+        assert!(sent.hardware.is_some());
+        // ソフトウェア一覧の収集は無効（test_config）
+        let agent = sent.agent.as_ref().unwrap();
+        assert_eq!(agent.version, crate::agent::VERSION);
+        assert_eq!(agent.capabilities, vec![Section::Hardware, Section::Adapters, Section::Updates]);
+        assert_eq!(fixture.transport.sent_adapters().len(), 1);
+        assert_eq!(fixture.transport.sent_updates().len(), 1);
+
+        // v1 のみ・アダプタ一覧を受け付けないサーバー
+        let fixture = Fixture::new("capabilities_v1");
+        fixture.transport.set_capabilities(CapabilitiesResponse::new(
+            vec![ApiVersion::V1],
+            vec![Section::Hardware, Section::Updates],
+        ));
+        let mut scheduler = fixture.scheduler();
+        assert_eq!(scheduler.step().await, SchedulerState::Idle);
+        assert_eq!(fixture.transport.sent_pc_info()[0].hardware, None);
+        assert_eq!(fixture.transport.sent_pc_info()[0].agent, None);
+        assert!(fixture.transport.sent_adapters().is_empty());
+        assert_eq!(fixture.transport.sent_updates().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_installs_signed_newer_release() {
+        let fixture = Fixture::new("update_install");
+        let signer = ReleaseSigner::from_bytes([7; 32]);
+        fixture.transport.publish_release(signed_release(&signer, "999.0.0", b"new version"), b"new version".to_vec());
+
+        let mut scheduler = fixture
+            .scheduler_with(update_config(&signer), ClientState::default())
+            .with_self_update(Some(fixture.installer()));
+
+        // 送信に成功した後で新しいバージョンに置き換えて再起動
+        assert_eq!(scheduler.step().await, SchedulerState::Restart);
+        assert_eq!(fixture.transport.sent_pc_info().len(), 1);
+        assert_eq!(fixture.read_exe("exe").unwrap(), b"new version");
+        assert_eq!(fixture.read_exe("exe.old").unwrap(), b"current");
+
+        let saved = StateStore::new(&fixture.state_path).load().unwrap();
+        let pending = saved.update.pending.unwrap();
+        assert_eq!(pending.version, "999.0.0");
+        assert_eq!(pending.previous_version, agent::VERSION);
+        assert_eq!(pending.attempts, 0);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_tampered_release() {
+        let fixture = Fixture::new("update_tampered");
+        let signer = ReleaseSigner::from_bytes([7; 32]);
+        fixture.transport.publish_release(signed_release(&signer, "999.0.0", b"new version"), b"tampered!!!".to_vec());
+
+        let mut scheduler = fixture
+            .scheduler_with(update_config(&signer), ClientState::default())
+            .with_self_update(Some(fixture.installer()));
+
+        // 検証に失敗しても送信は継続
+        assert_eq!(scheduler.step().await, SchedulerState::Idle);
+        assert_eq!(fixture.transport.downloads(), vec![1]);
+        assert_eq!(fixture.read_exe("exe").unwrap(), b"current");
+        assert!(fixture.read_exe("exe.old").is_none());
+        assert_eq!(scheduler.client_state().update.pending, None);
+    }
+
+    #[tokio::test]
+    async fn test_failed_first_send_rolls_back() {
+        let fixture = Fixture::new("update_rollback");
+        let installer = fixture.installer();
+        installer.install(b"updated").unwrap();
+        fixture.transport.set_mode(MemoryTransportMode::ServerError);
+
+        let mut scheduler = fixture
+            .scheduler_with(test_config(), fixture.save_pending_update())
+            .with_self_update(Some(installer));
+
+        assert_eq!(scheduler.step().await, SchedulerState::Restart);
+        assert_eq!(fixture.read_exe("exe").unwrap(), b"current");
+        assert_eq!(fixture.read_exe("exe.failed").unwrap(), b"updated");
+
+        let saved = StateStore::new(&fixture.state_path).load().unwrap();
+        assert_eq!(saved.update.pending, None);
+        assert_eq!(saved.update.rejected_versions, vec![agent::VERSION.to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_offline_first_send_keeps_update() {
+        let fixture = Fixture::new("update_offline");
+        let installer = fixture.installer();
+        installer.install(b"updated").unwrap();
+        fixture.transport.set_mode(MemoryTransportMode::Unreachable);
+
+        let mut scheduler = fixture
+            .scheduler_with(test_config(), fixture.save_pending_update())
+            .with_self_update(Some(installer));
+
+        // サーバーに到達できない間は試用中のまま送信を再試行する
+        assert_eq!(scheduler.step().await, SchedulerState::Offline { attempt: 1 });
+        assert_eq!(fixture.read_exe("exe").unwrap(), b"updated");
+        assert_eq!(fixture.read_exe("exe.old").unwrap(), b"current");
+
+        let saved = StateStore::new(&fixture.state_path).load().unwrap();
+        assert_eq!(saved.update.pending.as_ref().map(|pending| pending.attempts), Some(1));
+        assert!(saved.update.rejected_versions.is_empty());
+
+        // 復旧後の送信に成功した時点で確定する
+        fixture.transport.set_mode(MemoryTransportMode::Online);
+        assert_eq!(scheduler.step().await, SchedulerState::Sending);
+        assert_eq!(scheduler.step().await, SchedulerState::Idle);
+        assert!(fixture.read_exe("exe.old").is_none());
+
+        let saved = StateStore::new(&fixture.state_path).load().unwrap();
+        assert_eq!(saved.update, UpdateState::default());
+    }
+
+    #[tokio::test]
+    async fn test_without_installer_keeps_pending_update() {
+        let fixture = Fixture::new("update_once");
+        let installer = fixture.installer();
+        installer.install(b"updated").unwrap();
+        let pending = fixture.save_pending_update();
+
+        // --once と同じく with_self_update を指定しない
+        let mut scheduler = fixture.scheduler_with(test_config(), pending.clone());
+        assert_eq!(scheduler.step().await, SchedulerState::Idle);
+
+        fixture.transport.set_mode(MemoryTransportMode::ServerError);
+        let mut scheduler = fixture.scheduler_with(test_config(), pending.clone());
+        assert_eq!(scheduler.step().await, SchedulerState::Backoff { attempt: 1 });
+
+        // 置き換え前の実行ファイルも試用中の状態もそのまま残る
+        assert_eq!(fixture.read_exe("exe.old").unwrap(), b"current");
+        let saved = StateStore::new(&fixture.state_path).load().unwrap();
+        assert_eq!(saved.update, pending.update);
+    }
+
+    #[tokio::test]
+    async fn test_successful_first_send_confirms_update() {
+        let fixture = Fixture::new("update_confirm");
+        let installer = fixture.installer();
+        installer.install(b"updated").unwrap();
+
+        let mut scheduler = fixture
+            .scheduler_with(test_config(), fixture.save_pending_update())
+            .with_self_update(Some(installer));
+
+        assert_eq!(scheduler.step().await, SchedulerState::Idle);
+        assert_eq!(fixture.read_exe("exe").unwrap(), b"updated");
+        assert!(fixture.read_exe("exe.old").is_none());
+
+        let saved = StateStore::new(&fixture.state_path).load().unwrap();
+        assert_eq!(saved.update, UpdateState::default());
+    }
+
+    #[tokio::test]
+    async fn test_applies_config_from_server() {
+        let fixture = Fixture::new("server_config");
+        let config = ConfigOverrides {
+            send_interval_secs: Some(7200),
+            log_level: Some("debug".to_string()),
+            ..Default::default()
+        };
+        fixture.transport.set_config(Some(config.clone()));
+
+        let levels = Arc::new(Mutex::new(Vec::new()));
+        let recorded = levels.clone();
+        let mut scheduler = fixture
+            .scheduler()
+            .with_log_reload(move |level: &str| recorded.lock().unwrap().push(level.to_string()));
+
+        assert_eq!(scheduler.step().await, SchedulerState::Idle);
+        assert_eq!(*levels.lock().unwrap(), vec!["info", "debug"]);
+        let saved = StateStore::new(&fixture.state_path).load().unwrap();
+        assert_eq!(saved.server_config, Some(config));
+
+        // 配布された送信間隔（2時間）で送信
+        assert_eq!(scheduler.step().await, SchedulerState::Idle);
+        assert_eq!(scheduler.step().await, SchedulerState::Sending);
+        assert_eq!(fixture.clock.elapsed_secs(), 7200);
+
+        // 再起動後もサーバーに接続する前から適用
+        let restarted = fixture.scheduler_with(test_config(), saved);
+        assert_eq!(restarted.config.client.send_interval_secs, 7200);
+        assert_eq!(restarted.config.pc_info.user_name, "testuser");
+
+        // 配布が無くなった場合はローカルの設定に戻す（ハートビートのレスポンス）
+        fixture.transport.set_config(None);
+        assert_eq!(scheduler.step().await, SchedulerState::Idle);
+        assert_eq!(fixture.transport.heartbeats().len(), 1);
+        assert_eq!(levels.lock().unwrap().last().unwrap(), "info");
+        assert_eq!(scheduler.config.client.send_interval_secs, 21600);
+        let saved = StateStore::new(&fixture.state_path).load().unwrap();
+        assert_eq!(saved.server_config, None);
+    }
+
+    #[tokio::test]
+    async fn test_preview_does_not_send_or_save() {
+        let fixture = Fixture::new("preview");
+        let mut scheduler = fixture.scheduler();
+
+        let payload = scheduler.preview().await.unwrap();
+        assert_eq!(payload.pc_info.uuid, "test-uuid");
+        assert_eq!(payload.pc_info.ip_address, "192.168.1.100");
+        assert!(payload.pc_info.agent.is_some());
+        assert!(payload.software.is_none());
+        assert_eq!(payload.updates.uuid, "test-uuid");
+
+        assert!(fixture.transport.sent_pc_info().is_empty());
+        assert!(!fixture.state_path.exists());
+
+        // プレビュー後も起動直後の送信は通常どおり行う
+        assert_eq!(scheduler.step().await, SchedulerState::Idle);
+        assert_eq!(fixture.transport.sent_pc_info().len(), 1);
+    }
+}
@@ -0,0 +1,574 @@
+//! Windowsサービスとしての実行
+//!
+//! クライアントをサービス制御マネージャー（SCM）に直接登録し、停止・シャットダウンの要求で終了します。
+//! SCM との通信は StatusReporter / ServiceManager の実装（Windows のみ）に閉じ込め、
+//! 制御要求の扱いと状態の遷移はこのモジュールで行うため、Windows 以外でもテストできます。
+//!
+//! 自動更新による再起動（update::RESTART_EXIT_CODE）はサービス固有の終了コードとして報告し、
+//! 登録時に設定した回復操作（失敗時に再起動）で新しいバージョンを起動します。
+
+use crate::error::{ClientError, Result};
+use crate::update;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// サービス名（NSSM で登録していた場合と同じ名前）
+pub const SERVICE_NAME: &str = "PCInventoryClient";
+
+/// サービスの表示名
+pub const DISPLAY_NAME: &str = "PC Inventory Client";
+
+/// サービスの説明
+pub const DESCRIPTION: &str = "PC情報を収集してサーバーに送信します";
+
+/// SCM から起動されたことを示すサブコマンド（登録時の起動引数）
+pub const RUN_SERVICE_COMMAND: &str = "run-service";
+
+/// 異常終了・再起動要求の後、再起動するまでの待機時間
+pub const RESTART_DELAY: Duration = Duration::from_secs(10);
+
+/// SCM に報告するサービスの状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceState {
+    StartPending,
+    Running,
+    StopPending,
+    /// 停止（0 以外の終了コードは失敗として扱われ、回復操作の対象になる）
+    Stopped { exit_code: u32 },
+}
+
+/// SCM からの制御要求
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlEvent {
+    Stop,
+    Shutdown,
+    Interrogate,
+    /// 受け付けない要求（一時停止など）
+    Other,
+}
+
+/// 制御要求への応答
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlResponse {
+    Handled,
+    NotImplemented,
+}
+
+/// クライアントの終了理由
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    /// 停止要求による終了
+    Stopped,
+    /// 実行ファイルの置き換えによる再起動要求
+    Restart,
+    /// 起動時のエラー（設定ファイルの不備など）
+    Failed,
+}
+
+impl Exit {
+    /// プロセス（またはサービス）の終了コード
+    pub fn code(self) -> i32 {
+        match self {
+            Exit::Stopped => 0,
+            Exit::Restart => update::RESTART_EXIT_CODE,
+            Exit::Failed => 1,
+        }
+    }
+}
+
+/// SCM への状態の報告
+pub trait StatusReporter {
+    /// サービスの状態を報告
+    ///
+    /// # エラー
+    /// SCM への報告に失敗した場合
+    fn report(&self, state: ServiceState) -> Result<()>;
+}
+
+/// サービスの登録内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceDefinition {
+    pub name: String,
+    pub display_name: String,
+    pub description: String,
+    /// 実行ファイルのパス
+    pub executable: PathBuf,
+    /// SCM から起動する際の引数
+    pub arguments: Vec<String>,
+    /// 異常終了・再起動要求の後、再起動するまでの待機時間
+    pub restart_delay: Duration,
+}
+
+impl ServiceDefinition {
+    /// 実行ファイルを自動起動のサービスとして登録する内容を作成
+    ///
+    /// # 引数
+    /// * `executable` - 実行ファイルのパス（通常は現在の実行ファイル）
+    pub fn for_executable(executable: impl Into<PathBuf>) -> Self {
+        Self {
+            name: SERVICE_NAME.to_string(),
+            display_name: DISPLAY_NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            executable: executable.into(),
+            arguments: vec![RUN_SERVICE_COMMAND.to_string()],
+            restart_delay: RESTART_DELAY,
+        }
+    }
+}
+
+/// サービスの登録・削除
+pub trait ServiceManager {
+    /// サービスを登録して開始
+    ///
+    /// # エラー
+    /// 登録に失敗した場合（同名のサービスが登録済み、管理者権限が無いなど）
+    fn install(&self, definition: &ServiceDefinition) -> Result<()>;
+
+    /// サービスを停止して削除
+    ///
+    /// # エラー
+    /// 削除に失敗した場合（サービスが登録されていない場合を含む）
+    fn uninstall(&self, name: &str) -> Result<()>;
+}
+
+/// 停止要求の通知
+///
+/// 制御ハンドラー（SCM のスレッド）や Ctrl+C の待機から、クライアントのメインループに停止を伝えます。
+/// 待機を始める前に要求された場合も、要求済みとして扱います。
+#[derive(Debug, Clone)]
+pub struct StopSignal {
+    sender: Arc<watch::Sender<bool>>,
+}
+
+impl StopSignal {
+    pub fn new() -> Self {
+        let (sender, _) = watch::channel(false);
+        Self {
+            sender: Arc::new(sender),
+        }
+    }
+
+    /// 停止を要求
+    pub fn request(&self) {
+        self.sender.send_replace(true);
+    }
+
+    /// 停止が要求されているか
+    pub fn is_requested(&self) -> bool {
+        *self.sender.borrow()
+    }
+
+    /// 停止が要求されるまで待機
+    pub async fn wait(&self) {
+        let mut receiver = self.sender.subscribe();
+        // sender は self が保持しているため、チャネルが閉じることはない
+        let _ = receiver.wait_for(|requested| *requested).await;
+    }
+}
+
+impl Default for StopSignal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// SCM からの制御要求を処理
+///
+/// 停止・シャットダウンの要求は StopSignal に伝え、メインループの終了を待たずに戻ります。
+///
+/// # 戻り値
+/// SCM に返す応答
+pub fn handle_control(event: ControlEvent, stop: &StopSignal) -> ControlResponse {
+    match event {
+        ControlEvent::Stop | ControlEvent::Shutdown => {
+            tracing::info!("Service {:?} requested", event);
+            stop.request();
+            ControlResponse::Handled
+        }
+        ControlEvent::Interrogate => ControlResponse::Handled,
+        ControlEvent::Other => ControlResponse::NotImplemented,
+    }
+}
+
+/// 停止が要求されるまでクライアントのメインループを実行
+///
+/// 停止要求を受けた時点でメインループ（送信中のリクエストを含む）を中断します。
+/// 状態ファイルへの書き込みは一時ファイルからの置き換えのため、中断しても壊れません。
+///
+/// # 引数
+/// * `stop` - 停止要求の通知
+/// * `main` - メインループ（再起動が必要になった場合に終了する）
+///
+/// # 戻り値
+/// 終了理由（Exit::Stopped または Exit::Restart）
+pub async fn run_until_stopped(stop: &StopSignal, main: impl Future<Output = ()>) -> Exit {
+    tokio::select! {
+        _ = main => Exit::Restart,
+        _ = stop.wait() => {
+            tracing::info!("Stop requested, shutting down");
+            Exit::Stopped
+        }
+    }
+}
+
+/// サービスとしてクライアントを実行し、状態を SCM に報告
+///
+/// # 引数
+/// * `reporter` - SCM への状態の報告
+/// * `stop` - 制御ハンドラーと共有する停止要求の通知
+/// * `main` - クライアントの本体（停止要求または再起動要求で終了する）
+///
+/// # エラー
+/// SCM への状態の報告に失敗した場合
+pub fn run_service<R: StatusReporter>(
+    reporter: &R,
+    stop: StopSignal,
+    main: impl FnOnce(StopSignal) -> Exit,
+) -> Result<()> {
+    reporter.report(ServiceState::StartPending)?;
+    reporter.report(ServiceState::Running)?;
+
+    let exit = main(stop);
+    tracing::info!("Service exiting: {:?} (exit code: {})", exit, exit.code());
+
+    reporter.report(ServiceState::StopPending)?;
+    reporter.report(ServiceState::Stopped {
+        exit_code: exit.code() as u32,
+    })
+}
+
+/// OS のサービスマネージャーに接続
+///
+/// # エラー
+/// 接続に失敗した場合、または Windows 以外の場合
+pub fn system_manager() -> Result<Box<dyn ServiceManager>> {
+    platform::manager()
+}
+
+/// SCM のサービスディスパッチャーに制御を渡し、サービスとして実行
+///
+/// SCM から起動された場合（run-service）にのみ使用します。サービスが停止するまで戻りません。
+///
+/// # 引数
+/// * `main` - クライアントの本体（SCM のスレッドで実行される）
+///
+/// # エラー
+/// ディスパッチャーの開始に失敗した場合（コンソールから直接実行した場合を含む）、または Windows 以外の場合
+pub fn run_dispatcher(main: impl FnOnce(StopSignal) -> Exit + Send + 'static) -> Result<()> {
+    platform::run_dispatcher(Box::new(main))
+}
+
+type ServiceMain = Box<dyn FnOnce(StopSignal) -> Exit + Send>;
+
+#[cfg(windows)]
+mod platform {
+    use super::{
+        handle_control, run_service, ClientError, ControlEvent, ControlResponse, Result, ServiceDefinition,
+        ServiceMain, ServiceManager, ServiceState, StatusReporter, StopSignal, SERVICE_NAME,
+    };
+    use std::ffi::{OsStr, OsString};
+    use std::sync::Mutex;
+    use std::time::{Duration, Instant};
+    use windows_service::service::{
+        ServiceAccess, ServiceAction, ServiceActionType, ServiceControl, ServiceControlAccept, ServiceErrorControl,
+        ServiceExitCode, ServiceFailureActions, ServiceFailureResetPeriod, ServiceInfo, ServiceStartType,
+        ServiceState as ScmState, ServiceStatus, ServiceType,
+    };
+    use windows_service::service_control_handler::{self, ServiceControlHandlerResult, ServiceStatusHandle};
+    use windows_service::service_manager::{self, ServiceManagerAccess};
+    use windows_service::{define_windows_service, service_dispatcher};
+    use windows_sys::Win32::Foundation::ERROR_SERVICE_DOES_NOT_EXIST;
+
+    /// 失敗回数をリセットするまでの期間
+    const FAILURE_RESET_PERIOD: Duration = Duration::from_secs(24 * 60 * 60);
+
+    /// 停止中・開始中の状態の報告に含める、次の報告までの目安
+    const PENDING_WAIT_HINT: Duration = Duration::from_secs(30);
+
+    /// 削除後、サービスが SCM から消えるまで待つ時間
+    const DELETE_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// SCM のスレッドで実行するクライアントの本体（run_dispatcher で設定）
+    static SERVICE_MAIN: Mutex<Option<ServiceMain>> = Mutex::new(None);
+
+    fn service_error(context: &str, e: windows_service::Error) -> ClientError {
+        ClientError::ServiceError(format!("{}: {}", context, e))
+    }
+
+    pub(super) fn manager() -> Result<Box<dyn ServiceManager>> {
+        let manager = service_manager::ServiceManager::local_computer(
+            None::<&str>,
+            ServiceManagerAccess::CONNECT | ServiceManagerAccess::CREATE_SERVICE,
+        )
+        .map_err(|e| service_error("Failed to connect to the service control manager (run as administrator)", e))?;
+        Ok(Box::new(ScmManager { manager }))
+    }
+
+    struct ScmManager {
+        manager: service_manager::ServiceManager,
+    }
+
+    impl ServiceManager for ScmManager {
+        fn install(&self, definition: &ServiceDefinition) -> Result<()> {
+            let info = ServiceInfo {
+                name: OsString::from(&definition.name),
+                display_name: OsString::from(&definition.display_name),
+                service_type: ServiceType::OWN_PROCESS,
+                start_type: ServiceStartType::AutoStart,
+                error_control: ServiceErrorControl::Normal,
+                executable_path: definition.executable.clone(),
+                launch_arguments: definition.arguments.iter().map(OsString::from).collect(),
+                dependencies: vec![],
+                account_name: None, // LocalSystem
+                account_password: None,
+            };
+            let service = self
+                .manager
+                .create_service(&info, ServiceAccess::CHANGE_CONFIG | ServiceAccess::START)
+                .map_err(|e| service_error("Failed to create service", e))?;
+
+            service
+                .set_description(&definition.description)
+                .map_err(|e| service_error("Failed to set service description", e))?;
+
+            // 異常終了・再起動要求（サービス固有の終了コード）のいずれも再起動する
+            let restart = ServiceAction {
+                action_type: ServiceActionType::Restart,
+                delay: definition.restart_delay,
+            };
+            service
+                .update_failure_actions(ServiceFailureActions {
+                    reset_period: ServiceFailureResetPeriod::After(FAILURE_RESET_PERIOD),
+                    reboot_msg: None,
+                    command: None,
+                    actions: Some(vec![restart.clone(), restart.clone(), restart]),
+                })
+                .map_err(|e| service_error("Failed to set service recovery actions", e))?;
+            service
+                .set_failure_actions_on_non_crash_failures(true)
+                .map_err(|e| service_error("Failed to enable recovery actions on non-crash failures", e))?;
+
+            service
+                .start(&[] as &[&OsStr])
+                .map_err(|e| service_error("Service installed but failed to start", e))
+        }
+
+        fn uninstall(&self, name: &str) -> Result<()> {
+            let service = self
+                .manager
+                .open_service(name, ServiceAccess::QUERY_STATUS | ServiceAccess::STOP | ServiceAccess::DELETE)
+                .map_err(|e| service_error("Failed to open service", e))?;
+
+            service.delete().map_err(|e| service_error("Failed to delete service", e))?;
+            let status = service
+                .query_status()
+                .map_err(|e| service_error("Failed to query service status", e))?;
+            if status.current_state != ScmState::Stopped {
+                service.stop().map_err(|e| service_error("Failed to stop service", e))?;
+            }
+            drop(service);
+
+            // 停止が完了するまでは削除予定の状態で残る
+            let started = Instant::now();
+            while started.elapsed() < DELETE_TIMEOUT {
+                if let Err(windows_service::Error::Winapi(e)) =
+                    self.manager.open_service(name, ServiceAccess::QUERY_STATUS)
+                {
+                    if e.raw_os_error() == Some(ERROR_SERVICE_DOES_NOT_EXIST as i32) {
+                        return Ok(());
+                    }
+                }
+                std::thread::sleep(Duration::from_secs(1));
+            }
+            tracing::warn!("Service {} is marked for deletion and will be removed once it stops", name);
+            Ok(())
+        }
+    }
+
+    /// ServiceStatusHandle による状態の報告
+    struct ScmReporter {
+        handle: ServiceStatusHandle,
+    }
+
+    impl StatusReporter for ScmReporter {
+        fn report(&self, state: ServiceState) -> Result<()> {
+            let (current_state, exit_code, wait_hint) = match state {
+                ServiceState::StartPending => (ScmState::StartPending, ServiceExitCode::NO_ERROR, PENDING_WAIT_HINT),
+                ServiceState::Running => (ScmState::Running, ServiceExitCode::NO_ERROR, Duration::default()),
+                ServiceState::StopPending => (ScmState::StopPending, ServiceExitCode::NO_ERROR, PENDING_WAIT_HINT),
+                ServiceState::Stopped { exit_code: 0 } => {
+                    (ScmState::Stopped, ServiceExitCode::NO_ERROR, Duration::default())
+                }
+                ServiceState::Stopped { exit_code } => {
+                    (ScmState::Stopped, ServiceExitCode::ServiceSpecific(exit_code), Duration::default())
+                }
+            };
+            let controls_accepted = if state == ServiceState::Running {
+                ServiceControlAccept::STOP | ServiceControlAccept::SHUTDOWN
+            } else {
+                ServiceControlAccept::empty()
+            };
+
+            self.handle
+                .set_service_status(ServiceStatus {
+                    service_type: ServiceType::OWN_PROCESS,
+                    current_state,
+                    controls_accepted,
+                    exit_code,
+                    checkpoint: 0,
+                    wait_hint,
+                    process_id: None,
+                })
+                .map_err(|e| service_error("Failed to report service status", e))
+        }
+    }
+
+    pub(super) fn run_dispatcher(main: ServiceMain) -> Result<()> {
+        *SERVICE_MAIN.lock().unwrap_or_else(|e| e.into_inner()) = Some(main);
+        service_dispatcher::start(SERVICE_NAME, ffi_service_main)
+            .map_err(|e| service_error("Failed to start service dispatcher (not started by the SCM?)", e))
+    }
+
+    define_windows_service!(ffi_service_main, service_main);
+
+    fn service_main(_arguments: Vec<OsString>) {
+        let main = SERVICE_MAIN.lock().unwrap_or_else(|e| e.into_inner()).take();
+        let Some(main) = main else {
+            tracing::error!("Service started without a service main");
+            return;
+        };
+        if let Err(e) = serve(main) {
+            tracing::error!("Service failed: {}", e);
+        }
+    }
+
+    fn serve(main: ServiceMain) -> Result<()> {
+        let stop = StopSignal::new();
+        let handler_stop = stop.clone();
+        let handle = service_control_handler::register(SERVICE_NAME, move |control| {
+            let event = match control {
+                ServiceControl::Stop => ControlEvent::Stop,
+                ServiceControl::Shutdown => ControlEvent::Shutdown,
+                ServiceControl::Interrogate => ControlEvent::Interrogate,
+                _ => ControlEvent::Other,
+            };
+            match handle_control(event, &handler_stop) {
+                ControlResponse::Handled => ServiceControlHandlerResult::NoError,
+                ControlResponse::NotImplemented => ServiceControlHandlerResult::NotImplemented,
+            }
+        })
+        .map_err(|e| service_error("Failed to register service control handler", e))?;
+
+        run_service(&ScmReporter { handle }, stop, main)
+    }
+}
+
+#[cfg(not(windows))]
+mod platform {
+    use super::{ClientError, Result, ServiceMain, ServiceManager};
+
+    pub(super) fn manager() -> Result<Box<dyn ServiceManager>> {
+        Err(ClientError::ServiceError("Windows service mode is only available on Windows".to_string()))
+    }
+
+    pub(super) fn run_dispatcher(_main: ServiceMain) -> Result<()> {
+        Err(ClientError::ServiceError("Windows service mode is only available on Windows".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// 報告された状態を記録する StatusReporter
+    #[derive(Default)]
+    struct RecordingReporter {
+        states: RefCell<Vec<ServiceState>>,
+    }
+
+    impl StatusReporter for RecordingReporter {
+        fn report(&self, state: ServiceState) -> Result<()> {
+            self.states.borrow_mut().push(state);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_handle_control() {
+        let stop = StopSignal::new();
+
+        assert_eq!(handle_control(ControlEvent::Interrogate, &stop), ControlResponse::Handled);
+        assert_eq!(handle_control(ControlEvent::Other, &stop), ControlResponse::NotImplemented);
+        assert!(!stop.is_requested());
+
+        assert_eq!(handle_control(ControlEvent::Shutdown, &stop), ControlResponse::Handled);
+        assert!(stop.is_requested());
+    }
+
+    #[tokio::test]
+    async fn test_run_until_stopped() {
+        // 待機前に要求された停止も反映される
+        let stop = StopSignal::new();
+        handle_control(ControlEvent::Stop, &stop);
+        assert_eq!(run_until_stopped(&stop, std::future::pending()).await, Exit::Stopped);
+
+        // 制御ハンドラーは別スレッドから呼ばれる
+        let stop = StopSignal::new();
+        let handler_stop = stop.clone();
+        std::thread::spawn(move || handle_control(ControlEvent::Stop, &handler_stop));
+        assert_eq!(run_until_stopped(&stop, std::future::pending()).await, Exit::Stopped);
+
+        // メインループの終了は再起動要求
+        assert_eq!(run_until_stopped(&StopSignal::new(), async {}).await, Exit::Restart);
+    }
+
+    #[test]
+    fn test_run_service_reports_states() {
+        let reporter = RecordingReporter::default();
+        let stop = StopSignal::new();
+        stop.request();
+
+        run_service(&reporter, stop, |stop| {
+            assert!(stop.is_requested());
+            Exit::Stopped
+        })
+        .unwrap();
+
+        assert_eq!(
+            reporter.states.into_inner(),
+            vec![
+                ServiceState::StartPending,
+                ServiceState::Running,
+                ServiceState::StopPending,
+                ServiceState::Stopped { exit_code: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_service_restart_reports_failure() {
+        // 再起動要求は回復操作で再起動させるため、0 以外の終了コードで報告する
+        let reporter = RecordingReporter::default();
+        run_service(&reporter, StopSignal::new(), |_| Exit::Restart).unwrap();
+
+        assert_eq!(
+            reporter.states.borrow().last(),
+            Some(&ServiceState::Stopped {
+                exit_code: update::RESTART_EXIT_CODE as u32
+            })
+        );
+    }
+
+    #[test]
+    fn test_definition_for_executable() {
+        let definition = ServiceDefinition::for_executable(r"C:\PCInventory\pc-inventory-client.exe");
+
+        assert_eq!(definition.name, "PCInventoryClient");
+        assert_eq!(definition.arguments, vec!["run-service".to_string()]);
+        assert_eq!(definition.restart_delay, Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,268 @@
+use crate::error::{ClientError, Result};
+
+/// インストール済みソフトウェア収集構造体
+///
+/// Windows ではレジストリの Uninstall キー、
+/// Linux では dpkg / rpm のパッケージデータベースから
+/// インストール済みアプリケーションの一覧を取得します。
+pub struct SoftwareCollector;
+
+/// インストール済みソフトウェア情報（送信形式と同じ）
+pub type SoftwareInfo = pc_inventory_protocol::SoftwareItem;
+
+impl SoftwareCollector {
+    /// インストール済みソフトウェアの一覧を取得
+    ///
+    /// 取得結果は名前・バージョン順にソートされ、重複は除去されます。
+    ///
+    /// # 戻り値
+    /// SoftwareInfo のベクター
+    ///
+    /// # エラー
+    /// パッケージ情報の取得に失敗した場合
+    pub fn collect() -> Result<Vec<SoftwareInfo>> {
+        tracing::debug!("Collecting installed software");
+
+        let mut list = Self::collect_platform()?;
+        list.sort();
+        list.dedup();
+
+        tracing::info!("Installed software collected: {} entries", list.len());
+        Ok(list)
+    }
+
+    /// Uninstall レジストリキーからソフトウェア一覧を取得（Windows）
+    ///
+    /// 64bit / 32bit (WOW6432Node) のマシン全体のキーと、
+    /// 現在のユーザーのキーを走査します。
+    /// DisplayName を持たないエントリ、SystemComponent = 1 のエントリ、
+    /// 更新プログラム（ParentKeyName を持つエントリ）は除外します。
+    #[cfg(windows)]
+    fn collect_platform() -> Result<Vec<SoftwareInfo>> {
+        use winreg::enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE, KEY_READ};
+        use winreg::RegKey;
+
+        let uninstall_keys = [
+            (HKEY_LOCAL_MACHINE, r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall"),
+            (HKEY_LOCAL_MACHINE, r"SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall"),
+            (HKEY_CURRENT_USER, r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall"),
+        ];
+
+        let mut list = Vec::new();
+
+        for (hive, path) in uninstall_keys {
+            let uninstall = match RegKey::predef(hive).open_subkey_with_flags(path, KEY_READ) {
+                Ok(key) => key,
+                Err(e) => {
+                    tracing::debug!("Skipping registry key {}: {}", path, e);
+                    continue;
+                }
+            };
+
+            for sub_name in uninstall.enum_keys().filter_map(|k| k.ok()) {
+                let entry = match uninstall.open_subkey_with_flags(&sub_name, KEY_READ) {
+                    Ok(key) => key,
+                    Err(_) => continue,
+                };
+
+                let name: String = match entry.get_value("DisplayName") {
+                    Ok(name) => name,
+                    Err(_) => continue,
+                };
+
+                if entry.get_value::<u32, _>("SystemComponent").unwrap_or(0) == 1 {
+                    continue;
+                }
+                if entry.get_value::<String, _>("ParentKeyName").is_ok() {
+                    continue;
+                }
+
+                let version: String = entry.get_value("DisplayVersion").unwrap_or_default();
+                let publisher: Option<String> = entry.get_value("Publisher").ok();
+                let install_date = entry
+                    .get_value::<String, _>("InstallDate")
+                    .ok()
+                    .and_then(|d| Self::normalize_yyyymmdd(&d));
+
+                if let Some(info) = Self::build_info(&name, &version, publisher.as_deref(), install_date) {
+                    list.push(info);
+                }
+            }
+        }
+
+        Ok(list)
+    }
+
+    /// dpkg / rpm のパッケージデータベースからソフトウェア一覧を取得（Linux）
+    ///
+    /// dpkg-query と rpm のうち利用可能なものをすべて使用します。
+    /// どちらも利用できない場合はエラーを返します。
+    #[cfg(not(windows))]
+    fn collect_platform() -> Result<Vec<SoftwareInfo>> {
+        use std::process::Command;
+
+        let mut list = Vec::new();
+        let mut found_manager = false;
+
+        match Command::new("dpkg-query")
+            .args(["-W", "-f=${Package}\\t${Version}\\t${Maintainer}\\t${db:Status-Abbrev}\\n"])
+            .output()
+        {
+            Ok(output) if output.status.success() => {
+                found_manager = true;
+                list.extend(Self::parse_dpkg_output(&String::from_utf8_lossy(&output.stdout)));
+            }
+            Ok(output) => tracing::debug!("dpkg-query exited with status: {}", output.status),
+            Err(e) => tracing::debug!("dpkg-query not available: {}", e),
+        }
+
+        match Command::new("rpm")
+            .args(["-qa", "--queryformat", "%{NAME}\\t%{VERSION}-%{RELEASE}\\t%{VENDOR}\\t%{INSTALLTIME}\\n"])
+            .output()
+        {
+            Ok(output) if output.status.success() => {
+                found_manager = true;
+                list.extend(Self::parse_rpm_output(&String::from_utf8_lossy(&output.stdout)));
+            }
+            Ok(output) => tracing::debug!("rpm exited with status: {}", output.status),
+            Err(e) => tracing::debug!("rpm not available: {}", e),
+        }
+
+        if !found_manager {
+            return Err(ClientError::SoftwareError(
+                "No supported package manager found (dpkg-query or rpm)".to_string(),
+            ));
+        }
+
+        Ok(list)
+    }
+
+    /// dpkg-query の出力を解析
+    ///
+    /// 1行1パッケージ、タブ区切りで「名前、バージョン、メンテナ、状態」の形式を想定します。
+    /// 状態が "ii"（インストール済み）以外の行は除外します。
+    #[cfg_attr(windows, allow(dead_code))]
+    fn parse_dpkg_output(output: &str) -> Vec<SoftwareInfo> {
+        output
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split('\t').collect();
+                if fields.len() < 4 || !fields[3].starts_with("ii") {
+                    return None;
+                }
+                let publisher = Some(fields[2]).filter(|p| !p.trim().is_empty());
+                Self::build_info(fields[0], fields[1], publisher, None)
+            })
+            .collect()
+    }
+
+    /// rpm -qa の出力を解析
+    ///
+    /// 1行1パッケージ、タブ区切りで「名前、バージョン-リリース、ベンダー、インストール日時（UNIX時刻）」の形式を想定します。
+    #[cfg_attr(windows, allow(dead_code))]
+    fn parse_rpm_output(output: &str) -> Vec<SoftwareInfo> {
+        output
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split('\t').collect();
+                if fields.len() < 4 {
+                    return None;
+                }
+                let publisher = Some(fields[2]).filter(|p| !p.trim().is_empty() && *p != "(none)");
+                let install_date = fields[3]
+                    .trim()
+                    .parse::<i64>()
+                    .ok()
+                    .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+                    .map(|dt| dt.format("%Y-%m-%d").to_string());
+                Self::build_info(fields[0], fields[1], publisher, install_date)
+            })
+            .collect()
+    }
+
+    /// "YYYYMMDD" 形式の日付を "YYYY-MM-DD" 形式に変換
+    ///
+    /// 不正な日付の場合は None を返します。
+    #[cfg_attr(not(windows), allow(dead_code))]
+    fn normalize_yyyymmdd(date: &str) -> Option<String> {
+        let date = date.trim();
+        if date.len() != 8 {
+            return None;
+        }
+        chrono::NaiveDate::parse_from_str(date, "%Y%m%d")
+            .ok()
+            .map(|d| d.format("%Y-%m-%d").to_string())
+    }
+
+    /// 各フィールドを整形して SoftwareInfo を生成
+    ///
+    /// 名前が空の場合は None を返します。
+    fn build_info(name: &str, version: &str, publisher: Option<&str>, install_date: Option<String>) -> Option<SoftwareInfo> {
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+
+        Some(SoftwareInfo {
+            name: name.to_string(),
+            version: version.trim().to_string(),
+            publisher: publisher.map(|p| p.trim().to_string()),
+            install_date,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dpkg_output() {
+        let output = "bash\t5.1-6ubuntu1\tUbuntu Developers <ubuntu-devel@lists.ubuntu.com>\tii \n\
+                      removed-pkg\t1.0\tSomeone\trc \n\
+                      curl\t7.81.0-1\t\tii \n";
+
+        let list = SoftwareCollector::parse_dpkg_output(output);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].name, "bash");
+        assert_eq!(list[0].version, "5.1-6ubuntu1");
+        assert_eq!(list[0].publisher.as_deref(), Some("Ubuntu Developers <ubuntu-devel@lists.ubuntu.com>"));
+        assert_eq!(list[1].name, "curl");
+        assert_eq!(list[1].publisher, None);
+    }
+
+    #[test]
+    fn test_parse_rpm_output() {
+        let output = "bash\t5.1.8-6.el9\tRed Hat, Inc.\t1700000000\n\
+                      gpg-pubkey\t3228467c-613798eb\t(none)\t1700000000\n\
+                      broken-line\n";
+
+        let list = SoftwareCollector::parse_rpm_output(output);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].name, "bash");
+        assert_eq!(list[0].version, "5.1.8-6.el9");
+        assert_eq!(list[0].publisher.as_deref(), Some("Red Hat, Inc."));
+        assert_eq!(list[0].install_date.as_deref(), Some("2023-11-14"));
+        assert_eq!(list[1].publisher, None);
+    }
+
+    #[test]
+    fn test_normalize_yyyymmdd() {
+        assert_eq!(SoftwareCollector::normalize_yyyymmdd("20240131"), Some("2024-01-31".to_string()));
+        assert_eq!(SoftwareCollector::normalize_yyyymmdd("2024013"), None);
+        assert_eq!(SoftwareCollector::normalize_yyyymmdd("20241301"), None);
+    }
+
+    #[test]
+    #[ignore] // 実際のパッケージ環境でのみ動作
+    fn test_collect() {
+        let result = SoftwareCollector::collect();
+        assert!(result.is_ok());
+
+        let list = result.unwrap();
+        println!("Installed software ({}):", list.len());
+        for info in list.iter().take(20) {
+            println!("  {} {}", info.name, info.version);
+        }
+    }
+}
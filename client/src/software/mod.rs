@@ -0,0 +1,5 @@
+pub mod collector;
+pub mod snapshot;
+
+pub use collector::{SoftwareCollector, SoftwareInfo};
+pub use snapshot::SoftwareSnapshot;
@@ -0,0 +1,159 @@
+use crate::error::{ClientError, Result};
+use crate::software::SoftwareInfo;
+use crate::storage::write_atomic;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// 最後にサーバーへ送信したソフトウェア一覧
+///
+/// 次回送信時に差分（追加・削除）を計算するため、JSONファイルとして保存します。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SoftwareSnapshot {
+    pub software: Vec<SoftwareInfo>,
+}
+
+/// 前回送信分との差分
+///
+/// ソフトウェアはサーバーのテーブル（pc_software）と同じく名前とバージョンで識別します。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SoftwareDiff {
+    /// 新たに追加されたソフトウェア（発行元・インストール日が変わったものを含む）
+    pub added: Vec<SoftwareInfo>,
+
+    /// 削除されたソフトウェア
+    pub removed: Vec<SoftwareInfo>,
+}
+
+impl SoftwareSnapshot {
+    /// スナップショットファイルを読み込み
+    ///
+    /// # 戻り値
+    /// * `Ok(Some(SoftwareSnapshot))` - ファイルが存在する場合
+    /// * `Ok(None)` - ファイルが存在しない場合（初回送信）
+    ///
+    /// # エラー
+    /// ファイルの読み込みまたはパースに失敗した場合
+    pub fn load(path: &str) -> Result<Option<Self>> {
+        if !Path::new(path).exists() {
+            tracing::debug!("Software snapshot not found: {}", path);
+            return Ok(None);
+        }
+
+        let contents = fs::read_to_string(path)?;
+        let snapshot = serde_json::from_str(&contents)
+            .map_err(|e| ClientError::SoftwareError(format!("Failed to parse software snapshot '{}': {}", path, e)))?;
+
+        Ok(Some(snapshot))
+    }
+
+    /// スナップショットファイルを保存（一時ファイルに書き込んでから置き換え）
+    pub fn save(&self, path: &str) -> Result<()> {
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| ClientError::SoftwareError(format!("Failed to serialize software snapshot: {}", e)))?;
+        write_atomic(Path::new(path), contents.as_bytes())
+    }
+
+    /// スナップショットファイルを削除（次回は完全な一覧を送信）
+    ///
+    /// ファイルが存在しない場合は何もしません。
+    pub fn remove(path: &str) -> Result<()> {
+        match fs::remove_file(path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 現在のソフトウェア一覧との差分を計算
+    ///
+    /// # 引数
+    /// * `current` - 現在のソフトウェア一覧
+    ///
+    /// # 戻り値
+    /// 追加・削除されたソフトウェアの一覧（どちらも名前・バージョン順）。
+    /// 名前とバージョンが同じで発行元・インストール日だけが変わった場合は、削除せずに追加（更新）のみとします。
+    pub fn diff(&self, current: &[SoftwareInfo]) -> SoftwareDiff {
+        let previous = Self::by_key(&self.software);
+        let current = Self::by_key(current);
+
+        SoftwareDiff {
+            added: current
+                .iter()
+                .filter(|(key, software)| previous.get(key) != Some(software))
+                .map(|(_, software)| (*software).clone())
+                .collect(),
+            removed: previous
+                .iter()
+                .filter(|(key, _)| !current.contains_key(key))
+                .map(|(_, software)| (*software).clone())
+                .collect(),
+        }
+    }
+
+    /// 名前・バージョンをキーとしたソフトウェア一覧（pc_software の一意キーと同じ）
+    fn by_key(list: &[SoftwareInfo]) -> BTreeMap<(&str, &str), &SoftwareInfo> {
+        list.iter().map(|s| ((s.name.as_str(), s.version.as_str()), s)).collect()
+    }
+}
+
+impl SoftwareDiff {
+    /// 差分が無いか
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn software(name: &str, version: &str) -> SoftwareInfo {
+        SoftwareInfo {
+            name: name.to_string(),
+            version: version.to_string(),
+            publisher: None,
+            install_date: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_detects_added_and_removed() {
+        let snapshot = SoftwareSnapshot {
+            software: vec![software("Google Chrome", "119.0"), software("7-Zip", "23.01")],
+        };
+        let current = vec![software("7-Zip", "23.01"), software("Google Chrome", "120.0")];
+
+        let diff = snapshot.diff(&current);
+        assert_eq!(diff.added, vec![software("Google Chrome", "120.0")]);
+        assert_eq!(diff.removed, vec![software("Google Chrome", "119.0")]);
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn test_diff_publisher_change_is_not_removed() {
+        let snapshot = SoftwareSnapshot {
+            software: vec![software("7-Zip", "23.01")],
+        };
+        let updated = SoftwareInfo {
+            publisher: Some("Igor Pavlov".to_string()),
+            install_date: Some("2024-01-31".to_string()),
+            ..software("7-Zip", "23.01")
+        };
+
+        let diff = snapshot.diff(std::slice::from_ref(&updated));
+        assert_eq!(diff.added, vec![updated]);
+        assert!(diff.removed.is_empty());
+    }
+
+    #[test]
+    fn test_diff_unchanged() {
+        let snapshot = SoftwareSnapshot {
+            software: vec![software("7-Zip", "23.01")],
+        };
+
+        let diff = snapshot.diff(&[software("7-Zip", "23.01")]);
+        assert!(diff.is_empty());
+    }
+}
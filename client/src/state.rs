@@ -0,0 +1,350 @@
+use crate::error::{ClientError, Result};
+use crate::storage::{write_atomic, FileLock};
+use crate::update::UpdateState;
+use crate::wmi::HardwareInfo;
+use pc_inventory_protocol::ConfigOverrides;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// クライアントの実行時状態
+///
+/// 最終送信日時や自動取得したPC情報など、クライアントが自動的に更新する値を保持します。
+/// 使用者が編集する config.toml とは別のJSONファイルに保存し、config.toml は読み取り専用とします。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClientState {
+    /// 最終送信日時（RFC 3339形式、未送信の場合は空）
+    pub last_send_datetime: String,
+
+    /// 最後に送信に成功したPC情報のハッシュ（変化が無い間はハートビートのみ送信する）
+    pub last_sent_hash: String,
+
+    /// 自動取得したPC情報
+    pub pc_info: DetectedPcInfo,
+
+    /// 自動更新の状態（試用中のバージョン・元に戻したバージョン）
+    pub update: UpdateState,
+
+    /// サーバーから配布された設定（再起動後もサーバーに接続する前から適用する）
+    pub server_config: Option<ConfigOverrides>,
+}
+
+/// 自動取得したPC情報（WMI・ネットワーク情報）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DetectedPcInfo {
+    pub uuid: String,
+    pub mac_address: String,
+    pub network_type: String,
+    pub ip_address: String,
+    /// グローバルIPv6アドレス（デュアルスタック環境のみ）
+    pub ipv6_address: String,
+    pub os: String,
+    pub os_version: String,
+    pub model_name: String,
+    /// ハードウェア情報（取得前の状態ファイルには無い）
+    pub hardware: Option<HardwareInfo>,
+}
+
+/// 旧バージョンの config.toml に保存されていた実行時状態（移行用）
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct LegacyConfig {
+    client: LegacyClientSettings,
+    pc_info: DetectedPcInfo,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct LegacyClientSettings {
+    last_send_datetime: String,
+    last_sent_hash: String,
+}
+
+impl ClientState {
+    /// 状態ファイルを読み込み
+    ///
+    /// # 戻り値
+    /// * `Ok(Some(ClientState))` - ファイルが存在する場合
+    /// * `Ok(None)` - ファイルが存在しない場合（初回起動）
+    ///
+    /// # エラー
+    /// ファイルの読み込みまたはパースに失敗した場合
+    fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            tracing::debug!("Client state not found: {}", path.display());
+            return Ok(None);
+        }
+
+        let contents = fs::read_to_string(path)?;
+        let state = serde_json::from_str(&contents)
+            .map_err(|e| ClientError::StateError(format!("Failed to parse client state '{}': {}", path.display(), e)))?;
+
+        Ok(Some(state))
+    }
+
+    /// 旧形式の config.toml の内容から実行時状態を取り出す
+    ///
+    /// # 戻り値
+    /// * `Ok(Some(ClientState))` - 移行すべき値が含まれている場合
+    /// * `Ok(None)` - 実行時状態が含まれていない場合
+    fn from_legacy_config(contents: &str) -> Result<Option<Self>> {
+        let legacy: LegacyConfig = toml::from_str(contents)?;
+
+        let state = Self {
+            last_send_datetime: legacy.client.last_send_datetime,
+            last_sent_hash: legacy.client.last_sent_hash,
+            pc_info: legacy.pc_info,
+            ..Self::default()
+        };
+
+        if state == Self::default() {
+            Ok(None)
+        } else {
+            Ok(Some(state))
+        }
+    }
+
+    /// 状態ファイルを保存
+    ///
+    /// 一時ファイルに書き込んでから置き換えるため、書き込み中にプロセスが終了しても
+    /// 既存の状態ファイルが壊れることはありません。
+    /// 排他制御は行わないため、StateStore を経由して呼び出してください。
+    fn save(&self, path: &Path) -> Result<()> {
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| ClientError::StateError(format!("Failed to serialize client state: {}", e)))?;
+        write_atomic(path, contents.as_bytes())
+    }
+
+    pub fn update_last_send_datetime(&mut self, datetime: String) {
+        self.last_send_datetime = datetime;
+    }
+
+    pub fn update_last_sent_hash(&mut self, hash: String) {
+        self.last_sent_hash = hash;
+    }
+}
+
+/// 状態ファイルの書き込み窓口
+///
+/// 状態ファイルへの書き込みはすべてこの構造体を経由します。
+/// 書き込みは次の手順で直列化され、同時に更新しても互いの変更を失いません。
+/// 1. プロセス内のミューテックスを取得
+/// 2. アドバイザリロック（"<state_file>.lock"）を取得（別プロセスとの排他）
+/// 3. 最新の状態ファイルを読み込んで変更を適用
+/// 4. 一時ファイルに書き込んでから置き換え
+///
+/// clone したインスタンスは同じミューテックスを共有します。
+#[derive(Debug, Clone)]
+pub struct StateStore {
+    path: PathBuf,
+    writer: Arc<Mutex<()>>,
+}
+
+impl StateStore {
+    /// 状態ファイルのパスを指定して作成
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            writer: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// 状態ファイルのパス
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 現在の状態を読み込み（ファイルが無い場合は初期状態）
+    ///
+    /// # エラー
+    /// ファイルの読み込みまたはパースに失敗した場合
+    pub fn load(&self) -> Result<ClientState> {
+        Ok(ClientState::load(&self.path)?.unwrap_or_default())
+    }
+
+    /// 状態ファイルを読み込み、存在しない場合は config.toml から移行
+    ///
+    /// 旧バージョンでは実行時状態を config.toml に書き込んでいたため、
+    /// 状態ファイルが無く config.toml に値が残っている場合は一度だけ状態ファイルへ移行します。
+    /// config.toml は変更しません（残った値は以降読み込まれません）。
+    ///
+    /// # 引数
+    /// * `config_path` - config.toml のパス
+    ///
+    /// # エラー
+    /// ファイルの読み込み・パース・保存に失敗した場合
+    pub fn load_or_migrate(&self, config_path: &str) -> Result<ClientState> {
+        let _guard = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        let _lock = FileLock::exclusive(&self.path)?;
+
+        if let Some(state) = ClientState::load(&self.path)? {
+            return Ok(state);
+        }
+
+        let contents = fs::read_to_string(config_path)
+            .map_err(|e| ClientError::ConfigError(format!("Failed to read config file '{}': {}", config_path, e)))?;
+
+        match ClientState::from_legacy_config(&contents)? {
+            Some(state) => {
+                tracing::info!("Migrating runtime state from {} to {}", config_path, self.path.display());
+                state.save(&self.path)?;
+                Ok(state)
+            }
+            None => Ok(ClientState::default()),
+        }
+    }
+
+    /// 状態を更新して保存
+    ///
+    /// 最新の状態ファイルを読み込んで `f` で変更し、保存します。
+    /// 読み込みから保存までは排他されるため、他の書き込みによる変更は保持されます。
+    ///
+    /// # 引数
+    /// * `f` - 状態を変更する処理（変更したい項目のみ書き換えること）
+    ///
+    /// # 戻り値
+    /// 保存した状態
+    ///
+    /// # エラー
+    /// ロックの取得、ファイルの読み込み・保存に失敗した場合
+    pub fn update<F>(&self, f: F) -> Result<ClientState>
+    where
+        F: FnOnce(&mut ClientState),
+    {
+        let _guard = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        let _lock = FileLock::exclusive(&self.path)?;
+
+        let mut state = ClientState::load(&self.path)?.unwrap_or_default();
+        f(&mut state);
+        state.save(&self.path)?;
+
+        Ok(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_legacy_config() {
+        let contents = r#"
+            [client]
+            last_send_datetime = "2024-01-31T09:00:00+09:00"
+            check_interval_secs = 3600
+
+            [pc_info]
+            user_name = "testuser"
+            uuid = "test-uuid"
+            ip_address = "192.168.1.100"
+        "#;
+
+        let state = ClientState::from_legacy_config(contents).unwrap().unwrap();
+        assert_eq!(state.last_send_datetime, "2024-01-31T09:00:00+09:00");
+        assert_eq!(state.last_sent_hash, "");
+        assert_eq!(state.pc_info.uuid, "test-uuid");
+        assert_eq!(state.pc_info.ip_address, "192.168.1.100");
+    }
+
+    #[test]
+    fn test_from_legacy_config_without_state() {
+        let contents = r#"
+            [client]
+            check_interval_secs = 3600
+
+            [pc_info]
+            user_name = "testuser"
+        "#;
+
+        assert_eq!(ClientState::from_legacy_config(contents).unwrap(), None);
+    }
+
+    fn temp_state_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("client_state_{}_{}.json", name, std::process::id()))
+    }
+
+    fn remove_state_files(path: &Path) {
+        let _ = fs::remove_file(path);
+        let _ = fs::remove_file(path.with_extension("json.lock"));
+    }
+
+    #[test]
+    fn test_update_and_load() {
+        let path = temp_state_path("update");
+        let store = StateStore::new(&path);
+
+        let saved = store
+            .update(|state| {
+                state.update_last_send_datetime("2024-01-31T09:00:00+09:00".to_string());
+                state.pc_info.uuid = "test-uuid".to_string();
+            })
+            .unwrap();
+
+        let loaded = store.load().unwrap();
+        remove_state_files(&path);
+        assert_eq!(loaded, saved);
+        assert_eq!(loaded.pc_info.uuid, "test-uuid");
+    }
+
+    /// 読み込み→変更→保存を並行して繰り返し、変更が失われず、ファイルが壊れないこと
+    fn hammer_updates(path: &Path, stores: Vec<StateStore>) {
+        const ITERATIONS: usize = 25;
+        let writers = stores.len();
+
+        let handles: Vec<_> = stores
+            .into_iter()
+            .enumerate()
+            .map(|(i, store)| {
+                std::thread::spawn(move || {
+                    for _ in 0..ITERATIONS {
+                        store
+                            .update(|state| {
+                                let count: usize = state.last_sent_hash.parse().unwrap_or(0);
+                                state.last_sent_hash = (count + 1).to_string();
+                                state.pc_info.model_name = format!("writer-{}", i);
+                            })
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let contents = fs::read_to_string(path).unwrap();
+        remove_state_files(path);
+        let state: ClientState = serde_json::from_str(&contents).unwrap();
+        assert_eq!(state.last_sent_hash, (writers * ITERATIONS).to_string());
+        assert!(state.pc_info.model_name.starts_with("writer-"));
+    }
+
+    #[test]
+    fn test_concurrent_updates_shared_store() {
+        let path = temp_state_path("shared");
+        let store = StateStore::new(&path);
+        hammer_updates(&path, (0..8).map(|_| store.clone()).collect());
+    }
+
+    #[test]
+    fn test_concurrent_updates_file_lock() {
+        // 別々のインスタンス（ミューテックスを共有しない）でもファイルロックで直列化される
+        let path = temp_state_path("file_lock");
+        hammer_updates(&path, (0..8).map(|_| StateStore::new(&path)).collect());
+    }
+
+    #[test]
+    fn test_load_or_migrate_keeps_existing_state() {
+        let path = temp_state_path("migrate");
+        let store = StateStore::new(&path);
+        store.update(|state| state.pc_info.uuid = "current-uuid".to_string()).unwrap();
+
+        // 状態ファイルがある場合は config.toml を読まない
+        let state = store.load_or_migrate("does-not-exist.toml").unwrap();
+        remove_state_files(&path);
+        assert_eq!(state.pc_info.uuid, "current-uuid");
+    }
+}
@@ -0,0 +1,118 @@
+use crate::error::Result;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// 一時ファイル名の重複を避けるための連番
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// ファイルを一時ファイル経由で置き換える
+///
+/// 同じディレクトリの一時ファイルに書き込んでディスクへ同期した後、rename で置き換えます。
+/// 書き込み中にプロセスが終了しても、置き換え前のファイルはそのまま残ります。
+///
+/// # 引数
+/// * `path` - 書き込み先のファイル
+/// * `contents` - 書き込む内容
+///
+/// # エラー
+/// 一時ファイルの作成・書き込み、または置き換えに失敗した場合
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let temp_path = temp_path_for(path);
+
+    let result = (|| {
+        let mut file = File::create(&temp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&temp_path, path)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    Ok(result?)
+}
+
+/// 書き込み先と同じディレクトリの一時ファイル名（".<名前>.<PID>.<連番>.tmp"）
+fn temp_path_for(path: &Path) -> PathBuf {
+    let mut name = std::ffi::OsString::from(".");
+    name.push(path.file_name().unwrap_or_default());
+    name.push(format!(
+        ".{}.{}.tmp",
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    path.with_file_name(name)
+}
+
+/// アドバイザリロック（排他）
+///
+/// 対象ファイルと同じディレクトリの "<名前>.lock" をロックします。
+/// 対象ファイル自体は rename で置き換えられるため、ロックは別ファイルで行います。
+/// drop 時にロックを解放します。
+pub struct FileLock {
+    file: File,
+}
+
+impl FileLock {
+    /// 排他ロックを取得（他のプロセス・スレッドが保持している間は待機）
+    ///
+    /// # 引数
+    /// * `path` - ロック対象のファイル
+    ///
+    /// # エラー
+    /// ロックファイルの作成またはロックの取得に失敗した場合
+    pub fn exclusive(path: &Path) -> Result<Self> {
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(".lock");
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path.with_file_name(name))?;
+        file.lock()?;
+
+        Ok(Self { file })
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_atomic_concurrent() {
+        let path = std::env::temp_dir().join(format!("storage_test_{}.json", std::process::id()));
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    for j in 0..25 {
+                        let contents = serde_json::json!({ "writer": i, "seq": j, "padding": "x".repeat(4096) });
+                        write_atomic(&path, contents.to_string().as_bytes()).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        // 書き込みが交錯しても、いずれか1回分の完全な内容になっている
+        let contents = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        let value: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        assert_eq!(value["padding"].as_str().unwrap().len(), 4096);
+    }
+}
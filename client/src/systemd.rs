@@ -0,0 +1,48 @@
+//! systemd との連携（Linux）
+//!
+//! 起動完了は設定・状態ファイルの読み込み後に通知します。
+//! ウォッチドッグはスケジューラの状態遷移が期限内に進んでいる間だけ通知します（Scheduler::with_progress）。
+//! 通知・ウォッチドッグ・journald の処理はサーバーと共通（pc-inventory-systemd）です。
+//!
+//! ユニットファイルは `pc-inventory-client --print-systemd-unit` で出力します。
+//! 自動更新による再起動（update::RESTART_EXIT_CODE）は Restart=on-failure で新しいバージョンを起動します。
+
+use crate::service::RESTART_DELAY;
+pub use pc_inventory_systemd::{
+    is_journal_stream, journald_layer, notify, shutdown_signal, spawn_watchdog, watchdog_interval, Notification, Progress,
+    UnitFile,
+};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// ユニット名（--print-systemd-unit の出力の保存先）
+pub const UNIT_NAME: &str = "pc-inventory-client.service";
+
+/// ユニットファイルに設定するウォッチドッグのタイムアウト
+pub const WATCHDOG_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// 実行ファイルを Type=notify のサービスとして起動するユニットファイル
+///
+/// # 引数
+/// * `executable` - 実行ファイルのパス（通常は現在の実行ファイル）
+pub fn unit_file(executable: impl Into<PathBuf>) -> UnitFile {
+    let mut unit = UnitFile::for_executable("PC Inventory Client", executable);
+    unit.restart_delay = RESTART_DELAY;
+    unit.watchdog_timeout = WATCHDOG_TIMEOUT;
+    unit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_file() {
+        let unit = unit_file("/opt/pc-inventory/pc-inventory-client").render();
+
+        assert!(unit.contains("Description=PC Inventory Client\n"));
+        assert!(unit.contains("ExecStart=/opt/pc-inventory/pc-inventory-client\n"));
+        assert!(unit.contains("Restart=on-failure\nRestartSec=10\n"));
+        assert!(unit.contains("WatchdogSec=60\n"));
+    }
+}
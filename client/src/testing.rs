@@ -0,0 +1,162 @@
+//! テスト用の部品（単体テスト・結合テストで共用）
+//!
+//! `test-util` フィーチャーを有効にした場合のみ公開します（結合テストは dev-dependencies で有効化）。
+//!
+//! - FakeClock: 待機せずに時刻だけを進める時計
+//! - FakeCollector: 固定のPC情報を返す収集元
+//!
+//! サーバーの代わりに送信内容を記録する送信手段は api::MemoryTransport です。
+
+use crate::config::ClientConfig;
+use crate::error::{ClientError, Result};
+use crate::network::detector::NetworkInfo;
+use crate::network::{AdapterInfo, NetworkType};
+use crate::scheduler::{Clock, Collector};
+use crate::software::SoftwareInfo;
+use crate::wmi::collector::PcInfoData as SystemInfo;
+use crate::wmi::{HardwareInfo, HotfixInfo};
+use chrono::{DateTime, TimeZone, Utc};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// FakeCollector が返すUUID
+pub const TEST_UUID: &str = "test-uuid";
+
+/// 待機せずに時刻だけを進める時計
+///
+/// clone したインスタンスは時刻を共有します。
+#[derive(Debug, Clone)]
+pub struct FakeClock(Arc<Mutex<DateTime<Utc>>>);
+
+impl FakeClock {
+    /// 開始時刻
+    pub fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 31, 0, 0, 0).unwrap()
+    }
+
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(Self::start())))
+    }
+
+    /// 開始時刻からの経過秒数
+    pub fn elapsed_secs(&self) -> i64 {
+        (self.now() - Self::start()).num_seconds()
+    }
+}
+
+impl Default for FakeClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.0.lock().unwrap()
+    }
+
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+        *self.0.lock().unwrap() += chrono::Duration::from_std(duration).unwrap();
+        std::future::ready(())
+    }
+}
+
+/// 固定のPC情報を返す収集元（ネットワーク情報は差し替え可能）
+///
+/// clone したインスタンスはネットワーク情報を共有するため、スケジューラに渡した後も
+/// テスト側から IPアドレスの変更・切断を再現できます。
+#[derive(Debug, Clone)]
+pub struct FakeCollector {
+    network: Arc<Mutex<Option<NetworkInfo>>>,
+}
+
+impl FakeCollector {
+    pub fn new() -> Self {
+        Self {
+            network: Arc::new(Mutex::new(Some(NetworkInfo {
+                ip_address: "192.168.1.100".to_string(),
+                ipv6_address: "2001:db8::10".to_string(),
+                mac_address: "00:11:22:33:44:55".to_string(),
+                network_type: NetworkType::Wired,
+            }))),
+        }
+    }
+
+    /// 代表アダプタのIPアドレスを変更
+    pub fn set_ip_address(&self, ip_address: &str) {
+        if let Some(network) = self.network.lock().unwrap().as_mut() {
+            network.ip_address = ip_address.to_string();
+        }
+    }
+
+    /// ネットワークから切断（network_info がエラーを返す）
+    pub fn disconnect(&self) {
+        *self.network.lock().unwrap() = None;
+    }
+}
+
+impl Default for FakeCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Collector for FakeCollector {
+    fn system_info(&self) -> Result<SystemInfo> {
+        Ok(SystemInfo {
+            uuid: TEST_UUID.to_string(),
+            model_name: "Test Model".to_string(),
+            hardware: HardwareInfo {
+                manufacturer: Some("Test Manufacturer".to_string()),
+                total_memory_bytes: Some(17_179_869_184),
+                logical_processors: Some(8),
+            },
+            os: "Windows 11 Pro".to_string(),
+            os_version: "10.0.22631".to_string(),
+            user_name: "testuser".to_string(),
+        })
+    }
+
+    fn network_info(&self, _config: &ClientConfig) -> Result<NetworkInfo> {
+        self.network
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| ClientError::NetworkError("No active network adapter found".to_string()))
+    }
+
+    fn adapters(&self, config: &ClientConfig) -> Result<Vec<AdapterInfo>> {
+        let network = self.network_info(config)?;
+        Ok(vec![AdapterInfo {
+            name: "Ethernet".to_string(),
+            mac_address: Some(network.mac_address),
+            ipv4_addresses: vec![network.ip_address],
+            ipv6_addresses: vec![network.ipv6_address],
+            network_type: network.network_type,
+            is_up: true,
+            is_primary: true,
+            index: 1,
+        }])
+    }
+
+    fn software(&self) -> Result<Vec<SoftwareInfo>> {
+        Ok(vec![SoftwareInfo {
+            name: "Google Chrome".to_string(),
+            version: "120.0.6099.130".to_string(),
+            publisher: Some("Google LLC".to_string()),
+            install_date: Some("2024-01-15".to_string()),
+        }])
+    }
+
+    fn updates(&self) -> Result<(Option<u32>, Vec<HotfixInfo>)> {
+        Ok((
+            Some(3007),
+            vec![HotfixInfo {
+                hotfix_id: "KB5034441".to_string(),
+                description: Some("Security Update".to_string()),
+                installed_on: Some("2024-01-10".to_string()),
+            }],
+        ))
+    }
+}
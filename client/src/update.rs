@@ -0,0 +1,227 @@
+//! 自動更新
+//!
+//! サーバーの配布チャネルから取得した実行ファイルの署名を検証し、現在の実行ファイルと置き換えます。
+//! 置き換え後はプロセスを終了し、サービスマネージャー（Windows サービスの回復操作・systemd）による再起動で
+//! 新しいバージョンを起動します。
+//!
+//! 新しいバージョンは最初の送信に成功するまで「試用中」（UpdateState.pending）とし、
+//! サーバーに到達したうえで送信に失敗した場合、または送信に成功しないまま起動を繰り返した場合は
+//! 元の実行ファイルに戻します（サーバーに到達できないだけでは戻しません）。
+
+use crate::error::{ClientError, Result};
+use crate::storage::write_atomic;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 再起動を要求する終了コード
+///
+/// Windows サービスの回復操作・systemd（Restart=on-failure）はこのコードでの終了後にクライアントを再起動します。
+pub const RESTART_EXIT_CODE: i32 = 75;
+
+/// 試用中のバージョンの起動回数の上限（最初の送信に至らずに超えた場合は元に戻す）
+pub const MAX_START_ATTEMPTS: u32 = 3;
+
+/// 自動更新の状態（ClientState.update）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdateState {
+    /// 置き換え後、最初の送信の成功を待っている更新
+    pub pending: Option<PendingUpdate>,
+    /// 最初の送信に失敗して元に戻したバージョン（再び適用しない）
+    pub rejected_versions: Vec<String>,
+}
+
+/// 試用中の更新
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingUpdate {
+    /// 置き換え後のバージョン
+    pub version: String,
+    /// 置き換え前のバージョン
+    pub previous_version: String,
+    /// 置き換え後の起動回数
+    pub attempts: u32,
+}
+
+impl UpdateState {
+    /// 試用中の更新を取り消し、そのバージョンを再び適用しないよう記録
+    pub fn reject_pending(&mut self) {
+        if let Some(pending) = self.pending.take() {
+            if !self.rejected_versions.contains(&pending.version) {
+                self.rejected_versions.push(pending.version);
+            }
+        }
+    }
+}
+
+/// 実行ファイルの置き換え
+///
+/// 実行中のファイルは削除できないが名前は変更できるため（Windows）、
+/// 次の名前で置き換え・復元を行います。
+/// - "<実行ファイル>.new": ダウンロードした実行ファイル（置き換え前の一時ファイル）
+/// - "<実行ファイル>.old": 置き換え前の実行ファイル（試用中のみ）
+/// - "<実行ファイル>.failed": 元に戻した実行ファイル
+#[derive(Debug, Clone)]
+pub struct Installer {
+    path: PathBuf,
+}
+
+impl Installer {
+    /// 置き換える実行ファイルを指定して作成
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// 実行中の実行ファイルを置き換える
+    ///
+    /// # エラー
+    /// 実行ファイルのパスを取得できない場合
+    pub fn current_exe() -> Result<Self> {
+        Ok(Self::new(std::env::current_exe()?))
+    }
+
+    /// 置き換える実行ファイル
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 実行ファイルを置き換え
+    ///
+    /// 現在の実行ファイルは "<実行ファイル>.old" として残します（rollback で復元）。
+    ///
+    /// # 引数
+    /// * `binary` - 署名を検証済みの実行ファイル
+    ///
+    /// # エラー
+    /// 書き込み・名前の変更に失敗した場合（現在の実行ファイルはそのまま残ります）
+    pub fn install(&self, binary: &[u8]) -> Result<()> {
+        let staged = self.sibling("new");
+        let backup = self.sibling("old");
+
+        write_atomic(&staged, binary)?;
+        fs::set_permissions(&staged, fs::metadata(&self.path)?.permissions())?;
+
+        fs::rename(&self.path, &backup)?;
+        if let Err(e) = fs::rename(&staged, &self.path) {
+            let _ = fs::rename(&backup, &self.path);
+            let _ = fs::remove_file(&staged);
+            return Err(e.into());
+        }
+
+        tracing::info!("Executable replaced: {} (backup: {})", self.path.display(), backup.display());
+        Ok(())
+    }
+
+    /// 置き換えを確定し、置き換え前の実行ファイルを削除
+    pub fn commit(&self) -> Result<()> {
+        remove_if_exists(&self.sibling("old"))?;
+        remove_if_exists(&self.sibling("failed"))?;
+        Ok(())
+    }
+
+    /// 置き換え前の実行ファイルに戻す
+    ///
+    /// 実行中の（置き換え後の）実行ファイルは "<実行ファイル>.failed" として残します。
+    ///
+    /// # エラー
+    /// 置き換え前の実行ファイルが無い、または名前の変更に失敗した場合
+    pub fn rollback(&self) -> Result<()> {
+        let backup = self.sibling("old");
+        if !backup.exists() {
+            return Err(ClientError::UpdateError(format!("Backup not found: {}", backup.display())));
+        }
+
+        fs::rename(&self.path, self.sibling("failed"))?;
+        fs::rename(&backup, &self.path)?;
+
+        tracing::warn!("Executable restored from backup: {}", self.path.display());
+        Ok(())
+    }
+
+    /// 実行ファイルと同じディレクトリの "<実行ファイル>.<extension>"
+    fn sibling(&self, extension: &str) -> PathBuf {
+        let mut name = self.path.file_name().unwrap_or_default().to_os_string();
+        name.push(".");
+        name.push(extension);
+        self.path.with_file_name(name)
+    }
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TempExe {
+        dir: PathBuf,
+        installer: Installer,
+    }
+
+    impl TempExe {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("update_{}_{}", name, std::process::id()));
+            fs::create_dir_all(&dir).unwrap();
+            let path = dir.join("pc-inventory-client.exe");
+            fs::write(&path, b"old version").unwrap();
+            Self { dir, installer: Installer::new(path) }
+        }
+
+        fn contents(&self, name: &str) -> Option<Vec<u8>> {
+            fs::read(self.dir.join(name)).ok()
+        }
+    }
+
+    impl Drop for TempExe {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    #[test]
+    fn test_install_then_commit() {
+        let exe = TempExe::new("commit");
+        exe.installer.install(b"new version").unwrap();
+        assert_eq!(exe.contents("pc-inventory-client.exe").unwrap(), b"new version");
+        assert_eq!(exe.contents("pc-inventory-client.exe.old").unwrap(), b"old version");
+        assert!(exe.contents("pc-inventory-client.exe.new").is_none());
+
+        exe.installer.commit().unwrap();
+        assert!(exe.contents("pc-inventory-client.exe.old").is_none());
+    }
+
+    #[test]
+    fn test_install_then_rollback() {
+        let exe = TempExe::new("rollback");
+        exe.installer.install(b"new version").unwrap();
+        exe.installer.rollback().unwrap();
+
+        assert_eq!(exe.contents("pc-inventory-client.exe").unwrap(), b"old version");
+        assert_eq!(exe.contents("pc-inventory-client.exe.failed").unwrap(), b"new version");
+
+        // 置き換え前の実行ファイルが無い場合は戻せない
+        assert!(exe.installer.rollback().is_err());
+        assert_eq!(exe.contents("pc-inventory-client.exe").unwrap(), b"old version");
+    }
+
+    #[test]
+    fn test_reject_pending() {
+        let mut state = UpdateState {
+            pending: Some(PendingUpdate {
+                version: "0.2.0".to_string(),
+                previous_version: "0.1.0".to_string(),
+                attempts: 1,
+            }),
+            rejected_versions: Vec::new(),
+        };
+        state.reject_pending();
+        assert_eq!(state.pending, None);
+        assert_eq!(state.rejected_versions, vec!["0.2.0".to_string()]);
+    }
+}
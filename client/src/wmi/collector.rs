@@ -1,14 +1,13 @@
 use crate::error::{ClientError, Result};
 use serde::Deserialize;
-use wmi::{COMLibrary, WMIConnection, Variant};
-use std::collections::HashMap;
 
 /// WMI情報収集構造体
 ///
 /// Windows Management Instrumentation (WMI) を使用して、
 /// PC のハードウェア情報、OS情報、システム情報を取得します。
+/// Windows 以外では new() が常にエラーを返します（WMIを使用しない関数はすべての環境で使用できます）。
 pub struct WmiCollector {
-    wmi_con: WMIConnection,
+    wmi_con: platform::Connection,
 }
 
 /// マザーボード情報（UUID取得用）
@@ -20,13 +19,15 @@ struct ComputerSystemProduct {
     uuid: Option<String>,
 }
 
-/// コンピュータシステム情報（機種名取得用）
+/// コンピュータシステム情報（機種名・ハードウェア情報取得用）
 #[derive(Deserialize, Debug)]
 #[serde(rename = "Win32_ComputerSystem")]
 #[serde(rename_all = "PascalCase")]
 struct ComputerSystem {
     manufacturer: Option<String>,
     model: Option<String>,
+    total_physical_memory: Option<u64>,
+    number_of_logical_processors: Option<u32>,
 }
 
 /// OS情報
@@ -38,6 +39,23 @@ struct OperatingSystem {
     version: Option<String>,
 }
 
+/// 更新プログラム（修正プログラム）情報
+#[derive(Deserialize, Debug)]
+#[serde(rename = "Win32_QuickFixEngineering")]
+#[serde(rename_all = "PascalCase")]
+struct QuickFixEngineering {
+    #[serde(rename = "HotFixID")]
+    hot_fix_id: Option<String>,
+    description: Option<String>,
+    installed_on: Option<String>,
+}
+
+/// インストール済みの更新プログラム（KB、送信形式と同じ）
+pub type HotfixInfo = pc_inventory_protocol::HotfixItem;
+
+/// ハードウェア情報（送信形式と同じ）
+pub type HardwareInfo = pc_inventory_protocol::HardwareInfo;
+
 impl WmiCollector {
     /// 新しいWMIコレクタインスタンスを作成
     ///
@@ -46,11 +64,7 @@ impl WmiCollector {
     pub fn new() -> Result<Self> {
         tracing::debug!("Initializing WMI connection");
 
-        let com_con = COMLibrary::new()
-            .map_err(|e| ClientError::WmiError(format!("Failed to initialize COM library: {}", e)))?;
-
-        let wmi_con = WMIConnection::new(com_con)
-            .map_err(|e| ClientError::WmiError(format!("Failed to connect to WMI: {}", e)))?;
+        let wmi_con = platform::Connection::new()?;
 
         tracing::debug!("WMI connection established successfully");
 
@@ -113,6 +127,35 @@ impl WmiCollector {
         Ok(model_name)
     }
 
+    /// ハードウェア情報を取得
+    ///
+    /// Win32_ComputerSystem の Manufacturer、TotalPhysicalMemory、NumberOfLogicalProcessors を取得します。
+    /// 取得できなかった項目は None とします。
+    ///
+    /// # エラー
+    /// WMIクエリの実行に失敗した場合、またはコンピュータシステム情報が取得できなかった場合
+    pub fn get_hardware_info(&self) -> Result<HardwareInfo> {
+        tracing::debug!("Querying hardware info from Win32_ComputerSystem");
+
+        let results: Vec<ComputerSystem> = self.wmi_con
+            .query()
+            .map_err(|e| ClientError::WmiError(format!("Failed to query hardware info: {}", e)))?;
+
+        let system = results
+            .into_iter()
+            .next()
+            .ok_or_else(|| ClientError::WmiError("Computer system info not found".to_string()))?;
+
+        let hardware = HardwareInfo {
+            manufacturer: system.manufacturer,
+            total_memory_bytes: system.total_physical_memory,
+            logical_processors: system.number_of_logical_processors,
+        };
+
+        tracing::info!("Hardware info retrieved: {:?}", hardware);
+        Ok(hardware)
+    }
+
     /// OS情報を取得
     ///
     /// Win32_OperatingSystem の Caption と Version から、
@@ -142,6 +185,98 @@ impl WmiCollector {
         Ok((os_name, os_version))
     }
 
+    /// OSのビルドリビジョン（UBR）を取得
+    ///
+    /// レジストリ HKLM\SOFTWARE\Microsoft\Windows NT\CurrentVersion の UBR 値から取得します。
+    /// Win32_OperatingSystem.Version（例: "10.0.19045"）には含まれないため、
+    /// 同じバージョンのPCでも累積更新プログラムの適用状況を区別するために使用します。
+    ///
+    /// # 戻り値
+    /// UBR（例: 3930）。値が存在しない場合は None
+    ///
+    /// # エラー
+    /// レジストリの読み込みに失敗した場合
+    #[cfg(windows)]
+    pub fn get_os_build_revision() -> Result<Option<u32>> {
+        use winreg::enums::{HKEY_LOCAL_MACHINE, KEY_READ};
+        use winreg::RegKey;
+
+        tracing::debug!("Reading UBR from registry");
+
+        let key = RegKey::predef(HKEY_LOCAL_MACHINE)
+            .open_subkey_with_flags(r"SOFTWARE\Microsoft\Windows NT\CurrentVersion", KEY_READ)
+            .map_err(|e| ClientError::WmiError(format!("Failed to open CurrentVersion registry key: {}", e)))?;
+
+        let ubr = key.get_value::<u32, _>("UBR").ok();
+
+        tracing::info!("OS build revision retrieved: {:?}", ubr);
+        Ok(ubr)
+    }
+
+    /// OSのビルドリビジョン（UBR）を取得
+    ///
+    /// Windows 以外では常に None を返します。
+    #[cfg(not(windows))]
+    pub fn get_os_build_revision() -> Result<Option<u32>> {
+        Ok(None)
+    }
+
+    /// インストール済みの更新プログラム一覧を取得
+    ///
+    /// Win32_QuickFixEngineering から HotFixID、Description、InstalledOn を取得します。
+    /// HotFixID が空のエントリは除外します。
+    ///
+    /// # 戻り値
+    /// HotfixInfo のベクター（HotFixID 順）
+    ///
+    /// # エラー
+    /// WMIクエリの実行に失敗した場合
+    pub fn get_hotfixes(&self) -> Result<Vec<HotfixInfo>> {
+        tracing::debug!("Querying hotfixes from Win32_QuickFixEngineering");
+
+        let results: Vec<QuickFixEngineering> = self.wmi_con
+            .query()
+            .map_err(|e| ClientError::WmiError(format!("Failed to query hotfixes: {}", e)))?;
+
+        let mut hotfixes: Vec<HotfixInfo> = results
+            .into_iter()
+            .filter_map(|qfe| {
+                let hotfix_id = qfe.hot_fix_id?.trim().to_string();
+                if hotfix_id.is_empty() {
+                    return None;
+                }
+                Some(HotfixInfo {
+                    hotfix_id,
+                    description: qfe.description.map(|d| d.trim().to_string()).filter(|d| !d.is_empty()),
+                    installed_on: qfe.installed_on.as_deref().and_then(Self::normalize_installed_on),
+                })
+            })
+            .collect();
+        hotfixes.sort_by(|a, b| a.hotfix_id.cmp(&b.hotfix_id));
+        hotfixes.dedup_by(|a, b| a.hotfix_id == b.hotfix_id);
+
+        tracing::info!("Hotfixes retrieved: {} entries", hotfixes.len());
+        Ok(hotfixes)
+    }
+
+    /// InstalledOn を "YYYY-MM-DD" 形式に変換
+    ///
+    /// 通常は "M/D/YYYY" 形式ですが、環境によっては FILETIME の16進文字列が
+    /// 返されるため、両方に対応します。解釈できない場合は None を返します。
+    fn normalize_installed_on(value: &str) -> Option<String> {
+        let value = value.trim();
+
+        if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%m/%d/%Y") {
+            return Some(date.format("%Y-%m-%d").to_string());
+        }
+
+        // FILETIME: 1601-01-01 からの100ナノ秒単位
+        const FILETIME_UNIX_EPOCH_DIFF_SECS: i64 = 11_644_473_600;
+        let filetime = i64::from_str_radix(value.trim_start_matches("0x"), 16).ok()?;
+        chrono::DateTime::from_timestamp(filetime / 10_000_000 - FILETIME_UNIX_EPOCH_DIFF_SECS, 0)
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+    }
+
     /// ユーザー名を取得
     ///
     /// 環境変数 USERNAME からWindowsログインユーザー名を取得します。
@@ -163,7 +298,7 @@ impl WmiCollector {
 
     /// すべてのPC情報を一度に取得
     ///
-    /// UUID、機種名、OS情報、ユーザー名をまとめて取得します。
+    /// UUID、機種名、ハードウェア情報、OS情報、ユーザー名をまとめて取得します。
     ///
     /// # 戻り値
     /// PcInfoData構造体
@@ -172,12 +307,14 @@ impl WmiCollector {
 
         let uuid = self.get_uuid()?;
         let model_name = self.get_model_name()?;
+        let hardware = self.get_hardware_info()?;
         let (os, os_version) = self.get_os_info()?;
         let user_name = Self::get_user_name()?;
 
         Ok(PcInfoData {
             uuid,
             model_name,
+            hardware,
             os,
             os_version,
             user_name,
@@ -190,11 +327,55 @@ impl WmiCollector {
 pub struct PcInfoData {
     pub uuid: String,
     pub model_name: String,
+    pub hardware: HardwareInfo,
     pub os: String,
     pub os_version: String,
     pub user_name: String,
 }
 
+#[cfg(windows)]
+mod platform {
+    use crate::error::{ClientError, Result};
+    use serde::de::DeserializeOwned;
+    use wmi::{COMLibrary, WMIConnection};
+
+    pub(super) struct Connection(WMIConnection);
+
+    impl Connection {
+        pub(super) fn new() -> Result<Self> {
+            let com_con = COMLibrary::new()
+                .map_err(|e| ClientError::WmiError(format!("Failed to initialize COM library: {}", e)))?;
+
+            let wmi_con = WMIConnection::new(com_con)
+                .map_err(|e| ClientError::WmiError(format!("Failed to connect to WMI: {}", e)))?;
+
+            Ok(Self(wmi_con))
+        }
+
+        pub(super) fn query<T: DeserializeOwned>(&self) -> std::result::Result<Vec<T>, wmi::WMIError> {
+            self.0.query()
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod platform {
+    use crate::error::{ClientError, Result};
+    use serde::de::DeserializeOwned;
+
+    pub(super) struct Connection;
+
+    impl Connection {
+        pub(super) fn new() -> Result<Self> {
+            Err(ClientError::WmiError("WMI is only available on Windows".to_string()))
+        }
+
+        pub(super) fn query<T: DeserializeOwned>(&self) -> std::result::Result<Vec<T>, ClientError> {
+            Err(ClientError::WmiError("WMI is only available on Windows".to_string()))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,6 +409,17 @@ mod tests {
         println!("Model: {}", model_str);
     }
 
+    #[test]
+    #[ignore]
+    fn test_get_hardware_info() {
+        let collector = WmiCollector::new().unwrap();
+        let hardware = collector.get_hardware_info();
+        assert!(hardware.is_ok());
+        let hardware = hardware.unwrap();
+        assert!(hardware.total_memory_bytes.unwrap_or(0) > 0);
+        println!("Hardware: {:?}", hardware);
+    }
+
     #[test]
     #[ignore]
     fn test_get_os_info() {
@@ -250,6 +442,28 @@ mod tests {
         println!("Username: {}", username_str);
     }
 
+    #[test]
+    #[cfg(not(windows))]
+    fn test_wmi_collector_new_unsupported() {
+        assert!(matches!(WmiCollector::new(), Err(ClientError::WmiError(_))));
+    }
+
+    #[test]
+    fn test_normalize_installed_on() {
+        assert_eq!(WmiCollector::normalize_installed_on("1/31/2024"), Some("2024-01-31".to_string()));
+        assert_eq!(WmiCollector::normalize_installed_on("01d9a7e0c6b4a000"), Some("2023-06-26".to_string()));
+        assert_eq!(WmiCollector::normalize_installed_on(""), None);
+    }
+
+    #[test]
+    #[ignore]
+    fn test_get_hotfixes() {
+        let collector = WmiCollector::new().unwrap();
+        let hotfixes = collector.get_hotfixes();
+        assert!(hotfixes.is_ok());
+        println!("Hotfixes: {:?}", hotfixes.unwrap());
+    }
+
     #[test]
     #[ignore]
     fn test_collect_all() {
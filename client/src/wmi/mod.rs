@@ -1,3 +1,3 @@
 pub mod collector;
 
-pub use collector::WmiCollector;
+pub use collector::{HardwareInfo, HotfixInfo, WmiCollector};
@@ -0,0 +1,249 @@
+//! 結合テスト用の共通部品
+//!
+//! - MockServer: サーバーと同じエンドポイント・応答形式を持つインプロセスの axum サーバー
+//!   （start: バージョン導入前のサーバー、start_v2: /api/capabilities と /api/v2 に対応したサーバー）
+//! - TempFiles: 一時ディレクトリ内の状態ファイルとテスト用のクライアント設定
+//!
+//! 時計・収集元はクライアントの testing モジュール（FakeClock・FakeCollector）を使用します。
+
+#![allow(dead_code)]
+
+use axum::extract::{OriginalUri, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::Utc;
+use pc_inventory_client::config::ClientConfig;
+use pc_inventory_protocol::{
+    v1, CapabilitiesResponse, ErrorResponse, HeartbeatRequest, HeartbeatResponse, PcAdapterRequest, PcAdapterResponse,
+    PcInfoRequest, PcInfoResponse, PcSoftwareRequest, PcSoftwareResponse, PcUpdateRequest, PcUpdateResponse, ValidationError,
+};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::task::JoinHandle;
+
+type Shared = Arc<Mutex<Received>>;
+type Reply<T> = Result<Json<T>, (StatusCode, Json<ErrorResponse>)>;
+
+/// MockServer が受信した内容（サーバーと同じくプロトコルの型に変換・検証済み）
+#[derive(Debug, Default)]
+pub struct Received {
+    /// 登録済みPC（UUID → (ID, 最後に受信したPC情報)）
+    pub pcs: HashMap<String, (i32, PcInfoRequest)>,
+    /// POST /api/pc-info の受信回数
+    pub pc_info_posts: usize,
+    /// PC情報を受信したパス（受信順）
+    pub pc_info_paths: Vec<String>,
+    pub heartbeats: Vec<String>,
+    pub adapters: Vec<PcAdapterRequest>,
+    pub software: Vec<PcSoftwareRequest>,
+    pub updates: Vec<PcUpdateRequest>,
+    /// true の間、POST /api/pc-info に 500 を返す
+    pub fail_pc_info: bool,
+}
+
+/// インプロセスの axum サーバー
+///
+/// 実サーバー（server クレート）と同じパスで、リクエストをプロトコルの型（pc-inventory-protocol）に変換して検証し、
+/// プロトコルの型で応答します。受信内容はメモリに記録し、drop 時に停止します。
+pub struct MockServer {
+    addr: std::net::SocketAddr,
+    received: Shared,
+    handle: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// バージョン導入前のサーバー（従来のパスのみ、GET /api/capabilities は 404）
+    pub async fn start() -> Self {
+        Self::serve(|app| app.nest("/api", client_routes().route("/pc-info", post(pc_info_v1)))).await
+    }
+
+    /// GET /api/capabilities と /api/v2 に対応したサーバー
+    pub async fn start_v2() -> Self {
+        Self::serve(|app| {
+            app.nest("/api", client_routes().route("/pc-info", post(pc_info_v1)))
+                .nest("/api/v2", client_routes().route("/pc-info", post(pc_info)))
+                .route("/api/capabilities", get(capabilities))
+        })
+        .await
+    }
+
+    async fn serve(routes: impl FnOnce(Router<Shared>) -> Router<Shared>) -> Self {
+        let received = Shared::default();
+        let app = routes(Router::new()).with_state(received.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        Self { addr, received, handle }
+    }
+
+    /// クライアント設定の server.url に指定するURL
+    pub fn url(&self) -> String {
+        format!("http://{}/api/pc-info", self.addr)
+    }
+
+    pub fn received(&self) -> MutexGuard<'_, Received> {
+        self.received.lock().unwrap()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// PC情報以外のクライアントからの送信（全バージョンで同じ形式）
+fn client_routes() -> Router<Shared> {
+    Router::new()
+        .route("/pc-heartbeat", post(heartbeat))
+        .route("/pc-adapters", post(adapters))
+        .route("/pc-software", post(software))
+        .route("/pc-updates", post(updates))
+}
+
+async fn capabilities() -> Json<CapabilitiesResponse> {
+    Json(CapabilitiesResponse::current())
+}
+
+fn error(status: StatusCode, message: impl Into<String>) -> (StatusCode, Json<ErrorResponse>) {
+    (status, Json(ErrorResponse::new(message)))
+}
+
+/// サーバーと同じく、検証エラーは 400 を返す
+fn validate(result: Result<(), ValidationError>) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    result.map_err(|e| error(StatusCode::BAD_REQUEST, e.to_string()))
+}
+
+fn registered_id(received: &Received, uuid: &str) -> Result<i32, (StatusCode, Json<ErrorResponse>)> {
+    received
+        .pcs
+        .get(uuid)
+        .map(|(id, _)| *id)
+        .ok_or_else(|| error(StatusCode::NOT_FOUND, format!("PC not found: {}", uuid)))
+}
+
+/// 従来のパス（v1 の形式）は現在の形式に変換して処理
+async fn pc_info_v1(state: State<Shared>, uri: OriginalUri, Json(body): Json<v1::PcInfoRequest>) -> Reply<PcInfoResponse> {
+    pc_info(state, uri, Json(body.into())).await
+}
+
+async fn pc_info(State(received): State<Shared>, OriginalUri(uri): OriginalUri, Json(body): Json<PcInfoRequest>) -> Reply<PcInfoResponse> {
+    let mut received = received.lock().unwrap();
+    received.pc_info_posts += 1;
+    received.pc_info_paths.push(uri.path().to_string());
+    if received.fail_pc_info {
+        return Err(error(StatusCode::INTERNAL_SERVER_ERROR, "Database error: connection lost"));
+    }
+    validate(body.validate())?;
+
+    let next_id = received.pcs.len() as i32 + 1;
+    let response = match received.pcs.get(&body.uuid) {
+        Some((id, _)) => PcInfoResponse::updated(*id),
+        None => PcInfoResponse::created(next_id),
+    };
+    received.pcs.insert(body.uuid.clone(), (response.id, body));
+
+    Ok(Json(response))
+}
+
+async fn heartbeat(State(received): State<Shared>, Json(body): Json<HeartbeatRequest>) -> Reply<HeartbeatResponse> {
+    let mut received = received.lock().unwrap();
+    validate(body.validate())?;
+    received.heartbeats.push(body.uuid.clone());
+    let id = registered_id(&received, &body.uuid)?;
+    Ok(Json(HeartbeatResponse::touched(id, Utc::now())))
+}
+
+async fn adapters(State(received): State<Shared>, Json(body): Json<PcAdapterRequest>) -> Reply<PcAdapterResponse> {
+    let mut received = received.lock().unwrap();
+    validate(body.validate())?;
+    let id = registered_id(&received, &body.uuid)?;
+    let count = body.adapters.len() as u64;
+    received.adapters.push(body);
+    Ok(Json(PcAdapterResponse::synced(id, count)))
+}
+
+async fn software(State(received): State<Shared>, Json(body): Json<PcSoftwareRequest>) -> Reply<PcSoftwareResponse> {
+    let mut received = received.lock().unwrap();
+    validate(body.validate())?;
+    let id = registered_id(&received, &body.uuid)?;
+    let (added, removed) = (body.added.len() as u64, body.removed.len() as u64);
+    received.software.push(body);
+    Ok(Json(PcSoftwareResponse::synced(id, added, removed)))
+}
+
+async fn updates(State(received): State<Shared>, Json(body): Json<PcUpdateRequest>) -> Reply<PcUpdateResponse> {
+    let mut received = received.lock().unwrap();
+    validate(body.validate())?;
+    let id = registered_id(&received, &body.uuid)?;
+    let count = body.hotfixes.len() as u64;
+    received.updates.push(body);
+    Ok(Json(PcUpdateResponse::synced(id, count)))
+}
+
+/// 一時ディレクトリ内の状態ファイル・スナップショットファイル（drop 時に削除）
+pub struct TempFiles {
+    dir: PathBuf,
+}
+
+impl TempFiles {
+    pub fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("pc_inventory_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        Self { dir }
+    }
+
+    pub fn path(&self, file: &str) -> PathBuf {
+        self.dir.join(file)
+    }
+
+    /// テスト用のクライアント設定
+    pub fn config(&self, server_url: &str) -> ClientConfig {
+        toml::from_str(&format!(
+            r#"
+            [server]
+            url = "{server_url}"
+            request_timeout_secs = 5
+
+            [client]
+            state_file = '{state_file}'
+            check_interval_secs = 3600
+            send_interval_secs = 21600
+            watch_network_changes = false
+
+            [retry]
+            first_retry_delay_secs = 900
+            second_retry_delay_secs = 3600
+
+            [pc_info]
+            user_name = "testuser"
+
+            [logging]
+            level = "info"
+            file = "client.log"
+            max_file_size_mb = 50
+            max_backup_files = 3
+
+            [software]
+            enabled = true
+            snapshot_file = '{snapshot_file}'
+            "#,
+            state_file = self.path("client_state.json").display(),
+            snapshot_file = self.path("software_snapshot.json").display(),
+        ))
+        .unwrap()
+    }
+}
+
+impl Drop for TempFiles {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
@@ -0,0 +1,201 @@
+//! スケジューラ → ApiClient（reqwest）→ インプロセスサーバーの送信サイクル全体のテスト
+
+mod common;
+
+use common::{MockServer, TempFiles};
+use pc_inventory_client::api::ApiClient;
+use pc_inventory_client::config::ClientConfig;
+use pc_inventory_client::error::Result;
+use pc_inventory_client::scheduler::{Clock, Scheduler, SchedulerState};
+use pc_inventory_client::state::StateStore;
+use pc_inventory_client::testing::{FakeClock, FakeCollector, TEST_UUID};
+use pc_inventory_protocol::{NetworkType, Section};
+
+type Connect = fn(&ClientConfig) -> Result<ApiClient>;
+
+fn connect(config: &ClientConfig) -> Result<ApiClient> {
+    ApiClient::new(config.server.url.clone(), config.server.request_timeout_secs)
+}
+
+fn scheduler(
+    files: &TempFiles,
+    server_url: &str,
+    clock: &FakeClock,
+    collector: &FakeCollector,
+) -> Scheduler<FakeClock, FakeCollector, Connect> {
+    let config = files.config(server_url);
+    let store = StateStore::new(&config.client.state_file);
+    let state = store.load().unwrap();
+    Scheduler::new(config, store, state, clock.clone(), collector.clone(), connect)
+}
+
+#[tokio::test]
+async fn test_startup_registers_pc_and_syncs_additional_info() {
+    let server = MockServer::start().await;
+    let files = TempFiles::new("startup");
+    let clock = FakeClock::new();
+    let mut scheduler = scheduler(&files, &server.url(), &clock, &FakeCollector::new());
+
+    assert_eq!(scheduler.step().await, SchedulerState::Idle);
+
+    {
+        let received = server.received();
+        let (id, pc) = &received.pcs[TEST_UUID];
+        assert_eq!(*id, 1);
+        assert_eq!(pc.user_name, "testuser");
+        assert_eq!(pc.network_type, NetworkType::Wired);
+        assert_eq!(pc.ip_address, "192.168.1.100");
+        assert_eq!(pc.ipv6_address.as_deref(), Some("2001:db8::10"));
+
+        assert_eq!(received.adapters.len(), 1);
+        assert_eq!(received.adapters[0].adapters[0].name, "Ethernet");
+        assert_eq!(received.software.len(), 1);
+        assert!(received.software[0].full_sync);
+        assert_eq!(received.updates.len(), 1);
+        assert_eq!(received.updates[0].os_build_revision, Some(3007));
+    }
+
+    // 送信結果は状態ファイルに保存される
+    let state = StateStore::new(files.path("client_state.json")).load().unwrap();
+    assert_eq!(state.pc_info.uuid, TEST_UUID);
+    assert_eq!(state.last_send_datetime, clock.now().to_rfc3339());
+    assert!(!state.last_sent_hash.is_empty());
+    assert!(files.path("software_snapshot.json").exists());
+}
+
+#[tokio::test]
+async fn test_unchanged_info_sends_heartbeat_and_changed_info_sends_full_data() {
+    let server = MockServer::start().await;
+    let files = TempFiles::new("heartbeat");
+    let clock = FakeClock::new();
+    let collector = FakeCollector::new();
+    let mut scheduler = scheduler(&files, &server.url(), &clock, &collector);
+    scheduler.step().await;
+
+    // 送信間隔（6時間）経過後、変化が無ければハートビートのみ
+    while scheduler.step().await != SchedulerState::Sending {}
+    assert_eq!(clock.elapsed_secs(), 21600);
+    assert_eq!(scheduler.step().await, SchedulerState::Idle);
+    {
+        let received = server.received();
+        assert_eq!(received.pc_info_posts, 1);
+        assert_eq!(received.heartbeats, vec![TEST_UUID.to_string()]);
+        // ソフトウェア一覧に変化が無いため差分は送信しない
+        assert_eq!(received.software.len(), 1);
+    }
+
+    // IPアドレスが変われば全項目を送信
+    collector.set_ip_address("192.168.1.101");
+    while scheduler.step().await != SchedulerState::Sending {}
+    assert_eq!(scheduler.step().await, SchedulerState::Idle);
+    {
+        let received = server.received();
+        assert_eq!(received.pc_info_posts, 2);
+        assert_eq!(received.heartbeats.len(), 1);
+        assert_eq!(received.pcs[TEST_UUID].1.ip_address, "192.168.1.101");
+    }
+}
+
+#[tokio::test]
+async fn test_heartbeat_for_unknown_pc_falls_back_to_full_send() {
+    let files = TempFiles::new("unknown");
+    let clock = FakeClock::new();
+    let collector = FakeCollector::new();
+
+    let first = MockServer::start().await;
+    scheduler(&files, &first.url(), &clock, &collector).step().await;
+    drop(first);
+
+    // サーバーのデータが失われた場合、ハートビートは 404 となり全項目を再送する
+    let second = MockServer::start().await;
+    let mut scheduler = scheduler(&files, &second.url(), &clock, &collector);
+    assert_eq!(scheduler.step().await, SchedulerState::Idle);
+
+    let received = second.received();
+    assert_eq!(received.heartbeats.len(), 1);
+    assert_eq!(received.pc_info_posts, 1);
+    assert!(received.pcs.contains_key(TEST_UUID));
+}
+
+#[tokio::test]
+async fn test_server_error_backs_off_until_recovered() {
+    let server = MockServer::start().await;
+    server.received().fail_pc_info = true;
+    let files = TempFiles::new("backoff");
+    let clock = FakeClock::new();
+    let mut scheduler = scheduler(&files, &server.url(), &clock, &FakeCollector::new());
+
+    assert_eq!(scheduler.step().await, SchedulerState::Backoff { attempt: 1 });
+    assert_eq!(scheduler.step().await, SchedulerState::Sending);
+    assert_eq!(scheduler.step().await, SchedulerState::Backoff { attempt: 2 });
+    assert_eq!(scheduler.step().await, SchedulerState::Sending);
+    assert_eq!(clock.elapsed_secs(), 900 + 3600);
+
+    server.received().fail_pc_info = false;
+    assert_eq!(scheduler.step().await, SchedulerState::Idle);
+    assert_eq!(server.received().pc_info_posts, 3);
+
+    // 復旧後の送信日時が状態ファイルに保存される
+    let state = StateStore::new(files.path("client_state.json")).load().unwrap();
+    assert_eq!(state.last_send_datetime, clock.now().to_rfc3339());
+}
+
+#[tokio::test]
+async fn test_unreachable_server_goes_offline() {
+    // 空きポートを確保してから閉じ、接続を拒否させる
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/api/pc-info", listener.local_addr().unwrap());
+    drop(listener);
+
+    let files = TempFiles::new("offline");
+    let clock = FakeClock::new();
+    let mut scheduler = scheduler(&files, &url, &clock, &FakeCollector::new());
+
+    assert_eq!(scheduler.step().await, SchedulerState::Offline { attempt: 1 });
+
+    let state = StateStore::new(files.path("client_state.json")).load().unwrap();
+    assert!(state.last_send_datetime.is_empty());
+}
+
+#[tokio::test]
+async fn test_versioned_server_receives_current_format() {
+    let server = MockServer::start_v2().await;
+    let files = TempFiles::new("versioned");
+    let clock = FakeClock::new();
+    let mut scheduler = scheduler(&files, &server.url(), &clock, &FakeCollector::new());
+
+    assert_eq!(scheduler.step().await, SchedulerState::Idle);
+
+    let received = server.received();
+    assert_eq!(received.pc_info_paths, ["/api/v2/pc-info"]);
+    let (_, pc) = &received.pcs[TEST_UUID];
+    let hardware = pc.hardware.as_ref().unwrap();
+    assert_eq!(hardware.manufacturer.as_deref(), Some("Test Manufacturer"));
+    assert_eq!(hardware.total_memory_bytes, Some(17_179_869_184));
+    assert_eq!(hardware.logical_processors, Some(8));
+    let agent = pc.agent.as_ref().unwrap();
+    assert_eq!(agent.version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(agent.capabilities, Section::ALL);
+    assert_eq!(received.adapters.len(), 1);
+    assert_eq!(received.software.len(), 1);
+    assert_eq!(received.updates.len(), 1);
+}
+
+#[tokio::test]
+async fn test_legacy_server_receives_v1_format() {
+    // GET /api/capabilities が無いサーバーには従来のパスで v1 の形式を送信する
+    let server = MockServer::start().await;
+    let files = TempFiles::new("legacy");
+    let clock = FakeClock::new();
+    let mut scheduler = scheduler(&files, &server.url(), &clock, &FakeCollector::new());
+
+    assert_eq!(scheduler.step().await, SchedulerState::Idle);
+
+    let received = server.received();
+    assert_eq!(received.pc_info_paths, ["/api/pc-info"]);
+    // 従来のパスは v1 の形式として受信する（ハードウェア情報・クライアントの情報は含まれない）
+    let (_, pc) = &received.pcs[TEST_UUID];
+    assert_eq!(pc.hardware, None);
+    assert_eq!(pc.agent, None);
+    assert_eq!(received.adapters.len(), 1);
+}
@@ -1,6 +1,6 @@
 -- PC情報収集システム データベース初期構築スクリプト
--- バージョン: 2.1
--- 最終更新: 2025-10-22
+-- バージョン: 2.2
+-- 最終更新: 2026-10-18
 
 -- データベース作成
 CREATE DATABASE IF NOT EXISTS pc_inventory
@@ -23,20 +23,129 @@ CREATE TABLE IF NOT EXISTS pc_info (
     id INT AUTO_INCREMENT PRIMARY KEY COMMENT 'DBが自動採番する主キー',
     uuid VARCHAR(100) UNIQUE NOT NULL COMMENT 'マザーボードシリアル番号（WMI経由）',
     mac_address VARCHAR(17) COMMENT '現在アクティブなNICのMACアドレス',
-    network_type VARCHAR(20) COMMENT 'Wired または Wireless',
+    network_type VARCHAR(20) COMMENT 'Wired / Wireless / VPN / Virtual / Cellular / Unknown',
     user_name VARCHAR(50) COMMENT '使用者名（config.tomlから取得）',
-    ip_address VARCHAR(15) COMMENT 'IPv4アドレス',
+    ip_address VARCHAR(45) COMMENT 'IPアドレス（IPv4またはIPv6）',
+    ipv6_address VARCHAR(45) COMMENT 'グローバルIPv6アドレス',
     os VARCHAR(100) COMMENT 'OS名',
     os_version VARCHAR(100) COMMENT 'OSバージョン',
+    os_build_revision INT UNSIGNED COMMENT 'OSビルドリビジョン（UBR）',
+    hotfixes_updated_at DATETIME COMMENT '更新プログラム一覧の最終報告日時',
     model_name VARCHAR(100) COMMENT 'PC機種名',
+    manufacturer VARCHAR(255) COMMENT 'メーカー名（APIバージョン v2 以降）',
+    total_memory_bytes BIGINT UNSIGNED COMMENT '物理メモリの合計（バイト、APIバージョン v2 以降）',
+    logical_processors INT UNSIGNED COMMENT '論理プロセッサ数（APIバージョン v2 以降）',
+    agent_version VARCHAR(50) COMMENT 'クライアントのバージョン（APIバージョン v2 以降）',
+    agent_build VARCHAR(64) COMMENT 'クライアントのビルドハッシュ',
+    agent_capabilities VARCHAR(255) COMMENT 'クライアントが収集できる項目（カンマ区切り）',
     created_at DATETIME NOT NULL COMMENT '初回登録日時',
-    updated_at DATETIME NOT NULL COMMENT '最終更新日時',
+    updated_at DATETIME NOT NULL COMMENT '最終更新日時（いずれかの項目の値が変化した日時）',
+    last_seen_at DATETIME COMMENT '最終接触日時（PC情報の送信・ハートビート受信時に更新）',
     INDEX idx_uuid (uuid),
     INDEX idx_mac_address (mac_address),
-    INDEX idx_updated_at (updated_at)
+    INDEX idx_ip_address (ip_address),
+    INDEX idx_ipv6_address (ipv6_address),
+    INDEX idx_updated_at (updated_at),
+    INDEX idx_last_seen_at (last_seen_at),
+    INDEX idx_agent_version (agent_version)
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci COMMENT='PC情報管理テーブル';
 
+-- pc_softwareテーブル作成
+CREATE TABLE IF NOT EXISTS pc_software (
+    id INT AUTO_INCREMENT PRIMARY KEY COMMENT 'DBが自動採番する主キー',
+    pc_info_id INT NOT NULL COMMENT 'pc_info.id',
+    name VARCHAR(255) NOT NULL COMMENT '製品名',
+    version VARCHAR(100) NOT NULL DEFAULT '' COMMENT 'バージョン',
+    publisher VARCHAR(255) COMMENT '発行元',
+    install_date DATE COMMENT 'インストール日',
+    created_at DATETIME NOT NULL COMMENT '初回登録日時',
+    updated_at DATETIME NOT NULL COMMENT '最終更新日時',
+    UNIQUE KEY uq_pc_software (pc_info_id, name, version),
+    INDEX idx_name (name),
+    CONSTRAINT fk_pc_software_pc_info FOREIGN KEY (pc_info_id) REFERENCES pc_info (id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci COMMENT='インストール済みソフトウェア一覧テーブル';
+
+-- pc_hotfixテーブル作成
+CREATE TABLE IF NOT EXISTS pc_hotfix (
+    id INT AUTO_INCREMENT PRIMARY KEY COMMENT 'DBが自動採番する主キー',
+    pc_info_id INT NOT NULL COMMENT 'pc_info.id',
+    hotfix_id VARCHAR(20) NOT NULL COMMENT '更新プログラムID（例: KB5034441）',
+    description VARCHAR(100) COMMENT '種類（例: Security Update）',
+    installed_on DATE COMMENT 'インストール日',
+    created_at DATETIME NOT NULL COMMENT '登録日時',
+    UNIQUE KEY uq_pc_hotfix (pc_info_id, hotfix_id),
+    INDEX idx_hotfix_id (hotfix_id),
+    CONSTRAINT fk_pc_hotfix_pc_info FOREIGN KEY (pc_info_id) REFERENCES pc_info (id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci COMMENT='インストール済み更新プログラムテーブル';
+
+-- pc_network_adapterテーブル作成
+CREATE TABLE IF NOT EXISTS pc_network_adapter (
+    id INT AUTO_INCREMENT PRIMARY KEY COMMENT 'DBが自動採番する主キー',
+    pc_info_id INT NOT NULL COMMENT 'pc_info.id',
+    name VARCHAR(255) NOT NULL COMMENT 'インターフェース名',
+    mac_address VARCHAR(17) COMMENT 'MACアドレス',
+    ipv4_addresses VARCHAR(255) NOT NULL DEFAULT '' COMMENT 'IPv4アドレス（カンマ区切り）',
+    ipv6_addresses VARCHAR(1024) NOT NULL DEFAULT '' COMMENT 'IPv6アドレス（カンマ区切り）',
+    network_type VARCHAR(20) COMMENT 'Wired / Wireless / VPN / Virtual / Cellular / Unknown',
+    is_up TINYINT(1) NOT NULL COMMENT 'UP状態か',
+    is_primary TINYINT(1) NOT NULL COMMENT '代表アダプタか',
+    updated_at DATETIME NOT NULL COMMENT '最終更新日時',
+    UNIQUE KEY uq_pc_network_adapter (pc_info_id, name),
+    INDEX idx_mac_address (mac_address),
+    CONSTRAINT fk_pc_network_adapter_pc_info FOREIGN KEY (pc_info_id) REFERENCES pc_info (id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci COMMENT='ネットワークアダプタ一覧テーブル';
+
+-- client_releasesテーブル作成（実行ファイルはサーバーの releases.storage_dir に保存）
+CREATE TABLE IF NOT EXISTS client_releases (
+    id INT AUTO_INCREMENT PRIMARY KEY COMMENT 'DBが自動採番する主キー',
+    channel VARCHAR(50) NOT NULL COMMENT '配布チャネル（例: stable）',
+    target VARCHAR(100) NOT NULL COMMENT '実行環境のターゲット（例: x86_64-pc-windows-msvc）',
+    version VARCHAR(50) NOT NULL COMMENT 'クライアントのバージョン',
+    sha256 CHAR(64) NOT NULL COMMENT '実行ファイルのSHA-256（保存先のファイル名）',
+    size BIGINT UNSIGNED NOT NULL COMMENT '実行ファイルのサイズ（バイト）',
+    signature VARCHAR(100) NOT NULL COMMENT '署名（Ed25519、base64url）',
+    created_at DATETIME NOT NULL COMMENT '登録日時',
+    UNIQUE KEY uq_client_releases (channel, target, version)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci COMMENT='配布用クライアントテーブル';
+
+-- pc_info_historyテーブル作成（pc_info の変更前の値）
+CREATE TABLE IF NOT EXISTS pc_info_history (
+    id INT AUTO_INCREMENT PRIMARY KEY COMMENT 'DBが自動採番する主キー',
+    pc_info_id INT NOT NULL COMMENT 'pc_info.id（統合された場合は統合先）',
+    uuid VARCHAR(100) NOT NULL COMMENT '変更前のUUID',
+    mac_address VARCHAR(17) COMMENT '変更前のMACアドレス',
+    network_type VARCHAR(20) COMMENT '変更前のネットワークタイプ',
+    user_name VARCHAR(50) COMMENT '変更前の使用者名',
+    ip_address VARCHAR(45) COMMENT '変更前のIPアドレス',
+    ipv6_address VARCHAR(45) COMMENT '変更前のグローバルIPv6アドレス',
+    os VARCHAR(100) COMMENT '変更前のOS名',
+    os_version VARCHAR(100) COMMENT '変更前のOSバージョン',
+    model_name VARCHAR(100) COMMENT '変更前のPC機種名',
+    agent_version VARCHAR(50) COMMENT '変更前のクライアントのバージョン',
+    reason VARCHAR(20) NOT NULL COMMENT '記録の理由（updated / merged）',
+    valid_from DATETIME NOT NULL COMMENT 'この値が有効になった日時',
+    recorded_at DATETIME NOT NULL COMMENT '記録日時（値が変更・統合された日時）',
+    INDEX idx_pc_info_id_recorded_at (pc_info_id, recorded_at),
+    CONSTRAINT fk_pc_info_history_pc_info FOREIGN KEY (pc_info_id) REFERENCES pc_info (id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci COMMENT='PC情報の変更履歴テーブル';
+
+-- api_keysテーブル作成（管理用APIの認証）
+CREATE TABLE IF NOT EXISTS api_keys (
+    id INT AUTO_INCREMENT PRIMARY KEY COMMENT 'DBが自動採番する主キー',
+    name VARCHAR(100) NOT NULL COMMENT '用途・利用者の名前',
+    key_hash CHAR(64) NOT NULL COMMENT 'API キーのSHA-256（キーそのものは保存しない）',
+    created_at DATETIME NOT NULL COMMENT '作成日時',
+    last_used_at DATETIME COMMENT '最終使用日時',
+    UNIQUE KEY uq_api_keys_key_hash (key_hash)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci COMMENT='管理用APIのキーテーブル';
+
 -- 初期構築完了確認
 SELECT 'Database initialization completed successfully!' AS status;
 SHOW TABLES;
 DESCRIBE pc_info;
+DESCRIBE pc_software;
+DESCRIBE pc_hotfix;
+DESCRIBE pc_network_adapter;
+DESCRIBE client_releases;
+DESCRIBE pc_info_history;
+DESCRIBE api_keys;
@@ -0,0 +1,23 @@
+-- PC情報収集システム マイグレーション 001
+-- インストール済みソフトウェア一覧テーブル（pc_software）の追加
+--
+-- 既存環境に対して一度だけ実行してください。
+-- 新規構築の場合は init.sql に含まれているため実行不要です。
+
+USE pc_inventory;
+
+CREATE TABLE IF NOT EXISTS pc_software (
+    id INT AUTO_INCREMENT PRIMARY KEY COMMENT 'DBが自動採番する主キー',
+    pc_info_id INT NOT NULL COMMENT 'pc_info.id',
+    name VARCHAR(255) NOT NULL COMMENT '製品名',
+    version VARCHAR(100) NOT NULL DEFAULT '' COMMENT 'バージョン',
+    publisher VARCHAR(255) COMMENT '発行元',
+    install_date DATE COMMENT 'インストール日',
+    created_at DATETIME NOT NULL COMMENT '初回登録日時',
+    updated_at DATETIME NOT NULL COMMENT '最終更新日時',
+    UNIQUE KEY uq_pc_software (pc_info_id, name, version),
+    INDEX idx_name (name),
+    CONSTRAINT fk_pc_software_pc_info FOREIGN KEY (pc_info_id) REFERENCES pc_info (id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci COMMENT='インストール済みソフトウェア一覧テーブル';
+
+DESCRIBE pc_software;
@@ -0,0 +1,26 @@
+-- PC情報収集システム マイグレーション 002
+-- Windows Update適用状況（ビルドリビジョン・更新プログラム一覧）の追加
+--
+-- 既存環境に対して一度だけ実行してください。
+-- 新規構築の場合は init.sql に含まれているため実行不要です。
+
+USE pc_inventory;
+
+ALTER TABLE pc_info
+    ADD COLUMN os_build_revision INT UNSIGNED COMMENT 'OSビルドリビジョン（UBR）' AFTER os_version,
+    ADD COLUMN hotfixes_updated_at DATETIME COMMENT '更新プログラム一覧の最終報告日時' AFTER os_build_revision;
+
+CREATE TABLE IF NOT EXISTS pc_hotfix (
+    id INT AUTO_INCREMENT PRIMARY KEY COMMENT 'DBが自動採番する主キー',
+    pc_info_id INT NOT NULL COMMENT 'pc_info.id',
+    hotfix_id VARCHAR(20) NOT NULL COMMENT '更新プログラムID（例: KB5034441）',
+    description VARCHAR(100) COMMENT '種類（例: Security Update）',
+    installed_on DATE COMMENT 'インストール日',
+    created_at DATETIME NOT NULL COMMENT '登録日時',
+    UNIQUE KEY uq_pc_hotfix (pc_info_id, hotfix_id),
+    INDEX idx_hotfix_id (hotfix_id),
+    CONSTRAINT fk_pc_hotfix_pc_info FOREIGN KEY (pc_info_id) REFERENCES pc_info (id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci COMMENT='インストール済み更新プログラムテーブル';
+
+DESCRIBE pc_info;
+DESCRIBE pc_hotfix;
@@ -0,0 +1,25 @@
+-- PC情報収集システム マイグレーション 003
+-- ネットワークアダプタ一覧テーブル（pc_network_adapter）の追加
+--
+-- 既存環境に対して一度だけ実行してください。
+-- 新規構築の場合は init.sql に含まれているため実行不要です。
+
+USE pc_inventory;
+
+CREATE TABLE IF NOT EXISTS pc_network_adapter (
+    id INT AUTO_INCREMENT PRIMARY KEY COMMENT 'DBが自動採番する主キー',
+    pc_info_id INT NOT NULL COMMENT 'pc_info.id',
+    name VARCHAR(255) NOT NULL COMMENT 'インターフェース名',
+    mac_address VARCHAR(17) COMMENT 'MACアドレス',
+    ipv4_addresses VARCHAR(255) NOT NULL DEFAULT '' COMMENT 'IPv4アドレス（カンマ区切り）',
+    ipv6_addresses VARCHAR(1024) NOT NULL DEFAULT '' COMMENT 'IPv6アドレス（カンマ区切り）',
+    network_type VARCHAR(20) COMMENT 'ネットワークタイプ',
+    is_up TINYINT(1) NOT NULL COMMENT 'UP状態か',
+    is_primary TINYINT(1) NOT NULL COMMENT '代表アダプタか',
+    updated_at DATETIME NOT NULL COMMENT '最終更新日時',
+    UNIQUE KEY uq_pc_network_adapter (pc_info_id, name),
+    INDEX idx_mac_address (mac_address),
+    CONSTRAINT fk_pc_network_adapter_pc_info FOREIGN KEY (pc_info_id) REFERENCES pc_info (id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci COMMENT='ネットワークアダプタ一覧テーブル';
+
+DESCRIBE pc_network_adapter;
@@ -0,0 +1,15 @@
+-- PC情報収集システム マイグレーション 004
+-- IPv6対応（pc_info.ip_address の拡張、ipv6_address 列の追加）
+--
+-- 既存環境に対して一度だけ実行してください。
+-- 新規構築の場合は init.sql に含まれているため実行不要です。
+
+USE pc_inventory;
+
+ALTER TABLE pc_info
+    MODIFY COLUMN ip_address VARCHAR(45) COMMENT 'IPアドレス（IPv4またはIPv6）',
+    ADD COLUMN ipv6_address VARCHAR(45) COMMENT 'グローバルIPv6アドレス' AFTER ip_address,
+    ADD INDEX idx_ip_address (ip_address),
+    ADD INDEX idx_ipv6_address (ipv6_address);
+
+DESCRIBE pc_info;
@@ -0,0 +1,15 @@
+-- PC情報収集システム マイグレーション 005
+-- ネットワークタイプの正規化（"Ethernet" / "Wi-Fi" → "Wired" / "Wireless"）
+--
+-- 既存環境に対して一度だけ実行してください。
+-- 新規構築の場合は実行不要です。
+
+USE pc_inventory;
+
+UPDATE pc_info SET network_type = 'Wired' WHERE network_type = 'Ethernet';
+UPDATE pc_info SET network_type = 'Wireless' WHERE network_type = 'Wi-Fi';
+
+UPDATE pc_network_adapter SET network_type = 'Wired' WHERE network_type = 'Ethernet';
+UPDATE pc_network_adapter SET network_type = 'Wireless' WHERE network_type = 'Wi-Fi';
+
+SELECT network_type, COUNT(*) AS count FROM pc_info GROUP BY network_type;
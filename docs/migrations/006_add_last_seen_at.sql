@@ -0,0 +1,16 @@
+-- PC情報収集システム マイグレーション 006
+-- ハートビート対応（pc_info.last_seen_at 列の追加）
+--
+-- 既存環境に対して一度だけ実行してください。
+-- 新規構築の場合は init.sql に含まれているため実行不要です。
+
+USE pc_inventory;
+
+ALTER TABLE pc_info
+    ADD COLUMN last_seen_at DATETIME COMMENT '最終接触日時（PC情報の送信・ハートビート受信時に更新）' AFTER updated_at,
+    ADD INDEX idx_last_seen_at (last_seen_at);
+
+-- 既存レコードは最終更新日時を最終接触日時とみなす
+UPDATE pc_info SET last_seen_at = updated_at WHERE last_seen_at IS NULL;
+
+DESCRIBE pc_info;
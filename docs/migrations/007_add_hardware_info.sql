@@ -0,0 +1,14 @@
+-- PC情報収集システム マイグレーション 007
+-- APIバージョン v2 対応（pc_info へのハードウェア情報列の追加）
+--
+-- 既存環境に対して一度だけ実行してください。
+-- 新規構築の場合は init.sql に含まれているため実行不要です。
+
+USE pc_inventory;
+
+ALTER TABLE pc_info
+    ADD COLUMN manufacturer VARCHAR(255) COMMENT 'メーカー名（APIバージョン v2 以降）' AFTER model_name,
+    ADD COLUMN total_memory_bytes BIGINT UNSIGNED COMMENT '物理メモリの合計（バイト、APIバージョン v2 以降）' AFTER manufacturer,
+    ADD COLUMN logical_processors INT UNSIGNED COMMENT '論理プロセッサ数（APIバージョン v2 以降）' AFTER total_memory_bytes;
+
+DESCRIBE pc_info;
@@ -0,0 +1,15 @@
+-- PC情報収集システム マイグレーション 008
+-- クライアントのバージョン・ビルド・収集項目の記録（pc_info への列の追加）
+--
+-- 既存環境に対して一度だけ実行してください。
+-- 新規構築の場合は init.sql に含まれているため実行不要です。
+
+USE pc_inventory;
+
+ALTER TABLE pc_info
+    ADD COLUMN agent_version VARCHAR(50) COMMENT 'クライアントのバージョン（APIバージョン v2 以降）' AFTER logical_processors,
+    ADD COLUMN agent_build VARCHAR(64) COMMENT 'クライアントのビルドハッシュ' AFTER agent_version,
+    ADD COLUMN agent_capabilities VARCHAR(255) COMMENT 'クライアントが収集できる項目（カンマ区切り）' AFTER agent_build,
+    ADD INDEX idx_agent_version (agent_version);
+
+DESCRIBE pc_info;
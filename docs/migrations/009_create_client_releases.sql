@@ -0,0 +1,21 @@
+-- PC情報収集システム マイグレーション 009
+-- 配布用クライアントテーブル（client_releases）の追加
+--
+-- 既存環境に対して一度だけ実行してください。
+-- 新規構築の場合は init.sql に含まれているため実行不要です。
+
+USE pc_inventory;
+
+CREATE TABLE IF NOT EXISTS client_releases (
+    id INT AUTO_INCREMENT PRIMARY KEY COMMENT 'DBが自動採番する主キー',
+    channel VARCHAR(50) NOT NULL COMMENT '配布チャネル（例: stable）',
+    target VARCHAR(100) NOT NULL COMMENT '実行環境のターゲット（例: x86_64-pc-windows-msvc）',
+    version VARCHAR(50) NOT NULL COMMENT 'クライアントのバージョン',
+    sha256 CHAR(64) NOT NULL COMMENT '実行ファイルのSHA-256（保存先のファイル名）',
+    size BIGINT UNSIGNED NOT NULL COMMENT '実行ファイルのサイズ（バイト）',
+    signature VARCHAR(100) NOT NULL COMMENT '署名（Ed25519、base64url）',
+    created_at DATETIME NOT NULL COMMENT '登録日時',
+    UNIQUE KEY uq_client_releases (channel, target, version)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci COMMENT='配布用クライアントテーブル';
+
+DESCRIBE client_releases;
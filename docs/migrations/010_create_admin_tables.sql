@@ -0,0 +1,39 @@
+-- PC情報収集システム マイグレーション 010
+-- 管理用API（pc-inventory-admin）対応（pc_info_history・api_keys テーブルの追加）
+--
+-- 既存環境に対して一度だけ実行してください。
+-- 新規構築の場合は init.sql に含まれているため実行不要です。
+
+USE pc_inventory;
+
+CREATE TABLE IF NOT EXISTS pc_info_history (
+    id INT AUTO_INCREMENT PRIMARY KEY COMMENT 'DBが自動採番する主キー',
+    pc_info_id INT NOT NULL COMMENT 'pc_info.id（統合された場合は統合先）',
+    uuid VARCHAR(100) NOT NULL COMMENT '変更前のUUID',
+    mac_address VARCHAR(17) COMMENT '変更前のMACアドレス',
+    network_type VARCHAR(20) COMMENT '変更前のネットワークタイプ',
+    user_name VARCHAR(50) COMMENT '変更前の使用者名',
+    ip_address VARCHAR(45) COMMENT '変更前のIPアドレス',
+    ipv6_address VARCHAR(45) COMMENT '変更前のグローバルIPv6アドレス',
+    os VARCHAR(100) COMMENT '変更前のOS名',
+    os_version VARCHAR(100) COMMENT '変更前のOSバージョン',
+    model_name VARCHAR(100) COMMENT '変更前のPC機種名',
+    agent_version VARCHAR(50) COMMENT '変更前のクライアントのバージョン',
+    reason VARCHAR(20) NOT NULL COMMENT '記録の理由（updated / merged）',
+    valid_from DATETIME NOT NULL COMMENT 'この値が有効になった日時',
+    recorded_at DATETIME NOT NULL COMMENT '記録日時（値が変更・統合された日時）',
+    INDEX idx_pc_info_id_recorded_at (pc_info_id, recorded_at),
+    CONSTRAINT fk_pc_info_history_pc_info FOREIGN KEY (pc_info_id) REFERENCES pc_info (id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci COMMENT='PC情報の変更履歴テーブル';
+
+CREATE TABLE IF NOT EXISTS api_keys (
+    id INT AUTO_INCREMENT PRIMARY KEY COMMENT 'DBが自動採番する主キー',
+    name VARCHAR(100) NOT NULL COMMENT '用途・利用者の名前',
+    key_hash CHAR(64) NOT NULL COMMENT 'API キーのSHA-256（キーそのものは保存しない）',
+    created_at DATETIME NOT NULL COMMENT '作成日時',
+    last_used_at DATETIME COMMENT '最終使用日時',
+    UNIQUE KEY uq_api_keys_key_hash (key_hash)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci COMMENT='管理用APIのキーテーブル';
+
+DESCRIBE pc_info_history;
+DESCRIBE api_keys;
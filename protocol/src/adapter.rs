@@ -0,0 +1,235 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use crate::error::{require, ValidationError};
+use crate::network_type::NetworkType;
+
+/// インターフェース名の最大長（pc_network_adapter.name）
+pub const MAX_ADAPTER_NAME_LENGTH: usize = 255;
+
+/// IPv4アドレス（カンマ区切り）の最大長（pc_network_adapter.ipv4_addresses）
+pub const MAX_IPV4_ADDRESSES_LENGTH: usize = 255;
+
+/// IPv6アドレス（カンマ区切り）の最大長（pc_network_adapter.ipv6_addresses）
+pub const MAX_IPV6_ADDRESSES_LENGTH: usize = 1024;
+
+/// MACアドレスが "00:11:22:33:44:55" 形式（区切りは ":" または "-"）か
+///
+/// pc_network_adapter.mac_address（VARCHAR(17)）に保存できる6バイトのアドレスのみ有効とします。
+pub fn is_valid_mac_address(mac: &str) -> bool {
+    let octets: Vec<&str> = mac.split([':', '-']).collect();
+    mac.len() == 17
+        && octets.len() == 6
+        && octets.iter().all(|octet| octet.len() == 2 && octet.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// ネットワークアダプタ情報（リクエストの1要素）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdapterItem {
+    /// インターフェース名（例: "Ethernet", "eth0"）
+    pub name: String,
+    /// MACアドレス（存在しないアダプタは None）
+    pub mac_address: Option<String>,
+    #[serde(default)]
+    pub ipv4_addresses: Vec<String>,
+    #[serde(default)]
+    pub ipv6_addresses: Vec<String>,
+    pub network_type: NetworkType,
+    pub is_up: bool,
+    /// 代表アダプタか（PC情報の ip_address / mac_address の取得元）
+    pub is_primary: bool,
+}
+
+/// ネットワークアダプタ一覧リクエスト（POST /api/pc-adapters）
+///
+/// adapters は完全な一覧として扱い、既存の一覧を置き換える。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcAdapterRequest {
+    pub uuid: String,
+    #[serde(default)]
+    pub adapters: Vec<AdapterItem>,
+}
+
+/// ネットワークアダプタ一覧レスポンス（成功時）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcAdapterResponse {
+    pub status: String,
+    pub id: i32,
+    pub adapters: u64,
+}
+
+impl AdapterItem {
+    /// インターフェース名・MACアドレス・IPアドレスを検証
+    ///
+    /// # エラー
+    /// 名前が空または長すぎる場合、MACアドレスの形式が不正な場合、IPアドレスとして解釈できない場合、
+    /// IPアドレスをカンマ区切りにした長さが保存できる長さを超える場合
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.name.trim().is_empty() || self.name.chars().count() > MAX_ADAPTER_NAME_LENGTH {
+            return Err(ValidationError::InvalidValue(format!("Invalid adapter name: '{}'", self.name)));
+        }
+
+        if let Some(mac) = &self.mac_address {
+            if !is_valid_mac_address(mac) {
+                return Err(ValidationError::InvalidValue(format!(
+                    "Invalid MAC address on adapter '{}': {}",
+                    self.name, mac
+                )));
+            }
+        }
+
+        for ip in &self.ipv4_addresses {
+            if ip.parse::<Ipv4Addr>().is_err() {
+                return Err(ValidationError::InvalidValue(format!(
+                    "Invalid IPv4 address on adapter '{}': {}",
+                    self.name, ip
+                )));
+            }
+        }
+
+        for ip in &self.ipv6_addresses {
+            if ip.parse::<Ipv6Addr>().is_err() {
+                return Err(ValidationError::InvalidValue(format!(
+                    "Invalid IPv6 address on adapter '{}': {}",
+                    self.name, ip
+                )));
+            }
+        }
+
+        if self.ipv4_addresses.join(",").len() > MAX_IPV4_ADDRESSES_LENGTH {
+            return Err(ValidationError::InvalidValue(format!(
+                "IPv4 addresses on adapter '{}' exceed {} characters",
+                self.name, MAX_IPV4_ADDRESSES_LENGTH
+            )));
+        }
+
+        if self.ipv6_addresses.join(",").len() > MAX_IPV6_ADDRESSES_LENGTH {
+            return Err(ValidationError::InvalidValue(format!(
+                "IPv6 addresses on adapter '{}' exceed {} characters",
+                self.name, MAX_IPV6_ADDRESSES_LENGTH
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl PcAdapterRequest {
+    /// UUID・各アダプタ・インターフェース名の重複・代表アダプタの数を検証
+    ///
+    /// インターフェース名はデータベースの照合順序と同じく、大文字・小文字と末尾の空白を区別せずに比較します。
+    ///
+    /// # エラー
+    /// UUIDが空の場合、いずれかのアダプタが不正な場合、インターフェース名が重複する場合、代表アダプタが複数ある場合
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        require("uuid", &self.uuid)?;
+
+        let mut names = HashSet::new();
+        for adapter in &self.adapters {
+            adapter.validate()?;
+            if !names.insert(adapter.name.trim_end().to_lowercase()) {
+                return Err(ValidationError::InvalidValue(format!(
+                    "Duplicate adapter name: '{}'",
+                    adapter.name
+                )));
+            }
+        }
+
+        if self.adapters.iter().filter(|a| a.is_primary).count() > 1 {
+            return Err(ValidationError::InvalidValue(
+                "Only one adapter can be marked as primary".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl PcAdapterResponse {
+    /// 同期成功時のレスポンスを生成
+    pub fn synced(id: i32, adapters: u64) -> Self {
+        Self {
+            status: "success".to_string(),
+            id,
+            adapters,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adapter(name: &str, is_primary: bool) -> AdapterItem {
+        AdapterItem {
+            name: name.to_string(),
+            mac_address: Some("00:11:22:33:44:55".to_string()),
+            ipv4_addresses: vec!["192.168.1.100".to_string()],
+            ipv6_addresses: vec!["2001:db8::10".to_string()],
+            network_type: NetworkType::Wired,
+            is_up: true,
+            is_primary,
+        }
+    }
+
+    #[test]
+    fn test_adapter_request_round_trip() {
+        let json = r#"{"uuid":"test-uuid","adapters":[{"name":"Ethernet","mac_address":"00:11:22:33:44:55","ipv4_addresses":["192.168.1.100"],"ipv6_addresses":["2001:db8::10"],"network_type":"Wired","is_up":true,"is_primary":true}]}"#;
+        let req: PcAdapterRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.adapters, vec![adapter("Ethernet", true)]);
+        assert_eq!(serde_json::to_string(&req).unwrap(), json);
+
+        let json = r#"{"status":"success","id":42,"adapters":3}"#;
+        let response: PcAdapterResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response, PcAdapterResponse::synced(42, 3));
+        assert_eq!(serde_json::to_string(&response).unwrap(), json);
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut req = PcAdapterRequest {
+            uuid: "test-uuid".to_string(),
+            adapters: vec![adapter("Ethernet", true), adapter("Wi-Fi", false)],
+        };
+        assert!(req.validate().is_ok());
+
+        req.adapters[1].is_primary = true;
+        assert!(req.validate().is_err());
+
+        req.adapters[1].is_primary = false;
+        req.adapters[1].ipv4_addresses = vec!["2001:db8::10".to_string()];
+        assert!(req.validate().is_err());
+
+        req.adapters[1] = adapter("ethernet ", false);
+        assert_eq!(
+            req.validate(),
+            Err(ValidationError::InvalidValue("Duplicate adapter name: 'ethernet '".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_validate_lengths() {
+        let mut item = adapter("Ethernet", true);
+        item.ipv4_addresses = vec!["192.168.100.100".to_string(); 16];
+        assert!(item.validate().is_ok());
+        item.ipv4_addresses.push("192.168.100.100".to_string());
+        assert!(item.validate().is_err());
+
+        let mut item = adapter("Ethernet", true);
+        item.ipv6_addresses = vec!["2001:db8:1234:5678:9abc:def0:1234:5678".to_string(); 30];
+        assert!(item.validate().is_err());
+
+        let mut item = adapter("ib0", true);
+        item.mac_address = Some("80:00:02:08:fe:80:00:00:00:00:00:00".to_string());
+        assert!(item.validate().is_err());
+    }
+
+    #[test]
+    fn test_is_valid_mac_address() {
+        assert!(is_valid_mac_address("00:11:22:33:44:55"));
+        assert!(is_valid_mac_address("AA-BB-CC-DD-EE-FF"));
+        assert!(!is_valid_mac_address("00:11:22:33:44"));
+        assert!(!is_valid_mac_address("00:11:22:33:44:5g"));
+        assert!(!is_valid_mac_address("0:11:22:33:44:555"));
+    }
+}
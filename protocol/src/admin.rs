@@ -0,0 +1,307 @@
+//! 管理用API（/api/admin、pc-inventory-admin から使用）
+//!
+//! 管理用APIは API キー（`Authorization: Bearer <キー>`）で認証します。
+//! クライアントからの送信とは異なり、バージョン別のパスはありません。
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::error::{require, ValidationError};
+
+/// API キーの名前の最大長（api_keys.name）
+pub const MAX_API_KEY_NAME_LENGTH: usize = 100;
+
+/// 一覧・検索で返すPCの最大件数
+pub const MAX_PC_LIST_LIMIT: u32 = 10_000;
+
+/// PC情報（pc_info の1行）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcRecord {
+    pub id: i32,
+    pub uuid: String,
+    pub mac_address: Option<String>,
+    pub network_type: Option<String>,
+    pub user_name: Option<String>,
+    pub ip_address: Option<String>,
+    pub ipv6_address: Option<String>,
+    pub os: Option<String>,
+    pub os_version: Option<String>,
+    pub model_name: Option<String>,
+    pub manufacturer: Option<String>,
+    /// 物理メモリの合計（バイト）
+    pub total_memory_bytes: Option<u64>,
+    pub logical_processors: Option<u32>,
+    /// クライアントのバージョン
+    pub agent_version: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// 最終更新日時（いずれかの項目の値が変化した日時）
+    pub updated_at: DateTime<Utc>,
+    /// 最終接触日時（PC情報の送信・ハートビートを受信した日時）
+    pub last_seen_at: Option<DateTime<Utc>>,
+}
+
+/// PC情報の変更履歴（pc_info_history の1行）
+///
+/// 変更される前の値を、その値が有効だった期間（valid_from から recorded_at まで）とともに記録します。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcHistoryEntry {
+    pub uuid: String,
+    pub mac_address: Option<String>,
+    pub network_type: Option<String>,
+    pub user_name: Option<String>,
+    pub ip_address: Option<String>,
+    pub ipv6_address: Option<String>,
+    pub os: Option<String>,
+    pub os_version: Option<String>,
+    pub model_name: Option<String>,
+    pub agent_version: Option<String>,
+    /// 記録の理由
+    pub reason: HistoryReason,
+    /// この値が有効になった日時
+    pub valid_from: DateTime<Utc>,
+    /// 記録した日時（値が変更・統合された日時）
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// 変更履歴を記録した理由
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryReason {
+    /// クライアントから送信された内容で更新された
+    Updated,
+    /// 別のレコードが統合された（統合元の最後の値）
+    Merged,
+}
+
+impl HistoryReason {
+    /// データベースに保存する値（"updated", "merged"）
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HistoryReason::Updated => "updated",
+            HistoryReason::Merged => "merged",
+        }
+    }
+}
+
+/// PC情報の一覧・検索（GET /api/admin/pcs のクエリパラメータ）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcListQuery {
+    /// UUID・使用者名・機種名・MACアドレス・IPアドレスの部分一致（None の場合はすべて）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    /// 最大件数（None の場合は MAX_PC_LIST_LIMIT）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    /// このIDより後のPC情報のみ（MAX_PC_LIST_LIMIT を超える一覧を取得する場合に、前回の最後のIDを指定）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after_id: Option<i32>,
+}
+
+impl PcListQuery {
+    /// 検索文字列（前後の空白を除き、空の場合は None）
+    pub fn search_text(&self) -> Option<&str> {
+        self.search.as_deref().map(str::trim).filter(|s| !s.is_empty())
+    }
+
+    /// 最大件数（MAX_PC_LIST_LIMIT を超える場合は MAX_PC_LIST_LIMIT）
+    pub fn effective_limit(&self) -> u32 {
+        self.limit.unwrap_or(MAX_PC_LIST_LIMIT).min(MAX_PC_LIST_LIMIT)
+    }
+}
+
+/// PC情報の一覧のレスポンス
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcListResponse {
+    pub status: String,
+    pub count: usize,
+    /// PC情報（ID順）
+    pub items: Vec<PcRecord>,
+}
+
+impl PcListResponse {
+    /// 一覧からレスポンスを生成
+    pub fn new(items: Vec<PcRecord>) -> Self {
+        Self {
+            status: "success".to_string(),
+            count: items.len(),
+            items,
+        }
+    }
+}
+
+/// PC情報と変更履歴のレスポンス（GET /api/admin/pcs/{id}）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcDetailResponse {
+    pub status: String,
+    pub pc: PcRecord,
+    /// 変更履歴（新しい順）
+    pub history: Vec<PcHistoryEntry>,
+}
+
+impl PcDetailResponse {
+    /// PC情報と変更履歴からレスポンスを生成
+    pub fn new(pc: PcRecord, history: Vec<PcHistoryEntry>) -> Self {
+        Self {
+            status: "success".to_string(),
+            pc,
+            history,
+        }
+    }
+}
+
+/// PC情報の統合（POST /api/admin/pcs/{id}/merge）
+///
+/// マザーボードの交換などでUUIDが変わり、同じPCが別のレコードとして登録された場合に使用します。
+/// 統合元の変更履歴と最後の値を統合先（パスの id）の履歴に移し、統合元を削除します。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcMergeRequest {
+    /// 統合元（削除される）PCのID
+    pub source_id: i32,
+}
+
+/// PC情報の統合のレスポンス
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcMergeResponse {
+    pub status: String,
+    /// 統合先のPCのID
+    pub id: i32,
+    /// 削除された統合元のPCのID
+    pub merged_id: i32,
+    /// 統合先に移した変更履歴の件数（統合元の最後の値を含む）
+    pub history_moved: u64,
+}
+
+impl PcMergeResponse {
+    /// 統合の結果からレスポンスを生成
+    pub fn new(id: i32, merged_id: i32, history_moved: u64) -> Self {
+        Self {
+            status: "merged".to_string(),
+            id,
+            merged_id,
+            history_moved,
+        }
+    }
+}
+
+/// 削除のレスポンス（PC情報・API キー）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeleteResponse {
+    pub status: String,
+    /// 削除したレコードのID
+    pub id: i32,
+}
+
+impl DeleteResponse {
+    /// 削除したレコードのIDからレスポンスを生成
+    pub fn new(id: i32) -> Self {
+        Self {
+            status: "deleted".to_string(),
+            id,
+        }
+    }
+}
+
+/// API キーの情報（キーそのものは作成時にのみ返す）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiKeyInfo {
+    pub id: i32,
+    /// 用途・利用者の名前
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    /// 最終使用日時（未使用の場合は None）
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+/// API キーの一覧のレスポンス（GET /api/admin/api-keys）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiKeyListResponse {
+    pub status: String,
+    /// API キー（ID順）
+    pub items: Vec<ApiKeyInfo>,
+}
+
+impl ApiKeyListResponse {
+    /// 一覧からレスポンスを生成
+    pub fn new(items: Vec<ApiKeyInfo>) -> Self {
+        Self {
+            status: "success".to_string(),
+            items,
+        }
+    }
+}
+
+/// API キーの作成（POST /api/admin/api-keys）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreateApiKeyRequest {
+    /// 用途・利用者の名前
+    pub name: String,
+}
+
+impl CreateApiKeyRequest {
+    /// 名前を検証
+    ///
+    /// # エラー
+    /// 名前が空の場合、または長すぎる場合
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        require("name", &self.name)?;
+        if self.name.trim().chars().count() > MAX_API_KEY_NAME_LENGTH {
+            return Err(ValidationError::InvalidValue(format!(
+                "API key name exceeds {} characters",
+                MAX_API_KEY_NAME_LENGTH
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// API キーの作成のレスポンス
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreateApiKeyResponse {
+    pub status: String,
+    pub key: ApiKeyInfo,
+    /// API キー（サーバーにはハッシュのみ保存するため、再表示できません）
+    pub secret: String,
+}
+
+impl CreateApiKeyResponse {
+    /// 作成した API キーからレスポンスを生成
+    pub fn new(key: ApiKeyInfo, secret: String) -> Self {
+        Self {
+            status: "created".to_string(),
+            key,
+            secret,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pc_list_query() {
+        let query = PcListQuery { search: Some("  ".to_string()), limit: Some(100_000), after_id: None };
+        assert_eq!(query.search_text(), None);
+        assert_eq!(query.effective_limit(), MAX_PC_LIST_LIMIT);
+
+        let query = PcListQuery { search: Some(" tanaka ".to_string()), limit: None, after_id: Some(42) };
+        assert_eq!(query.search_text(), Some("tanaka"));
+        assert_eq!(query.effective_limit(), MAX_PC_LIST_LIMIT);
+    }
+
+    #[test]
+    fn test_create_api_key_request_validate() {
+        assert!(CreateApiKeyRequest { name: "backup script".to_string() }.validate().is_ok());
+        assert_eq!(
+            CreateApiKeyRequest { name: " ".to_string() }.validate(),
+            Err(ValidationError::MissingField("name"))
+        );
+        assert!(CreateApiKeyRequest { name: "x".repeat(MAX_API_KEY_NAME_LENGTH + 1) }.validate().is_err());
+    }
+
+    #[test]
+    fn test_history_reason_round_trip() {
+        for reason in [HistoryReason::Updated, HistoryReason::Merged] {
+            assert_eq!(serde_json::to_string(&reason).unwrap(), format!("\"{}\"", reason.as_str()));
+        }
+    }
+}
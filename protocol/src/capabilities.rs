@@ -0,0 +1,149 @@
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Deserializer, Serialize};
+use crate::version::ApiVersion;
+
+/// PC情報以外に送信できる任意の項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Section {
+    /// ハードウェア情報（PcInfoRequest.hardware、v2以降）
+    Hardware,
+    /// ネットワークアダプタ一覧（POST pc-adapters）
+    Adapters,
+    /// ソフトウェア一覧（POST pc-software）
+    Software,
+    /// Windows Update適用状況（POST pc-updates）
+    Updates,
+}
+
+impl Section {
+    /// すべての項目
+    pub const ALL: [Section; 4] = [Section::Hardware, Section::Adapters, Section::Software, Section::Updates];
+
+    /// 項目名（"hardware", "adapters", "software", "updates"）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Section::Hardware => "hardware",
+            Section::Adapters => "adapters",
+            Section::Software => "software",
+            Section::Updates => "updates",
+        }
+    }
+}
+
+/// サーバーの対応状況レスポンス（GET /api/capabilities）
+///
+/// クライアントはこのレスポンスから使用するAPIバージョンと送信する項目を決定します。
+/// 新しいサーバーが返す未知のバージョン・項目は読み飛ばすため、古いクライアントでも解釈できます。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilitiesResponse {
+    pub status: String,
+    /// 受け付けるAPIバージョン
+    #[serde(deserialize_with = "known_only")]
+    pub api_versions: Vec<ApiVersion>,
+    /// 受け付ける任意の項目
+    #[serde(deserialize_with = "known_only")]
+    pub sections: Vec<Section>,
+}
+
+/// 配列の要素のうち、解釈できるものだけを取り出す
+pub(crate) fn known_only<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Element<T> {
+        Known(T),
+        Unknown(IgnoredAny),
+    }
+
+    let elements = Vec::<Element<T>>::deserialize(deserializer)?;
+    Ok(elements
+        .into_iter()
+        .filter_map(|e| match e {
+            Element::Known(value) => Some(value),
+            Element::Unknown(_) => None,
+        })
+        .collect())
+}
+
+impl CapabilitiesResponse {
+    /// 対応状況からレスポンスを生成
+    pub fn new(api_versions: Vec<ApiVersion>, sections: Vec<Section>) -> Self {
+        Self {
+            status: "success".to_string(),
+            api_versions,
+            sections,
+        }
+    }
+
+    /// 現在のバージョンのサーバーの対応状況（すべてのバージョン・項目を受け付ける）
+    pub fn current() -> Self {
+        Self::new(ApiVersion::ALL.to_vec(), Section::ALL.to_vec())
+    }
+
+    /// GET /api/capabilities が存在しない（バージョン導入前の）サーバーの対応状況
+    ///
+    /// バージョンを含まない従来のパスのみを受け付け、
+    /// ハードウェア情報以外の項目（アダプタ・ソフトウェア・更新プログラム）に対応しているものとみなします。
+    pub fn legacy() -> Self {
+        Self::new(Vec::new(), vec![Section::Adapters, Section::Software, Section::Updates])
+    }
+
+    /// 指定した項目を受け付けるか
+    pub fn accepts(&self, section: Section) -> bool {
+        self.sections.contains(&section)
+    }
+
+    /// 使用するAPIバージョンを決定
+    ///
+    /// サーバーとクライアントの両方が対応しているバージョンのうち最新のものを返します。
+    ///
+    /// # 戻り値
+    /// * `Some(ApiVersion)` - 使用するバージョン
+    /// * `None` - 共通のバージョンが無い場合（従来のパスを使用する）
+    pub fn negotiate(&self) -> Option<ApiVersion> {
+        // 未知のバージョンは読み込み時に取り除かれている
+        self.api_versions.iter().max().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_round_trip() {
+        let json = r#"{"status":"success","api_versions":["v1","v2"],"sections":["hardware","adapters","software","updates"]}"#;
+        let response: CapabilitiesResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response, CapabilitiesResponse::current());
+        assert_eq!(serde_json::to_string(&response).unwrap(), json);
+
+        for section in Section::ALL {
+            assert_eq!(serde_json::to_string(&section).unwrap(), format!("\"{}\"", section.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(CapabilitiesResponse::current().negotiate(), Some(ApiVersion::V2));
+        assert_eq!(CapabilitiesResponse::legacy().negotiate(), None);
+
+        let v1_only = CapabilitiesResponse::new(vec![ApiVersion::V1], vec![Section::Adapters]);
+        assert_eq!(v1_only.negotiate(), Some(ApiVersion::V1));
+        assert!(v1_only.accepts(Section::Adapters));
+        assert!(!v1_only.accepts(Section::Hardware));
+    }
+
+    #[test]
+    fn test_unknown_values_are_ignored() {
+        // 新しいサーバーが返す未知のバージョン・項目は読み飛ばす
+        let json = r#"{"status":"success","api_versions":["v1","v2","v3"],"sections":["hardware","firmware"]}"#;
+        let response: CapabilitiesResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.api_versions, ApiVersion::ALL);
+        assert_eq!(response.sections, vec![Section::Hardware]);
+        assert_eq!(response.negotiate(), Some(ApiVersion::V2));
+    }
+}
@@ -0,0 +1,88 @@
+use std::cmp::Ordering;
+
+/// バージョン文字列を比較
+///
+/// ".", "-", "_", "+", 空白 で区切り、さらに数字と非数字の境界で分割した要素を先頭から比較する。
+/// 数字の要素は数値として、それ以外は大文字小文字を区別せず文字列として比較し、
+/// 数字の要素は文字列の要素より大きいものとする。
+/// 不足する要素は "0" とみなすため、"1.0" と "1.0.0" は等しい。
+///
+/// # 例
+/// * "120.0.6099.130" > "99.0.4844.84"
+/// * "1.10" > "1.9"
+/// * "2.0" > "2.0-beta"
+pub fn compare_versions(left: &str, right: &str) -> Ordering {
+    let left = split_version(left);
+    let right = split_version(right);
+
+    for i in 0..left.len().max(right.len()) {
+        let l = left.get(i).map(String::as_str).unwrap_or("0");
+        let r = right.get(i).map(String::as_str).unwrap_or("0");
+
+        let ordering = compare_segment(l, r);
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    Ordering::Equal
+}
+
+/// バージョン文字列を比較用の要素に分割
+fn split_version(version: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+
+    for part in version
+        .trim()
+        .split(|c: char| matches!(c, '.' | '-' | '_' | '+') || c.is_whitespace())
+        .filter(|p| !p.is_empty())
+    {
+        let mut current = String::new();
+        for c in part.chars() {
+            if !current.is_empty() && current.ends_with(|p: char| p.is_ascii_digit()) != c.is_ascii_digit() {
+                segments.push(std::mem::take(&mut current));
+            }
+            current.push(c);
+        }
+        segments.push(current);
+    }
+
+    segments
+}
+
+/// 要素1つを比較
+fn compare_segment(left: &str, right: &str) -> Ordering {
+    let left_numeric = left.chars().all(|c| c.is_ascii_digit());
+    let right_numeric = right.chars().all(|c| c.is_ascii_digit());
+
+    match (left_numeric, right_numeric) {
+        (true, true) => {
+            let l = left.trim_start_matches('0');
+            let r = right.trim_start_matches('0');
+            l.len().cmp(&r.len()).then_with(|| l.cmp(r))
+        }
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => left.to_lowercase().cmp(&right.to_lowercase()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_numeric_segments() {
+        assert_eq!(compare_versions("1.10", "1.9"), Ordering::Greater);
+        assert_eq!(compare_versions("120.0.6099.130", "99.0.4844.84"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0", "1.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("01.2", "1.2"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_compare_mixed_segments() {
+        assert_eq!(compare_versions("2.0", "2.0-beta"), Ordering::Greater);
+        assert_eq!(compare_versions("5.1-6ubuntu1", "5.1-6ubuntu2"), Ordering::Less);
+        assert_eq!(compare_versions("23.01", "23.01"), Ordering::Equal);
+    }
+}
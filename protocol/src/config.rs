@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+use crate::error::ValidationError;
+
+/// タグの最大長
+pub const MAX_TAG_LENGTH: usize = 50;
+
+/// 1台のPCに付けられるタグの最大数
+pub const MAX_TAGS: usize = 10;
+
+/// クライアントのログレベル
+pub const LOG_LEVELS: [&str; 5] = ["trace", "debug", "info", "warn", "error"];
+
+/// サーバーから配布するクライアントの設定（PcInfoResponse.config / HeartbeatResponse.config）
+///
+/// クライアントはローカルの config.toml に上書きして使用します。
+/// 指定の無い項目（None）はローカルの値をそのまま使用します。
+/// 使用者名（pc_info.user_name）・送信先・自動更新の公開鍵は配布の対象外で、
+/// 常にローカルの値を使用します。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigOverrides {
+    /// 定期チェックの間隔（秒）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_interval_secs: Option<u64>,
+    /// 送信間隔（秒）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub send_interval_secs: Option<u64>,
+    /// 送信失敗時の1回目リトライまでの待機時間（秒）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_retry_delay_secs: Option<u64>,
+    /// 送信失敗時の2回目リトライまでの待機時間（秒）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub second_retry_delay_secs: Option<u64>,
+    /// ログレベル（trace, debug, info, warn, error）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_level: Option<String>,
+}
+
+impl ConfigOverrides {
+    /// 上書きする項目が無いか
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 別の設定を重ねる（other で指定された項目が優先）
+    pub fn merge(&mut self, other: &ConfigOverrides) {
+        if other.check_interval_secs.is_some() {
+            self.check_interval_secs = other.check_interval_secs;
+        }
+        if other.send_interval_secs.is_some() {
+            self.send_interval_secs = other.send_interval_secs;
+        }
+        if other.first_retry_delay_secs.is_some() {
+            self.first_retry_delay_secs = other.first_retry_delay_secs;
+        }
+        if other.second_retry_delay_secs.is_some() {
+            self.second_retry_delay_secs = other.second_retry_delay_secs;
+        }
+        if other.log_level.is_some() {
+            self.log_level.clone_from(&other.log_level);
+        }
+    }
+
+    /// 間隔が0でないこと、ログレベルが既知の値であることを検証
+    ///
+    /// # エラー
+    /// いずれかの値が不正な場合
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let intervals = [
+            ("check_interval_secs", self.check_interval_secs),
+            ("send_interval_secs", self.send_interval_secs),
+            ("first_retry_delay_secs", self.first_retry_delay_secs),
+            ("second_retry_delay_secs", self.second_retry_delay_secs),
+        ];
+        for (field, value) in intervals {
+            if value == Some(0) {
+                return Err(ValidationError::InvalidValue(format!("{} must be greater than 0", field)));
+            }
+        }
+
+        if let Some(level) = &self.log_level {
+            if !LOG_LEVELS.contains(&level.as_str()) {
+                return Err(ValidationError::InvalidValue(format!(
+                    "Invalid log level: '{}'. Must be one of: {}",
+                    level,
+                    LOG_LEVELS.join(", ")
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// PCのタグ（設定を配布するグループ）を検証
+///
+/// 英数字と ".", "-", "_" のみを許可します。
+///
+/// # エラー
+/// タグが多すぎる、空・長すぎる、または使用できない文字を含む場合
+pub fn validate_tags(tags: &[String]) -> Result<(), ValidationError> {
+    if tags.len() > MAX_TAGS {
+        return Err(ValidationError::InvalidValue(format!("Too many tags (max {})", MAX_TAGS)));
+    }
+
+    for tag in tags {
+        if tag.is_empty() || tag.len() > MAX_TAG_LENGTH {
+            return Err(ValidationError::InvalidValue(format!(
+                "Tag must be 1 to {} characters: '{}'",
+                MAX_TAG_LENGTH, tag
+            )));
+        }
+        if !tag.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_')) {
+            return Err(ValidationError::InvalidValue(format!(
+                "Tag may contain only letters, digits, '.', '-' and '_': '{}'",
+                tag
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_prefers_other() {
+        let mut global = ConfigOverrides {
+            check_interval_secs: Some(1800),
+            log_level: Some("info".to_string()),
+            ..Default::default()
+        };
+        global.merge(&ConfigOverrides {
+            send_interval_secs: Some(43200),
+            log_level: Some("debug".to_string()),
+            ..Default::default()
+        });
+
+        assert_eq!(global.check_interval_secs, Some(1800));
+        assert_eq!(global.send_interval_secs, Some(43200));
+        assert_eq!(global.log_level.as_deref(), Some("debug"));
+        assert_eq!(
+            serde_json::to_string(&global).unwrap(),
+            r#"{"check_interval_secs":1800,"send_interval_secs":43200,"log_level":"debug"}"#
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(ConfigOverrides::default().validate().is_ok());
+        assert!(ConfigOverrides { send_interval_secs: Some(0), ..Default::default() }.validate().is_err());
+        assert!(ConfigOverrides { log_level: Some("verbose".to_string()), ..Default::default() }.validate().is_err());
+
+        assert!(validate_tags(&["branch-office".to_string(), "floor_3".to_string()]).is_ok());
+        assert!(validate_tags(&["".to_string()]).is_err());
+        assert!(validate_tags(&["a,b".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// リクエストの検証エラー
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// 必須項目が空
+    #[error("Missing required field: {0}")]
+    MissingField(&'static str),
+
+    /// 値の形式・長さが不正
+    #[error("{0}")]
+    InvalidValue(String),
+}
+
+/// エラーレスポンス（4xx, 5xx）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub status: String,
+    pub message: String,
+}
+
+impl ErrorResponse {
+    /// エラーメッセージからレスポンスを生成
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            status: "error".to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// 必須の文字列項目が空でないか検証
+pub(crate) fn require(field: &'static str, value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(ValidationError::MissingField(field));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_response_round_trip() {
+        let json = r#"{"status":"error","message":"PC not registered: test-uuid"}"#;
+        let response: ErrorResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response, ErrorResponse::new("PC not registered: test-uuid"));
+        assert_eq!(serde_json::to_string(&response).unwrap(), json);
+    }
+}
@@ -0,0 +1,40 @@
+//! PC情報収集システム 通信プロトコル
+//!
+//! クライアントとサーバーの間で送受信するJSONの型（リクエスト・レスポンス）と、
+//! その検証ルールを定義します。クライアント・サーバーの両方がこのクレートに依存するため、
+//! 一方だけを変更して互換性が崩れることはありません。
+//!
+//! ルートの型は現在のAPIバージョン（`ApiVersion::CURRENT`）の形式です。
+//! 旧バージョンの形式は `v1` などのモジュールに残し、`From` で現在の形式に変換します。
+
+pub mod adapter;
+pub mod admin;
+pub mod capabilities;
+pub mod compare;
+pub mod config;
+pub mod error;
+pub mod network_type;
+pub mod pc_info;
+pub mod release;
+pub mod software;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testing;
+pub mod update;
+pub mod v1;
+pub mod version;
+
+pub use adapter::{AdapterItem, PcAdapterRequest, PcAdapterResponse};
+pub use admin::{
+    ApiKeyInfo, ApiKeyListResponse, CreateApiKeyRequest, CreateApiKeyResponse, DeleteResponse, HistoryReason, PcDetailResponse, PcHistoryEntry,
+    PcListQuery, PcListResponse, PcMergeRequest, PcMergeResponse, PcRecord,
+};
+pub use capabilities::{CapabilitiesResponse, Section};
+pub use compare::compare_versions;
+pub use config::ConfigOverrides;
+pub use error::{ErrorResponse, ValidationError};
+pub use network_type::NetworkType;
+pub use pc_info::{AgentInfo, HardwareInfo, HeartbeatRequest, HeartbeatResponse, PcInfoRequest, PcInfoResponse};
+pub use release::{ReleaseInfo, ReleaseKey, ReleaseResponse, ReleaseSigner, ReleaseUpload};
+pub use software::{PcSoftwareRequest, PcSoftwareResponse, SoftwareItem};
+pub use update::{HotfixItem, PcUpdateRequest, PcUpdateResponse};
+pub use version::ApiVersion;
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// ネットワークタイプ
+///
+/// 送信・保存時は "Wired" / "Wireless" / "VPN" / "Virtual" / "Cellular" / "Unknown" の文字列になります。
+/// 旧バージョンのクライアントが送信する名称（"Ethernet" / "Wi-Fi"）も受け付けます。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum NetworkType {
+    /// 有線LAN
+    Wired,
+    /// 無線LAN
+    Wireless,
+    /// VPN（トンネル、PPP、WireGuard など）
+    Vpn,
+    /// 仮想アダプタ（ブリッジ、Hyper-V、Docker など）
+    Virtual,
+    /// モバイル回線（WWAN）
+    Cellular,
+    /// 判定不能
+    #[default]
+    Unknown,
+}
+
+impl NetworkType {
+    /// 送信・保存に使用する文字列表現
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NetworkType::Wired => "Wired",
+            NetworkType::Wireless => "Wireless",
+            NetworkType::Vpn => "VPN",
+            NetworkType::Virtual => "Virtual",
+            NetworkType::Cellular => "Cellular",
+            NetworkType::Unknown => "Unknown",
+        }
+    }
+}
+
+impl fmt::Display for NetworkType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for NetworkType {
+    type Err = String;
+
+    /// 文字列からネットワークタイプに変換（大文字小文字は区別しない）
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "wired" | "ethernet" => Ok(NetworkType::Wired),
+            "wireless" | "wi-fi" => Ok(NetworkType::Wireless),
+            "vpn" => Ok(NetworkType::Vpn),
+            "virtual" => Ok(NetworkType::Virtual),
+            "cellular" => Ok(NetworkType::Cellular),
+            "unknown" => Ok(NetworkType::Unknown),
+            _ => Err(format!("Unknown network type: {}", s)),
+        }
+    }
+}
+
+impl From<NetworkType> for String {
+    fn from(network_type: NetworkType) -> Self {
+        network_type.as_str().to_string()
+    }
+}
+
+impl TryFrom<String> for NetworkType {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_accepts_legacy_names() {
+        assert_eq!("Ethernet".parse(), Ok(NetworkType::Wired));
+        assert_eq!("Wi-Fi".parse(), Ok(NetworkType::Wireless));
+        assert_eq!("vpn".parse(), Ok(NetworkType::Vpn));
+        assert!("Token Ring".parse::<NetworkType>().is_err());
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let json = serde_json::to_string(&NetworkType::Vpn).unwrap();
+        assert_eq!(json, "\"VPN\"");
+        assert_eq!(serde_json::from_str::<NetworkType>(&json).unwrap(), NetworkType::Vpn);
+        assert_eq!(serde_json::from_str::<NetworkType>("\"Ethernet\"").unwrap(), NetworkType::Wired);
+    }
+}
@@ -0,0 +1,403 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv6Addr};
+use crate::capabilities::{known_only, Section};
+use crate::config::{validate_tags, ConfigOverrides};
+use crate::error::{require, ValidationError};
+use crate::network_type::NetworkType;
+
+/// メーカー名の最大長（pc_info.manufacturer）
+pub const MAX_MANUFACTURER_LENGTH: usize = 255;
+
+/// クライアントのバージョンの最大長（pc_info.agent_version）
+pub const MAX_AGENT_VERSION_LENGTH: usize = 50;
+
+/// ビルドハッシュの最大長（pc_info.agent_build）
+pub const MAX_AGENT_BUILD_LENGTH: usize = 64;
+
+/// PC情報リクエスト（POST /api/v2/pc-info）
+///
+/// 現在のバージョン（v2）の形式です。旧形式は `v1::PcInfoRequest` を参照してください。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcInfoRequest {
+    pub uuid: String,
+    pub mac_address: String,
+    /// ネットワークタイプ（旧クライアントの "Ethernet" / "Wi-Fi" は Wired / Wireless に正規化）
+    pub network_type: NetworkType,
+    pub user_name: String,
+    /// IPアドレス（IPv4またはIPv6）
+    pub ip_address: String,
+    /// グローバルIPv6アドレス（デュアルスタック環境のみ、旧クライアントは送信しない）
+    #[serde(default)]
+    pub ipv6_address: Option<String>,
+    pub os: String,
+    pub os_version: String,
+    pub model_name: String,
+    /// ハードウェア情報（v2以降、サーバーが受け付ける場合のみ送信）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hardware: Option<HardwareInfo>,
+    /// クライアントの情報（v2以降）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<AgentInfo>,
+    /// PCのタグ（サーバーが配布する設定のグループ、v2以降）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// ハードウェア情報（PcInfoRequest.hardware）
+///
+/// 取得できなかった項目は None とします。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HardwareInfo {
+    /// メーカー名（例: "Dell Inc."）
+    pub manufacturer: Option<String>,
+    /// 物理メモリの合計（バイト）
+    pub total_memory_bytes: Option<u64>,
+    /// 論理プロセッサ数
+    pub logical_processors: Option<u32>,
+}
+
+/// クライアントの情報（PcInfoRequest.agent）
+///
+/// 配布したクライアントへの更新が完了したかを確認するために送信します。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentInfo {
+    /// クライアントのバージョン（例: "0.1.0"）
+    pub version: String,
+    /// ビルド元のコミットハッシュ（取得できなかった場合は None）
+    pub build_hash: Option<String>,
+    /// クライアントが収集できる項目（未知の項目は読み飛ばす）
+    #[serde(default, deserialize_with = "known_only")]
+    pub capabilities: Vec<Section>,
+}
+
+/// PC情報レスポンス（成功時）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcInfoResponse {
+    pub status: String,
+    pub action: String, // "created", "updated" or "unchanged"
+    pub id: i32,
+    /// サーバーから配布する設定（配布する設定が無い場合は None）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<ConfigOverrides>,
+}
+
+/// ハートビートリクエスト（POST /api/pc-heartbeat）
+///
+/// 前回送信時からPC情報に変化が無い場合に、PC情報の代わりに送信される。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeartbeatRequest {
+    pub uuid: String,
+    /// PCのタグ（PcInfoRequest.tags と同じ）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// ハートビートレスポンス（成功時）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeartbeatResponse {
+    pub status: String,
+    pub id: i32,
+    pub last_seen_at: DateTime<Utc>,
+    /// サーバーから配布する設定（PcInfoResponse.config と同じ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<ConfigOverrides>,
+}
+
+impl PcInfoRequest {
+    /// 必須項目とIPアドレスを検証
+    ///
+    /// ipv6_address が空文字の場合は未設定として扱います。
+    ///
+    /// # エラー
+    /// 必須項目が空の場合、または IPアドレスとして解釈できない場合
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        require("uuid", &self.uuid)?;
+        require("mac_address", &self.mac_address)?;
+        require("user_name", &self.user_name)?;
+        require("ip_address", &self.ip_address)?;
+        if self.ip_address.trim().parse::<IpAddr>().is_err() {
+            return Err(ValidationError::InvalidValue(format!("Invalid IP address: {}", self.ip_address)));
+        }
+        if let Some(ipv6) = self.ipv6_address.as_deref().map(str::trim).filter(|ip| !ip.is_empty()) {
+            if ipv6.parse::<Ipv6Addr>().is_err() {
+                return Err(ValidationError::InvalidValue(format!("Invalid IPv6 address: {}", ipv6)));
+            }
+        }
+        require("os", &self.os)?;
+        require("os_version", &self.os_version)?;
+        require("model_name", &self.model_name)?;
+        if let Some(hardware) = &self.hardware {
+            hardware.validate()?;
+        }
+        if let Some(agent) = &self.agent {
+            agent.validate()?;
+        }
+        validate_tags(&self.tags)?;
+
+        Ok(())
+    }
+
+    /// IPアドレスを検証し、正規化された表記に揃える
+    ///
+    /// IPv6アドレスは大文字小文字や省略表記の違いで検索漏れが起きないよう、
+    /// RFC 5952 形式（小文字・ゼロ圧縮）に変換します。
+    /// ipv6_address が空文字の場合は None として扱います。
+    ///
+    /// # エラー
+    /// ip_address がIPアドレスとして解釈できない場合、
+    /// または ipv6_address がIPv6アドレスとして解釈できない場合
+    pub fn normalize_addresses(&mut self) -> Result<(), ValidationError> {
+        let ip: IpAddr = self
+            .ip_address
+            .trim()
+            .parse()
+            .map_err(|_| ValidationError::InvalidValue(format!("Invalid IP address: {}", self.ip_address)))?;
+        self.ip_address = ip.to_string();
+
+        self.ipv6_address = match self.ipv6_address.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(ipv6) => {
+                let ipv6: Ipv6Addr = ipv6
+                    .parse()
+                    .map_err(|_| ValidationError::InvalidValue(format!("Invalid IPv6 address: {}", ipv6)))?;
+                Some(ipv6.to_string())
+            }
+        };
+
+        Ok(())
+    }
+}
+
+impl HardwareInfo {
+    /// メーカー名の長さを検証
+    ///
+    /// # エラー
+    /// メーカー名が長すぎる場合
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(manufacturer) = &self.manufacturer {
+            if manufacturer.chars().count() > MAX_MANUFACTURER_LENGTH {
+                return Err(ValidationError::InvalidValue(format!(
+                    "Manufacturer exceeds {} characters",
+                    MAX_MANUFACTURER_LENGTH
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl AgentInfo {
+    /// バージョンとビルドハッシュを検証
+    ///
+    /// # エラー
+    /// バージョンが空の場合、バージョンまたはビルドハッシュが長すぎる場合
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        require("agent.version", &self.version)?;
+        if self.version.chars().count() > MAX_AGENT_VERSION_LENGTH {
+            return Err(ValidationError::InvalidValue(format!(
+                "Agent version exceeds {} characters",
+                MAX_AGENT_VERSION_LENGTH
+            )));
+        }
+        if let Some(build_hash) = &self.build_hash {
+            if build_hash.chars().count() > MAX_AGENT_BUILD_LENGTH {
+                return Err(ValidationError::InvalidValue(format!(
+                    "Agent build hash exceeds {} characters",
+                    MAX_AGENT_BUILD_LENGTH
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PcInfoResponse {
+    /// 新規作成時のレスポンスを生成
+    pub fn created(id: i32) -> Self {
+        Self::with_action("created", id)
+    }
+
+    /// 更新時のレスポンスを生成
+    pub fn updated(id: i32) -> Self {
+        Self::with_action("updated", id)
+    }
+
+    /// 内容に変化が無かった場合のレスポンスを生成
+    pub fn unchanged(id: i32) -> Self {
+        Self::with_action("unchanged", id)
+    }
+
+    /// 配布する設定を付加
+    pub fn with_config(mut self, config: Option<ConfigOverrides>) -> Self {
+        self.config = config;
+        self
+    }
+
+    fn with_action(action: &str, id: i32) -> Self {
+        Self {
+            status: "success".to_string(),
+            action: action.to_string(),
+            id,
+            config: None,
+        }
+    }
+}
+
+impl HeartbeatRequest {
+    /// UUIDが空でないこと、タグの形式を検証
+    ///
+    /// # エラー
+    /// UUIDが空の場合、またはタグが不正な場合
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        require("uuid", &self.uuid)?;
+        validate_tags(&self.tags)
+    }
+}
+
+impl HeartbeatResponse {
+    /// 最終接触日時の更新成功時のレスポンスを生成
+    pub fn touched(id: i32, last_seen_at: DateTime<Utc>) -> Self {
+        Self {
+            status: "success".to_string(),
+            id,
+            last_seen_at,
+            config: None,
+        }
+    }
+
+    /// 配布する設定を付加
+    pub fn with_config(mut self, config: Option<ConfigOverrides>) -> Self {
+        self.config = config;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::pc_info_request as request;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_pc_info_request_round_trip() {
+        let json = r#"{"uuid":"test-uuid","mac_address":"00:11:22:33:44:55","network_type":"Wired","user_name":"testuser","ip_address":"192.168.1.100","ipv6_address":"2001:db8::10","os":"Windows 11 Pro","os_version":"10.0.22631","model_name":"Test Model"}"#;
+        let req: PcInfoRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req, request("192.168.1.100", Some("2001:db8::10")));
+        assert_eq!(serde_json::to_string(&req).unwrap(), json);
+    }
+
+    #[test]
+    fn test_pc_info_request_with_hardware_round_trip() {
+        let json = r#"{"uuid":"test-uuid","mac_address":"00:11:22:33:44:55","network_type":"Wired","user_name":"testuser","ip_address":"192.168.1.100","os":"Windows 11 Pro","os_version":"10.0.22631","model_name":"Test Model","hardware":{"manufacturer":"Dell Inc.","total_memory_bytes":17179869184,"logical_processors":8}}"#;
+        let req: PcInfoRequest = serde_json::from_str(json).unwrap();
+        let hardware = HardwareInfo {
+            manufacturer: Some("Dell Inc.".to_string()),
+            total_memory_bytes: Some(17_179_869_184),
+            logical_processors: Some(8),
+        };
+        assert_eq!(req.hardware.as_ref(), Some(&hardware));
+        assert_eq!(req.ipv6_address, None);
+        assert!(req.validate().is_ok());
+
+        let mut req = req;
+        req.hardware = Some(HardwareInfo {
+            manufacturer: Some("x".repeat(MAX_MANUFACTURER_LENGTH + 1)),
+            ..hardware
+        });
+        assert!(req.validate().is_err());
+    }
+
+    #[test]
+    fn test_agent_info_round_trip() {
+        let json = r#"{"version":"0.2.0","build_hash":"1a2b3c4d5e6f","capabilities":["hardware","adapters","updates"]}"#;
+        let agent: AgentInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(agent, AgentInfo {
+            version: "0.2.0".to_string(),
+            build_hash: Some("1a2b3c4d5e6f".to_string()),
+            capabilities: vec![Section::Hardware, Section::Adapters, Section::Updates],
+        });
+        assert_eq!(serde_json::to_string(&agent).unwrap(), json);
+
+        // 新しいクライアントが送信する未知の項目は読み飛ばす
+        let json = r#"{"version":"0.3.0","build_hash":null,"capabilities":["software","firmware"]}"#;
+        let agent: AgentInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(agent.capabilities, vec![Section::Software]);
+
+        let mut req = request("192.168.1.100", None);
+        req.agent = Some(AgentInfo { version: " ".to_string(), ..agent.clone() });
+        assert_eq!(req.validate(), Err(ValidationError::MissingField("agent.version")));
+        req.agent = Some(AgentInfo { build_hash: Some("f".repeat(65)), ..agent });
+        assert!(req.validate().is_err());
+    }
+
+    #[test]
+    fn test_pc_info_request_accepts_legacy_client() {
+        // 旧クライアントは ipv6_address を送信せず、network_type に "Ethernet" を使用する
+        let json = r#"{"uuid":"test-uuid","mac_address":"00:11:22:33:44:55","network_type":"Ethernet","user_name":"testuser","ip_address":"192.168.1.100","os":"Windows 11 Pro","os_version":"10.0.22631","model_name":"Test Model"}"#;
+        let req: PcInfoRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req, request("192.168.1.100", None));
+    }
+
+    #[test]
+    fn test_responses_round_trip() {
+        let json = r#"{"status":"success","action":"unchanged","id":42}"#;
+        let response: PcInfoResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response, PcInfoResponse::unchanged(42));
+        assert_eq!(serde_json::to_string(&response).unwrap(), json);
+
+        let json = r#"{"status":"success","id":42,"last_seen_at":"2024-01-31T09:00:00Z"}"#;
+        let response: HeartbeatResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response, HeartbeatResponse::touched(42, Utc.with_ymd_and_hms(2024, 1, 31, 9, 0, 0).unwrap()));
+        assert_eq!(serde_json::to_string(&response).unwrap(), json);
+    }
+
+    #[test]
+    fn test_response_with_config() {
+        let config = ConfigOverrides {
+            send_interval_secs: Some(43200),
+            ..Default::default()
+        };
+        let json = r#"{"status":"success","action":"updated","id":42,"config":{"send_interval_secs":43200}}"#;
+        let response: PcInfoResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response, PcInfoResponse::updated(42).with_config(Some(config)));
+        assert_eq!(serde_json::to_string(&response).unwrap(), json);
+
+        // タグを送信しないハートビート（旧クライアント）も受け付ける
+        let request: HeartbeatRequest = serde_json::from_str(r#"{"uuid":"test-uuid"}"#).unwrap();
+        assert!(request.tags.is_empty());
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(request("192.168.1.100", None).validate().is_ok());
+        assert!(request("2001:db8::10", Some("2001:db8::10")).validate().is_ok());
+        assert!(request("192.168.1.100", Some("")).validate().is_ok());
+
+        let mut req = request("192.168.1.100", None);
+        req.uuid = " ".to_string();
+        assert_eq!(req.validate(), Err(ValidationError::MissingField("uuid")));
+
+        assert!(request("999.1.1.1", None).validate().is_err());
+        assert!(request("192.168.1.100", Some("192.168.1.100")).validate().is_err());
+    }
+
+    #[test]
+    fn test_normalize_addresses_canonicalizes_ipv6() {
+        let mut req = request("2001:DB8:0:0::10", Some(" 2001:0db8::0010 "));
+        assert!(req.normalize_addresses().is_ok());
+        assert_eq!(req.ip_address, "2001:db8::10");
+        assert_eq!(req.ipv6_address.as_deref(), Some("2001:db8::10"));
+
+        let mut req = request("192.168.1.100", Some(""));
+        assert!(req.normalize_addresses().is_ok());
+        assert_eq!(req.ipv6_address, None);
+    }
+
+    #[test]
+    fn test_normalize_addresses_rejects_invalid() {
+        assert!(request("192.168.1.256", None).normalize_addresses().is_err());
+        assert!(request("192.168.1.100", Some("192.168.1.100")).normalize_addresses().is_err());
+    }
+}
@@ -0,0 +1,316 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use crate::error::{require, ValidationError};
+use crate::pc_info::MAX_AGENT_VERSION_LENGTH;
+
+/// 配布チャネル名の最大長（client_releases.channel）
+pub const MAX_CHANNEL_LENGTH: usize = 50;
+
+/// ターゲット名の最大長（client_releases.target）
+pub const MAX_TARGET_LENGTH: usize = 100;
+
+/// 配布するクライアントの情報（GET /api/releases/latest、POST /api/releases）
+///
+/// 実行ファイルの本体は `GET /api/releases/{id}/binary` で取得します。
+/// 署名は配布担当者の秘密鍵で作成され、サーバーは検証のみを行います。
+/// クライアントは設定ファイルに記載した配布チャネル・公開鍵で署名を検証してから置き換えます。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseInfo {
+    pub id: i32,
+    /// 配布チャネル（例: "stable"）
+    pub channel: String,
+    /// 実行環境のターゲット（例: "x86_64-pc-windows-msvc"）
+    pub target: String,
+    /// クライアントのバージョン（例: "0.2.0"）
+    pub version: String,
+    /// 実行ファイルのSHA-256（16進文字列）
+    pub sha256: String,
+    /// 実行ファイルのサイズ（バイト）
+    pub size: u64,
+    /// 署名（Ed25519、base64url）
+    pub signature: String,
+    pub published_at: DateTime<Utc>,
+}
+
+/// 配布するクライアントのレスポンス
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseResponse {
+    pub status: String,
+    /// 配布するクライアント（チャネルに登録されていない場合は None）
+    pub release: Option<ReleaseInfo>,
+}
+
+impl ReleaseResponse {
+    /// 配布するクライアントからレスポンスを生成
+    pub fn new(release: Option<ReleaseInfo>) -> Self {
+        Self {
+            status: "success".to_string(),
+            release,
+        }
+    }
+}
+
+/// 実行ファイルのアップロード（POST /api/releases のクエリパラメータ）
+///
+/// リクエストのボディは実行ファイルそのもの（application/octet-stream）です。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseUpload {
+    pub channel: String,
+    pub target: String,
+    pub version: String,
+    /// 署名（Ed25519、base64url）
+    pub signature: String,
+}
+
+impl ReleaseUpload {
+    /// パラメータを検証（署名の検証は ReleaseKey::verify で行う）
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        validate_name("channel", &self.channel, MAX_CHANNEL_LENGTH)?;
+        validate_name("target", &self.target, MAX_TARGET_LENGTH)?;
+        validate_name("version", &self.version, MAX_AGENT_VERSION_LENGTH)?;
+        require("signature", &self.signature)
+    }
+}
+
+impl ReleaseInfo {
+    /// ダウンロードした実行ファイルを検証
+    ///
+    /// 配布チャネル・サイズ・SHA-256・署名のすべてが一致する場合のみ成功します。
+    ///
+    /// # 引数
+    /// * `key` - 設定ファイルに記載した公開鍵
+    /// * `channel` - 設定ファイルに記載した配布チャネル
+    /// * `binary` - ダウンロードした実行ファイル
+    ///
+    /// # エラー
+    /// いずれかが一致しない場合
+    pub fn verify(&self, key: &ReleaseKey, channel: &str, binary: &[u8]) -> Result<(), ValidationError> {
+        if self.channel != channel {
+            return Err(ValidationError::InvalidValue(format!(
+                "Release channel mismatch: expected '{}', got '{}'",
+                channel, self.channel
+            )));
+        }
+
+        if binary.len() as u64 != self.size {
+            return Err(ValidationError::InvalidValue(format!(
+                "Release size mismatch: expected {} bytes, got {}",
+                self.size,
+                binary.len()
+            )));
+        }
+
+        if !sha256_hex(binary).eq_ignore_ascii_case(&self.sha256) {
+            return Err(ValidationError::InvalidValue("Release SHA-256 mismatch".to_string()));
+        }
+
+        key.verify(&self.channel, &self.target, &self.version, binary, &self.signature)
+    }
+}
+
+/// 配布チャネル名・ターゲット名・バージョンを検証
+///
+/// URL・ファイル名に使用するため、英数字と ".", "-", "_" のみを許可します。
+fn validate_name(field: &'static str, value: &str, max_length: usize) -> Result<(), ValidationError> {
+    require(field, value)?;
+
+    if value.len() > max_length {
+        return Err(ValidationError::InvalidValue(format!(
+            "{} is too long (max {} characters)",
+            field, max_length
+        )));
+    }
+
+    if !value.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_')) {
+        return Err(ValidationError::InvalidValue(format!(
+            "{} may contain only letters, digits, '.', '-' and '_': {}",
+            field, value
+        )));
+    }
+
+    Ok(())
+}
+
+/// SHA-256（16進文字列）を計算
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 署名の対象
+///
+/// 配布チャネル・ターゲット・バージョンを含めるため、署名済みの実行ファイルを
+/// 別のチャネル・別のバージョン・別の環境向けとして配布することはできません。
+fn signed_message(channel: &str, target: &str, version: &str, binary: &[u8]) -> Vec<u8> {
+    format!("pc-inventory-client\n{}\n{}\n{}\n{}", channel, target, version, sha256_hex(binary)).into_bytes()
+}
+
+/// 署名の検証に使用する公開鍵（Ed25519、base64url）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseKey(VerifyingKey);
+
+impl ReleaseKey {
+    /// base64url 形式の公開鍵を読み込み
+    ///
+    /// # エラー
+    /// 形式が不正な場合
+    pub fn parse(value: &str) -> Result<Self, ValidationError> {
+        let bytes: [u8; 32] = decode_fixed(value.trim(), "public key")?;
+        VerifyingKey::from_bytes(&bytes)
+            .map(Self)
+            .map_err(|e| ValidationError::InvalidValue(format!("Invalid public key: {}", e)))
+    }
+
+    /// 署名を検証
+    ///
+    /// # 引数
+    /// * `channel` - 配布チャネル
+    /// * `target` - ターゲット
+    /// * `version` - バージョン
+    /// * `binary` - 実行ファイル
+    /// * `signature` - 署名（base64url）
+    ///
+    /// # エラー
+    /// 署名が一致しない、または形式が不正な場合
+    pub fn verify(
+        &self,
+        channel: &str,
+        target: &str,
+        version: &str,
+        binary: &[u8],
+        signature: &str,
+    ) -> Result<(), ValidationError> {
+        let bytes: [u8; 64] = decode_fixed(signature.trim(), "signature")?;
+        self.0
+            .verify(&signed_message(channel, target, version, binary), &Signature::from_bytes(&bytes))
+            .map_err(|_| ValidationError::InvalidValue("Release signature verification failed".to_string()))
+    }
+}
+
+impl fmt::Display for ReleaseKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&URL_SAFE_NO_PAD.encode(self.0.as_bytes()))
+    }
+}
+
+/// 署名の作成に使用する秘密鍵（Ed25519、base64url）
+///
+/// 配布担当者の手元でのみ使用し、サーバー・クライアントには配置しません。
+pub struct ReleaseSigner(SigningKey);
+
+impl ReleaseSigner {
+    /// 32バイトの乱数から秘密鍵を作成
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(SigningKey::from_bytes(&bytes))
+    }
+
+    /// base64url 形式の秘密鍵を読み込み
+    ///
+    /// # エラー
+    /// 形式が不正な場合
+    pub fn parse(value: &str) -> Result<Self, ValidationError> {
+        decode_fixed(value.trim(), "secret key").map(Self::from_bytes)
+    }
+
+    /// 対応する公開鍵
+    pub fn public_key(&self) -> ReleaseKey {
+        ReleaseKey(self.0.verifying_key())
+    }
+
+    /// 秘密鍵（base64url）
+    pub fn to_secret_string(&self) -> String {
+        URL_SAFE_NO_PAD.encode(self.0.to_bytes())
+    }
+
+    /// 実行ファイルに署名
+    ///
+    /// # 戻り値
+    /// 署名（base64url）
+    pub fn sign(&self, channel: &str, target: &str, version: &str, binary: &[u8]) -> String {
+        URL_SAFE_NO_PAD.encode(self.0.sign(&signed_message(channel, target, version, binary)).to_bytes())
+    }
+}
+
+/// base64url 文字列を固定長のバイト列に変換
+fn decode_fixed<const N: usize>(value: &str, name: &str) -> Result<[u8; N], ValidationError> {
+    URL_SAFE_NO_PAD
+        .decode(value)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| ValidationError::InvalidValue(format!("Invalid {}: expected {} bytes of base64url", name, N)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(signer: &ReleaseSigner, binary: &[u8]) -> ReleaseInfo {
+        ReleaseInfo {
+            id: 1,
+            channel: "stable".to_string(),
+            target: "x86_64-pc-windows-msvc".to_string(),
+            version: "0.2.0".to_string(),
+            sha256: sha256_hex(binary),
+            size: binary.len() as u64,
+            signature: signer.sign("stable", "x86_64-pc-windows-msvc", "0.2.0", binary),
+            published_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_verify_signed_release() {
+        let signer = ReleaseSigner::from_bytes([7; 32]);
+        let key = ReleaseKey::parse(&signer.public_key().to_string()).unwrap();
+        let binary = b"new client binary";
+
+        let info = release(&signer, binary);
+        assert!(info.verify(&key, "stable", binary).is_ok());
+
+        // 内容・バージョンの改ざん
+        assert!(info.verify(&key, "stable", b"tampered binary!!").is_err());
+        let mut downgraded = info.clone();
+        downgraded.version = "0.3.0".to_string();
+        assert!(downgraded.verify(&key, "stable", binary).is_err());
+
+        // 設定と異なるチャネル、別のチャネル向けの署名
+        assert!(info.verify(&key, "beta", binary).is_err());
+        let mut promoted = info.clone();
+        promoted.channel = "beta".to_string();
+        assert!(promoted.verify(&key, "beta", binary).is_err());
+
+        // 別の鍵
+        let other = ReleaseSigner::from_bytes([8; 32]).public_key();
+        assert!(info.verify(&other, "stable", binary).is_err());
+    }
+
+    #[test]
+    fn test_key_round_trip() {
+        let signer = ReleaseSigner::from_bytes([7; 32]);
+        let restored = ReleaseSigner::parse(&signer.to_secret_string()).unwrap();
+        assert_eq!(restored.public_key(), signer.public_key());
+        assert!(ReleaseKey::parse("not a key").is_err());
+    }
+
+    #[test]
+    fn test_upload_validate() {
+        let upload = ReleaseUpload {
+            channel: "stable".to_string(),
+            target: "x86_64-pc-windows-msvc".to_string(),
+            version: "0.2.0".to_string(),
+            signature: "sig".to_string(),
+        };
+        assert!(upload.validate().is_ok());
+
+        let mut invalid = upload.clone();
+        invalid.channel = "../stable".to_string();
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = upload;
+        invalid.version = String::new();
+        assert_eq!(invalid.validate(), Err(ValidationError::MissingField("version")));
+    }
+}
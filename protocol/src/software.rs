@@ -0,0 +1,145 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use crate::error::{require, ValidationError};
+
+/// ソフトウェア名の最大長（pc_software.name）
+pub const MAX_SOFTWARE_NAME_LENGTH: usize = 255;
+
+/// バージョンの最大長（pc_software.version）
+pub const MAX_SOFTWARE_VERSION_LENGTH: usize = 100;
+
+/// ソフトウェア情報（リクエストの1要素）
+///
+/// クライアントでは差分計算のため名前・バージョン順に並べ替えて保持します。
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct SoftwareItem {
+    /// 製品名（例: "Google Chrome"）
+    pub name: String,
+    /// バージョン（例: "120.0.6099.130"）
+    pub version: String,
+    /// 発行元（例: "Google LLC"）
+    pub publisher: Option<String>,
+    /// インストール日（"YYYY-MM-DD" 形式）
+    pub install_date: Option<String>,
+}
+
+/// ソフトウェア一覧リクエスト（POST /api/pc-software）
+///
+/// full_sync が true の場合は added を完全な一覧として既存の一覧を置き換え、
+/// false の場合は removed を削除したうえで added を追加する。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcSoftwareRequest {
+    pub uuid: String,
+    pub full_sync: bool,
+    #[serde(default)]
+    pub added: Vec<SoftwareItem>,
+    #[serde(default)]
+    pub removed: Vec<SoftwareItem>,
+}
+
+/// ソフトウェア一覧レスポンス（成功時）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcSoftwareResponse {
+    pub status: String,
+    pub id: i32,
+    pub added: u64,
+    pub removed: u64,
+}
+
+impl SoftwareItem {
+    /// 名前・バージョンの長さとインストール日の形式を検証
+    ///
+    /// # エラー
+    /// 名前が空の場合、名前・バージョンが長すぎる場合、インストール日が "YYYY-MM-DD" 形式でない場合
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.name.trim().is_empty() {
+            return Err(ValidationError::InvalidValue("Software name cannot be empty".to_string()));
+        }
+        if self.name.chars().count() > MAX_SOFTWARE_NAME_LENGTH {
+            return Err(ValidationError::InvalidValue(format!(
+                "Software name is too long (max {} characters): {}",
+                MAX_SOFTWARE_NAME_LENGTH, self.name
+            )));
+        }
+        if self.version.chars().count() > MAX_SOFTWARE_VERSION_LENGTH {
+            return Err(ValidationError::InvalidValue(format!(
+                "Software version is too long (max {} characters): {}",
+                MAX_SOFTWARE_VERSION_LENGTH, self.name
+            )));
+        }
+        self.parsed_install_date()?;
+
+        Ok(())
+    }
+
+    /// インストール日を日付型に変換
+    ///
+    /// # エラー
+    /// "YYYY-MM-DD" 形式でない場合
+    pub fn parsed_install_date(&self) -> Result<Option<NaiveDate>, ValidationError> {
+        match self.install_date.as_deref().map(str::trim) {
+            None | Some("") => Ok(None),
+            Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map(Some)
+                .map_err(|_| ValidationError::InvalidValue(format!("Invalid install_date for '{}': {}", self.name, date))),
+        }
+    }
+}
+
+impl PcSoftwareRequest {
+    /// UUIDと追加・削除する各ソフトウェアを検証
+    ///
+    /// # エラー
+    /// UUIDが空の場合、いずれかのソフトウェア情報が不正な場合
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        require("uuid", &self.uuid)?;
+        self.added.iter().chain(self.removed.iter()).try_for_each(SoftwareItem::validate)
+    }
+}
+
+impl PcSoftwareResponse {
+    /// 同期成功時のレスポンスを生成
+    pub fn synced(id: i32, added: u64, removed: u64) -> Self {
+        Self {
+            status: "success".to_string(),
+            id,
+            added,
+            removed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(install_date: Option<&str>) -> SoftwareItem {
+        SoftwareItem {
+            name: "7-Zip".to_string(),
+            version: "23.01".to_string(),
+            publisher: None,
+            install_date: install_date.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_software_request_round_trip() {
+        let json = r#"{"uuid":"test-uuid","full_sync":false,"added":[{"name":"7-Zip","version":"23.01","publisher":null,"install_date":"2024-01-31"}],"removed":[]}"#;
+        let req: PcSoftwareRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.added, vec![item(Some("2024-01-31"))]);
+        assert_eq!(serde_json::to_string(&req).unwrap(), json);
+
+        let json = r#"{"status":"success","id":42,"added":1,"removed":0}"#;
+        let response: PcSoftwareResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response, PcSoftwareResponse::synced(42, 1, 0));
+        assert_eq!(serde_json::to_string(&response).unwrap(), json);
+    }
+
+    #[test]
+    fn test_parsed_install_date() {
+        assert_eq!(item(Some("2024-01-31")).parsed_install_date(), Ok(NaiveDate::from_ymd_opt(2024, 1, 31)));
+        assert_eq!(item(Some("")).parsed_install_date(), Ok(None));
+        assert!(item(Some("20240131")).parsed_install_date().is_err());
+        assert!(item(Some("20240131")).validate().is_err());
+    }
+}
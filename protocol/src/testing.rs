@@ -0,0 +1,28 @@
+//! テスト用のリクエスト（クライアント・サーバーのテストで共用）
+//!
+//! `test-util` フィーチャーを有効にした場合のみ公開します（各クレートの dev-dependencies で有効化）。
+
+use crate::network_type::NetworkType;
+use crate::pc_info::PcInfoRequest;
+
+/// 必須項目のみを設定したPC情報リクエスト（UUID は "test-uuid"、有線）
+///
+/// # 引数
+/// * `ip_address` - IPアドレス（IPv4またはIPv6）
+/// * `ipv6_address` - グローバルIPv6アドレス
+pub fn pc_info_request(ip_address: &str, ipv6_address: Option<&str>) -> PcInfoRequest {
+    PcInfoRequest {
+        uuid: "test-uuid".to_string(),
+        mac_address: "00:11:22:33:44:55".to_string(),
+        network_type: NetworkType::Wired,
+        user_name: "testuser".to_string(),
+        ip_address: ip_address.to_string(),
+        ipv6_address: ipv6_address.map(str::to_string),
+        os: "Windows 11 Pro".to_string(),
+        os_version: "10.0.22631".to_string(),
+        model_name: "Test Model".to_string(),
+        hardware: None,
+        agent: None,
+        tags: Vec::new(),
+    }
+}
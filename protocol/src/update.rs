@@ -0,0 +1,121 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use crate::error::{require, ValidationError};
+
+/// 更新プログラムIDの最大長（pc_hotfix.hotfix_id）
+pub const MAX_HOTFIX_ID_LENGTH: usize = 20;
+
+/// 更新プログラム情報（リクエストの1要素）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HotfixItem {
+    /// 更新プログラムID（例: "KB5034441"）
+    pub hotfix_id: String,
+    /// 種類（例: "Security Update"）
+    pub description: Option<String>,
+    /// インストール日（"YYYY-MM-DD" 形式）
+    pub installed_on: Option<String>,
+}
+
+/// Windows Update適用状況リクエスト（POST /api/pc-updates）
+///
+/// hotfixes は完全な一覧として扱い、既存の一覧を置き換える。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcUpdateRequest {
+    pub uuid: String,
+    /// OSのビルドリビジョン（UBR）
+    pub os_build_revision: Option<u32>,
+    #[serde(default)]
+    pub hotfixes: Vec<HotfixItem>,
+}
+
+/// Windows Update適用状況レスポンス（成功時）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcUpdateResponse {
+    pub status: String,
+    pub id: i32,
+    pub hotfixes: u64,
+}
+
+impl HotfixItem {
+    /// 更新プログラムIDとインストール日の形式を検証
+    ///
+    /// # エラー
+    /// IDが空または長すぎる場合、インストール日が "YYYY-MM-DD" 形式でない場合
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.hotfix_id.trim().is_empty() || self.hotfix_id.chars().count() > MAX_HOTFIX_ID_LENGTH {
+            return Err(ValidationError::InvalidValue(format!("Invalid hotfix_id: '{}'", self.hotfix_id)));
+        }
+        self.parsed_installed_on()?;
+
+        Ok(())
+    }
+
+    /// インストール日を日付型に変換
+    ///
+    /// # エラー
+    /// "YYYY-MM-DD" 形式でない場合
+    pub fn parsed_installed_on(&self) -> Result<Option<NaiveDate>, ValidationError> {
+        match self.installed_on.as_deref().map(str::trim) {
+            None | Some("") => Ok(None),
+            Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map(Some)
+                .map_err(|_| ValidationError::InvalidValue(format!("Invalid installed_on for '{}': {}", self.hotfix_id, date))),
+        }
+    }
+}
+
+impl PcUpdateRequest {
+    /// UUIDと各更新プログラムを検証
+    ///
+    /// # エラー
+    /// UUIDが空の場合、いずれかの更新プログラム情報が不正な場合
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        require("uuid", &self.uuid)?;
+        self.hotfixes.iter().try_for_each(HotfixItem::validate)
+    }
+}
+
+impl PcUpdateResponse {
+    /// 同期成功時のレスポンスを生成
+    pub fn synced(id: i32, hotfixes: u64) -> Self {
+        Self {
+            status: "success".to_string(),
+            id,
+            hotfixes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hotfix(hotfix_id: &str, installed_on: Option<&str>) -> HotfixItem {
+        HotfixItem {
+            hotfix_id: hotfix_id.to_string(),
+            description: Some("Security Update".to_string()),
+            installed_on: installed_on.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_update_request_round_trip() {
+        let json = r#"{"uuid":"test-uuid","os_build_revision":3007,"hotfixes":[{"hotfix_id":"KB5034441","description":"Security Update","installed_on":"2024-01-10"}]}"#;
+        let req: PcUpdateRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.hotfixes, vec![hotfix("KB5034441", Some("2024-01-10"))]);
+        assert_eq!(serde_json::to_string(&req).unwrap(), json);
+
+        let json = r#"{"status":"success","id":42,"hotfixes":1}"#;
+        let response: PcUpdateResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response, PcUpdateResponse::synced(42, 1));
+        assert_eq!(serde_json::to_string(&response).unwrap(), json);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(hotfix("KB5034441", Some("2024-01-10")).validate().is_ok());
+        assert!(hotfix("", None).validate().is_err());
+        assert!(hotfix("KB5034441-KB5034441-KB5034441", None).validate().is_err());
+        assert!(hotfix("KB5034441", Some("01/10/2024")).validate().is_err());
+    }
+}
@@ -0,0 +1,91 @@
+//! APIバージョン v1 の形式
+//!
+//! バージョン導入前のクライアントが送信する形式です。
+//! `/api/v1/...` およびバージョンを含まない従来のパスで受け付け、
+//! サーバーは `From` で現在の形式に変換して処理します。
+//! 配布済みのクライアントとの互換性のため、この形式は変更しないでください。
+
+use serde::{Deserialize, Serialize};
+use crate::network_type::NetworkType;
+
+/// PC情報リクエスト（v1）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcInfoRequest {
+    pub uuid: String,
+    pub mac_address: String,
+    pub network_type: NetworkType,
+    pub user_name: String,
+    pub ip_address: String,
+    #[serde(default)]
+    pub ipv6_address: Option<String>,
+    pub os: String,
+    pub os_version: String,
+    pub model_name: String,
+}
+
+impl From<PcInfoRequest> for crate::PcInfoRequest {
+    /// v1 の形式を現在の形式に変換（ハードウェア情報・クライアントの情報・タグは未送信として扱う）
+    fn from(request: PcInfoRequest) -> Self {
+        Self {
+            uuid: request.uuid,
+            mac_address: request.mac_address,
+            network_type: request.network_type,
+            user_name: request.user_name,
+            ip_address: request.ip_address,
+            ipv6_address: request.ipv6_address,
+            os: request.os,
+            os_version: request.os_version,
+            model_name: request.model_name,
+            hardware: None,
+            agent: None,
+            tags: Vec::new(),
+        }
+    }
+}
+
+impl From<crate::PcInfoRequest> for PcInfoRequest {
+    /// 現在の形式を v1 の形式に変換（v1 に存在しない項目は送信しない）
+    fn from(request: crate::PcInfoRequest) -> Self {
+        Self {
+            uuid: request.uuid,
+            mac_address: request.mac_address,
+            network_type: request.network_type,
+            user_name: request.user_name,
+            ip_address: request.ip_address,
+            ipv6_address: request.ipv6_address,
+            os: request.os,
+            os_version: request.os_version,
+            model_name: request.model_name,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HardwareInfo;
+
+    #[test]
+    fn test_v1_request_maps_to_current() {
+        // v1 のクライアントは hardware を送信しない（旧ネットワークタイプ名も受け付ける）
+        let json = r#"{"uuid":"test-uuid","mac_address":"00:11:22:33:44:55","network_type":"Wi-Fi","user_name":"testuser","ip_address":"192.168.1.100","os":"Windows 10 Pro","os_version":"10.0.19045","model_name":"Test Model"}"#;
+        let v1: PcInfoRequest = serde_json::from_str(json).unwrap();
+        let current = crate::PcInfoRequest::from(v1.clone());
+        assert_eq!(current.network_type, NetworkType::Wireless);
+        assert_eq!(current.hardware, None);
+        assert_eq!(current.agent, None);
+        assert!(current.validate().is_ok());
+
+        assert_eq!(PcInfoRequest::from(current), v1);
+    }
+
+    #[test]
+    fn test_current_request_maps_to_v1() {
+        let json = r#"{"uuid":"test-uuid","mac_address":"00:11:22:33:44:55","network_type":"Wired","user_name":"testuser","ip_address":"192.168.1.100","ipv6_address":null,"os":"Windows 11 Pro","os_version":"10.0.22631","model_name":"Test Model"}"#;
+        let mut current: crate::PcInfoRequest = serde_json::from_str(json).unwrap();
+        current.hardware = Some(HardwareInfo::default());
+
+        let v1 = PcInfoRequest::from(current);
+        assert_eq!(serde_json::to_string(&v1).unwrap(), json);
+    }
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// APIバージョン
+///
+/// クライアントからサーバーへの送信は `/api/v1/...`、`/api/v2/...` のように
+/// バージョンを含むパスで行います。バージョンを含まない従来のパス（`/api/pc-info` など）は
+/// 配布済みのクライアントのため v1 として扱います。
+///
+/// サーバーは古いバージョンの形式で送られたリクエストを現在の形式に変換して処理するため、
+/// クライアントとサーバーを同時に更新する必要はありません。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiVersion {
+    /// バージョン導入前の形式
+    V1,
+    /// PC情報にハードウェア情報（hardware）・クライアントの情報（agent）を追加
+    V2,
+}
+
+impl ApiVersion {
+    /// 現在のバージョン（PcInfoRequest などの形式）
+    pub const CURRENT: ApiVersion = ApiVersion::V2;
+
+    /// 対応しているすべてのバージョン（古い順）
+    pub const ALL: [ApiVersion; 2] = [ApiVersion::V1, ApiVersion::V2];
+
+    /// バージョン名（"v1", "v2"）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "v1",
+            ApiVersion::V2 => "v2",
+        }
+    }
+
+    /// エンドポイントのパスの接頭辞（例: "/api/v2"）
+    pub fn path_prefix(&self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "/api/v1",
+            ApiVersion::V2 => "/api/v2",
+        }
+    }
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_version_round_trip() {
+        let versions: Vec<ApiVersion> = serde_json::from_str(r#"["v1","v2"]"#).unwrap();
+        assert_eq!(versions, ApiVersion::ALL);
+        assert_eq!(serde_json::to_string(&ApiVersion::CURRENT).unwrap(), r#""v2""#);
+        assert!(ApiVersion::V1 < ApiVersion::V2);
+        assert_eq!(ApiVersion::V2.path_prefix(), "/api/v2");
+    }
+}
@@ -19,9 +19,9 @@ if %errorLevel% neq 0 (
 REM カレントディレクトリを設定
 cd /d "%~dp0"
 
-REM NSSMの存在確認
-if not exist "nssm.exe" (
-    echo ERROR: nssm.exe not found in the current directory.
+REM 実行ファイルの存在確認
+if not exist "pc-inventory-client.exe" (
+    echo ERROR: pc-inventory-client.exe not found.
     echo.
     pause
     exit /b 1
@@ -32,14 +32,8 @@ echo Stopping and removing service...
 echo ========================================
 echo.
 
-REM サービスを停止
-echo Stopping service...
-nssm stop "PCInventoryClient"
-timeout /t 3 /nobreak >nul
-
-REM サービスを削除
-echo Removing service...
-nssm remove "PCInventoryClient" confirm
+REM サービスを停止して削除（NSSM で登録したサービスも削除できる）
+pc-inventory-client.exe uninstall
 
 if %errorLevel% equ 0 (
     echo.
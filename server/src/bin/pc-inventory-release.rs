@@ -0,0 +1,90 @@
+//! 配布用クライアントの署名ツール
+//!
+//! 配布担当者の手元で実行し、鍵の作成と実行ファイルへの署名を行います。
+//! 秘密鍵はサーバー・クライアントには配置しないでください。
+//!
+//! ```text
+//! pc-inventory-release keygen <秘密鍵の保存先>
+//! pc-inventory-release sign <秘密鍵ファイル> <チャネル> <ターゲット> <バージョン> <実行ファイル>
+//! ```
+
+use pc_inventory_protocol::release::sha256_hex;
+use pc_inventory_protocol::ReleaseSigner;
+use std::fs;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage:
+  pc-inventory-release keygen <secret_key_file>
+  pc-inventory-release sign <secret_key_file> <channel> <target> <version> <binary>";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        ["keygen", secret_key_file] => keygen(secret_key_file),
+        ["sign", secret_key_file, channel, target, version, binary] => {
+            sign(secret_key_file, channel, target, version, binary)
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// 鍵を作成し、秘密鍵をファイルに保存して公開鍵を表示
+fn keygen(secret_key_file: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if fs::metadata(secret_key_file).is_ok() {
+        return Err(format!("{} already exists", secret_key_file).into());
+    }
+
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes)?;
+    let signer = ReleaseSigner::from_bytes(bytes);
+
+    fs::write(secret_key_file, signer.to_secret_string())?;
+
+    println!("Secret key saved to: {}", secret_key_file);
+    println!("Public key: {}", signer.public_key());
+    println!();
+    println!("Set the public key to [releases] public_key (server) and [update] public_key (client).");
+    Ok(())
+}
+
+/// 実行ファイルに署名し、アップロード用のクエリパラメータを表示
+fn sign(
+    secret_key_file: &str,
+    channel: &str,
+    target: &str,
+    version: &str,
+    binary: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let signer = ReleaseSigner::parse(&fs::read_to_string(secret_key_file)?)?;
+    let contents = fs::read(binary)?;
+    let signature = signer.sign(channel, target, version, &contents);
+
+    println!("Channel: {}", channel);
+    println!("Target: {}", target);
+    println!("Version: {}", version);
+    println!("SHA-256: {}", sha256_hex(&contents));
+    println!("Size: {} bytes", contents.len());
+    println!("Signature: {}", signature);
+    println!();
+    println!("Upload (API_KEY: an API key created with pc-inventory-admin):");
+    println!(
+        "  curl --data-binary @{} -H \"Content-Type: application/octet-stream\" \
+         -H \"Authorization: Bearer API_KEY\" \
+         \"http://SERVER:8080/api/releases?channel={}&target={}&version={}&signature={}\"",
+        binary, channel, target, version, signature
+    );
+    Ok(())
+}
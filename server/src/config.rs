@@ -1,3 +1,5 @@
+use pc_inventory_protocol::config::validate_tags;
+use pc_inventory_protocol::ConfigOverrides;
 use serde::Deserialize;
 use std::fs;
 use crate::error::{Result, ServerError};
@@ -8,12 +10,19 @@ pub struct ServerConfig {
     pub database: DatabaseSettings,
     pub logging: LoggingSettings,
     pub api: ApiSettings,
+    /// クライアントの配布（セクションが無い場合は配布しない）
+    #[serde(default)]
+    pub releases: Option<ReleaseSettings>,
+    /// クライアントに配布する設定（セクションが無い場合は配布しない）
+    #[serde(default)]
+    pub client_config: ClientConfigSettings,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct ServerSettings {
     pub host: String,
     pub port: u16,
+    #[allow(dead_code)]
     pub request_timeout_secs: u64,
 }
 
@@ -29,7 +38,9 @@ pub struct DatabaseSettings {
 pub struct LoggingSettings {
     pub level: String,
     pub file: String,
+    #[allow(dead_code)]
     pub max_file_size_mb: u64,
+    #[allow(dead_code)]
     pub max_backup_files: usize,
 }
 
@@ -38,6 +49,67 @@ pub struct ApiSettings {
     pub endpoint_path: String,
 }
 
+/// クライアントの配布設定
+#[derive(Debug, Deserialize, Clone)]
+pub struct ReleaseSettings {
+    /// 実行ファイルの保存先ディレクトリ
+    #[serde(default = "default_release_storage_dir")]
+    pub storage_dir: String,
+    /// 署名の検証に使用する公開鍵（Ed25519、base64url）
+    pub public_key: String,
+    /// アップロードできる実行ファイルの最大サイズ（MB）
+    #[serde(default = "default_max_upload_mb")]
+    pub max_upload_mb: u64,
+}
+
+/// クライアントに配布する設定
+///
+/// 全クライアント共通の設定に、PCのタグ（クライアントの pc_info.tags）に一致するグループの設定を
+/// 記載順に重ねたものを PcInfoResponse・HeartbeatResponse で返します。
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ClientConfigSettings {
+    /// 全クライアント共通の設定
+    #[serde(flatten)]
+    pub global: ConfigOverrides,
+    /// タグごとの設定（後に記載したグループが優先）
+    #[serde(default)]
+    pub groups: Vec<ClientConfigGroup>,
+}
+
+/// タグごとに配布する設定
+#[derive(Debug, Deserialize, Clone)]
+pub struct ClientConfigGroup {
+    /// 対象のPCのタグ
+    pub tag: String,
+    #[serde(flatten)]
+    pub overrides: ConfigOverrides,
+}
+
+impl ClientConfigSettings {
+    /// PCのタグに応じて配布する設定を決定
+    ///
+    /// # 引数
+    /// * `tags` - PCのタグ
+    ///
+    /// # 戻り値
+    /// 配布する設定（上書きする項目が無い場合は None）
+    pub fn overrides_for(&self, tags: &[String]) -> Option<ConfigOverrides> {
+        let mut overrides = self.global.clone();
+        for group in self.groups.iter().filter(|group| tags.contains(&group.tag)) {
+            overrides.merge(&group.overrides);
+        }
+        Some(overrides).filter(|overrides| !overrides.is_empty())
+    }
+}
+
+fn default_release_storage_dir() -> String {
+    "releases".to_string()
+}
+
+fn default_max_upload_mb() -> u64 {
+    100
+}
+
 impl ServerConfig {
     pub fn load(path: &str) -> Result<Self> {
         let contents = fs::read_to_string(path)
@@ -87,6 +159,63 @@ impl ServerConfig {
             ));
         }
 
+        // クライアントの配布設定の検証（公開鍵の形式は ReleaseStorage の作成時に検証）
+        if let Some(releases) = &self.releases {
+            if releases.storage_dir.trim().is_empty() {
+                return Err(ServerError::ConfigError("releases.storage_dir must not be empty".to_string()));
+            }
+
+            if releases.max_upload_mb == 0 {
+                return Err(ServerError::ConfigError("releases.max_upload_mb must be greater than 0".to_string()));
+            }
+        }
+
+        // クライアントに配布する設定の検証
+        self.client_config
+            .global
+            .validate()
+            .map_err(|e| ServerError::ConfigError(format!("client_config: {}", e)))?;
+        for group in &self.client_config.groups {
+            validate_tags(std::slice::from_ref(&group.tag))
+                .and_then(|()| group.overrides.validate())
+                .map_err(|e| ServerError::ConfigError(format!("client_config.groups ({}): {}", group.tag, e)))?;
+        }
+
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_config_overrides_for_tags() {
+        let settings: ClientConfigSettings = toml::from_str(
+            r#"
+            check_interval_secs = 1800
+            log_level = "info"
+
+            [[groups]]
+            tag = "branch"
+            send_interval_secs = 43200
+
+            [[groups]]
+            tag = "debug"
+            log_level = "debug"
+            "#,
+        )
+        .unwrap();
+
+        let overrides = settings.overrides_for(&["branch".to_string(), "debug".to_string()]).unwrap();
+        assert_eq!(overrides.check_interval_secs, Some(1800));
+        assert_eq!(overrides.send_interval_secs, Some(43200));
+        assert_eq!(overrides.log_level.as_deref(), Some("debug"));
+
+        let overrides = settings.overrides_for(&[]).unwrap();
+        assert_eq!(overrides.send_interval_secs, None);
+        assert_eq!(overrides.log_level.as_deref(), Some("info"));
+
+        assert_eq!(ClientConfigSettings::default().overrides_for(&["branch".to_string()]), None);
+    }
+}
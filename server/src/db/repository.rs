@@ -1,6 +1,12 @@
-use sqlx::{MySqlPool, Error as SqlxError};
-use chrono::Utc;
-use crate::models::pc_info::{PcInfo, PcInfoRequest};
+use sqlx::{MySqlPool, QueryBuilder, MySql, Transaction, Error as SqlxError};
+use chrono::{DateTime, Utc};
+use crate::models::agent::{format_capabilities, AgentInstall};
+use crate::models::api_key::ApiKey;
+use crate::models::pc_info::{PcHistory, PcInfo};
+use crate::models::release::ClientRelease;
+use crate::models::software::{SoftwareCursor, SoftwareInstall};
+use crate::models::update::{UpdateReportEntry, UpdateReportQuery};
+use pc_inventory_protocol::{AdapterItem, HistoryReason, PcInfoRequest, PcListQuery, PcSoftwareRequest, PcUpdateRequest, ReleaseUpload};
 
 /// PC情報のデータベースリポジトリ
 #[derive(Clone)]
@@ -29,8 +35,10 @@ impl PcInfoRepository {
         let result = sqlx::query_as::<_, PcInfo>(
             r#"
             SELECT id, uuid, mac_address, network_type, user_name,
-                   ip_address, os, os_version, model_name,
-                   created_at, updated_at
+                   ip_address, ipv6_address, os, os_version, model_name,
+                   manufacturer, total_memory_bytes, logical_processors,
+                   agent_version, agent_build, agent_capabilities,
+                   created_at, updated_at, last_seen_at
             FROM pc_info
             WHERE uuid = ?
             "#,
@@ -59,24 +67,36 @@ impl PcInfoRepository {
     pub async fn create(&self, request: &PcInfoRequest) -> Result<i32, SqlxError> {
         tracing::debug!("Creating new PC info record for UUID: {}", request.uuid);
         let now = Utc::now();
+        let hardware = request.hardware.clone().unwrap_or_default();
+        let agent = AgentColumns::from_request(request);
 
         let result = sqlx::query(
             r#"
             INSERT INTO pc_info (
                 uuid, mac_address, network_type, user_name,
-                ip_address, os, os_version, model_name,
-                created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ip_address, ipv6_address, os, os_version, model_name,
+                manufacturer, total_memory_bytes, logical_processors,
+                agent_version, agent_build, agent_capabilities,
+                created_at, updated_at, last_seen_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&request.uuid)
         .bind(&request.mac_address)
-        .bind(&request.network_type)
+        .bind(request.network_type.as_str())
         .bind(&request.user_name)
         .bind(&request.ip_address)
+        .bind(&request.ipv6_address)
         .bind(&request.os)
         .bind(&request.os_version)
         .bind(&request.model_name)
+        .bind(hardware.manufacturer)
+        .bind(hardware.total_memory_bytes)
+        .bind(hardware.logical_processors)
+        .bind(agent.version)
+        .bind(agent.build)
+        .bind(agent.capabilities)
+        .bind(now)
         .bind(now)
         .bind(now)
         .execute(&self.pool)
@@ -90,6 +110,12 @@ impl PcInfoRepository {
 
     /// 既存のPC情報レコードを更新
     ///
+    /// 最終更新日時（updated_at）と最終接触日時（last_seen_at）の両方を更新する。
+    /// 変更前の値は変更履歴（pc_info_history）に同じトランザクションで記録する。
+    /// 内容に変化が無い場合は呼び出さず、mark_seen を使用すること。
+    /// ハードウェア情報・クライアントの情報が送信されなかった場合（v1 のクライアントなど）は
+    /// 保存済みの値を変更しない。
+    ///
     /// # 引数
     /// * `id` - 更新するレコードのID
     /// * `request` - PC情報リクエストDTO
@@ -100,6 +126,13 @@ impl PcInfoRepository {
     pub async fn update(&self, id: i32, request: &PcInfoRequest) -> Result<(), SqlxError> {
         tracing::debug!("Updating PC info record ID: {} for UUID: {}", id, request.uuid);
         let now = Utc::now();
+        let reported = request.hardware.is_some();
+        let hardware = request.hardware.clone().unwrap_or_default();
+        let agent_reported = request.agent.is_some();
+        let agent = AgentColumns::from_request(request);
+        let mut tx = self.pool.begin().await?;
+
+        Self::record_history(&mut tx, id, id, HistoryReason::Updated, now).await?;
 
         let result = sqlx::query(
             r#"
@@ -108,32 +141,949 @@ impl PcInfoRepository {
                 network_type = ?,
                 user_name = ?,
                 ip_address = ?,
+                ipv6_address = ?,
                 os = ?,
                 os_version = ?,
                 model_name = ?,
-                updated_at = ?
+                manufacturer = IF(?, ?, manufacturer),
+                total_memory_bytes = IF(?, ?, total_memory_bytes),
+                logical_processors = IF(?, ?, logical_processors),
+                agent_version = IF(?, ?, agent_version),
+                agent_build = IF(?, ?, agent_build),
+                agent_capabilities = IF(?, ?, agent_capabilities),
+                updated_at = ?,
+                last_seen_at = ?
             WHERE id = ?
             "#,
         )
         .bind(&request.mac_address)
-        .bind(&request.network_type)
+        .bind(request.network_type.as_str())
         .bind(&request.user_name)
         .bind(&request.ip_address)
+        .bind(&request.ipv6_address)
         .bind(&request.os)
         .bind(&request.os_version)
         .bind(&request.model_name)
+        .bind(reported)
+        .bind(hardware.manufacturer)
+        .bind(reported)
+        .bind(hardware.total_memory_bytes)
+        .bind(reported)
+        .bind(hardware.logical_processors)
+        .bind(agent_reported)
+        .bind(agent.version)
+        .bind(agent_reported)
+        .bind(agent.build)
+        .bind(agent_reported)
+        .bind(agent.capabilities)
+        .bind(now)
         .bind(now)
         .bind(id)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
 
+        tx.commit().await?;
+
         tracing::debug!("Successfully updated PC info record ID: {}, rows affected: {}", id, result.rows_affected());
 
         Ok(())
     }
 
+    /// UUIDで特定したPCの最終接触日時（last_seen_at）のみを更新
+    ///
+    /// ハートビート受信時に呼び出す。PC情報の各項目と updated_at は変更しない。
+    /// 更新とIDの取得は1トランザクションで実行するため、同時に削除・統合されたPCを更新済みとして返すことはない。
+    ///
+    /// # 引数
+    /// * `uuid` - 対象PCのUUID
+    ///
+    /// # 戻り値
+    /// * `Ok(Some((id, last_seen_at)))` - 更新したレコードのIDと記録した日時
+    /// * `Ok(None)` - 該当するPCが登録されていない場合
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn touch_last_seen(&self, uuid: &str) -> Result<Option<(i32, DateTime<Utc>)>, SqlxError> {
+        tracing::debug!("Touching last_seen_at for UUID: {}", uuid);
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query("UPDATE pc_info SET last_seen_at = ? WHERE uuid = ?")
+            .bind(now)
+            .bind(uuid)
+            .execute(&mut *tx)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            tracing::debug!("No existing PC info found for UUID: {}", uuid);
+            return Ok(None);
+        }
+
+        // 更新した行はトランザクションの終了までロックされている
+        let id: i32 = sqlx::query_scalar("SELECT id FROM pc_info WHERE uuid = ?")
+            .bind(uuid)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        tracing::debug!("Updated last_seen_at for PC info ID: {}", id);
+
+        Ok(Some((id, now)))
+    }
+
+    /// PCの最終接触日時（last_seen_at）のみを更新
+    ///
+    /// 内容に変化の無いPC情報を受信した場合に呼び出す。
+    /// PC情報の各項目と updated_at は変更しない。
+    ///
+    /// # 引数
+    /// * `id` - 対象レコードのID
+    ///
+    /// # 戻り値
+    /// * `Ok(DateTime<Utc>)` - 記録した最終接触日時
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn mark_seen(&self, id: i32) -> Result<DateTime<Utc>, SqlxError> {
+        let now = Utc::now();
+
+        sqlx::query("UPDATE pc_info SET last_seen_at = ? WHERE id = ?")
+            .bind(now)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        tracing::debug!("Updated last_seen_at for PC info ID: {}", id);
+
+        Ok(now)
+    }
+
+    /// IPアドレスでPC情報を検索
+    ///
+    /// 完全なIPアドレス（IPv4/IPv6）が指定された場合は、代表IPアドレス・IPv6アドレスと
+    /// すべてのネットワークアダプタのアドレスを対象に完全一致で検索する。
+    /// それ以外の文字列は代表IPアドレス・IPv6アドレスの前方一致で検索する
+    /// （例: "192.168.1." や "2001:db8:"）。
+    ///
+    /// # 引数
+    /// * `ip` - 検索するIPアドレスまたはその先頭部分（None の場合はすべて）
+    ///
+    /// # 戻り値
+    /// * `Ok(Vec<PcInfo>)` - 条件に一致するPC情報（ID順）
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn search_by_ip(&self, ip: Option<&str>) -> Result<Vec<PcInfo>, SqlxError> {
+        tracing::debug!("Searching PC info by IP: {:?}", ip);
+
+        let mut builder: QueryBuilder<MySql> = QueryBuilder::new(
+            r#"
+            SELECT p.id, p.uuid, p.mac_address, p.network_type, p.user_name,
+                   p.ip_address, p.ipv6_address, p.os, p.os_version, p.model_name,
+                   p.manufacturer, p.total_memory_bytes, p.logical_processors,
+                   p.agent_version, p.agent_build, p.agent_capabilities,
+                   p.created_at, p.updated_at, p.last_seen_at
+            FROM pc_info p
+            "#,
+        );
+
+        if let Some(ip) = ip.map(str::trim).filter(|ip| !ip.is_empty()) {
+            match ip.parse::<std::net::IpAddr>() {
+                Ok(addr) => {
+                    let addr = addr.to_string();
+                    builder
+                        .push(" WHERE p.ip_address = ")
+                        .push_bind(addr.clone())
+                        .push(" OR p.ipv6_address = ")
+                        .push_bind(addr.clone())
+                        .push(" OR EXISTS (SELECT 1 FROM pc_network_adapter a WHERE a.pc_info_id = p.id AND (FIND_IN_SET(")
+                        .push_bind(addr.clone())
+                        .push(", a.ipv4_addresses) > 0 OR FIND_IN_SET(")
+                        .push_bind(addr)
+                        .push(", a.ipv6_addresses) > 0))");
+                }
+                Err(_) => {
+                    let pattern = format!("{}%", escape_like(&ip.to_lowercase()));
+                    builder
+                        .push(" WHERE p.ip_address LIKE ")
+                        .push_bind(pattern.clone())
+                        .push(" OR p.ipv6_address LIKE ")
+                        .push_bind(pattern);
+                }
+            }
+        }
+        builder.push(" ORDER BY p.id");
+
+        let result = builder.build_query_as::<PcInfo>().fetch_all(&self.pool).await?;
+
+        tracing::debug!("Found {} PC info records", result.len());
+        Ok(result)
+    }
+
+    /// IDでPC情報を検索
+    ///
+    /// # 戻り値
+    /// * `Ok(Some(PcInfo))` - レコードが見つかった場合
+    /// * `Ok(None)` - レコードが見つからなかった場合
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn find_by_id(&self, id: i32) -> Result<Option<PcInfo>, SqlxError> {
+        sqlx::query_as::<_, PcInfo>(
+            r#"
+            SELECT id, uuid, mac_address, network_type, user_name,
+                   ip_address, ipv6_address, os, os_version, model_name,
+                   manufacturer, total_memory_bytes, logical_processors,
+                   agent_version, agent_build, agent_capabilities,
+                   created_at, updated_at, last_seen_at
+            FROM pc_info
+            WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// PC情報の一覧・検索
+    ///
+    /// 検索文字列は UUID・使用者名・機種名・MACアドレス・IPアドレス・IPv6アドレスの部分一致で検索する。
+    /// after_id を指定した場合は、そのIDより後のPC情報のみを返す（続きの取得）。
+    ///
+    /// # 引数
+    /// * `query` - 検索文字列・最大件数・取得を始める位置
+    ///
+    /// # 戻り値
+    /// * `Ok(Vec<PcInfo>)` - 条件に一致するPC情報（ID順）
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn list_pcs(&self, query: &PcListQuery) -> Result<Vec<PcInfo>, SqlxError> {
+        tracing::debug!("Listing PC info: {:?}", query);
+
+        let mut builder: QueryBuilder<MySql> = QueryBuilder::new(
+            r#"
+            SELECT id, uuid, mac_address, network_type, user_name,
+                   ip_address, ipv6_address, os, os_version, model_name,
+                   manufacturer, total_memory_bytes, logical_processors,
+                   agent_version, agent_build, agent_capabilities,
+                   created_at, updated_at, last_seen_at
+            FROM pc_info
+            WHERE id > "#,
+        );
+        builder.push_bind(query.after_id.unwrap_or(0));
+
+        if let Some(search) = query.search_text() {
+            let pattern = format!("%{}%", escape_like(search));
+            builder.push(" AND (");
+            let mut separated = builder.separated(" OR ");
+            for column in ["uuid", "user_name", "model_name", "mac_address", "ip_address", "ipv6_address"] {
+                separated.push(format!("{} LIKE ", column)).push_bind_unseparated(pattern.clone());
+            }
+            builder.push(")");
+        }
+        builder.push(" ORDER BY id LIMIT ").push_bind(query.effective_limit());
+
+        let result = builder.build_query_as::<PcInfo>().fetch_all(&self.pool).await?;
+
+        tracing::debug!("Found {} PC info records", result.len());
+        Ok(result)
+    }
+
+    /// PC情報の変更履歴を取得
+    ///
+    /// # 戻り値
+    /// * `Ok(Vec<PcHistory>)` - 変更履歴（新しい順）
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn find_history(&self, pc_info_id: i32) -> Result<Vec<PcHistory>, SqlxError> {
+        sqlx::query_as::<_, PcHistory>(
+            r#"
+            SELECT uuid, mac_address, network_type, user_name,
+                   ip_address, ipv6_address, os, os_version, model_name,
+                   agent_version, reason, valid_from, recorded_at
+            FROM pc_info_history
+            WHERE pc_info_id = ?
+            ORDER BY recorded_at DESC, id DESC
+            "#,
+        )
+        .bind(pc_info_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// PC情報を削除
+    ///
+    /// ソフトウェア・更新プログラム・ネットワークアダプタ・変更履歴も削除される（ON DELETE CASCADE）。
+    /// クライアントが送信を続けている場合は、次回の送信で新しいレコードとして登録される。
+    ///
+    /// # 戻り値
+    /// * `Ok(true)` - 削除した場合
+    /// * `Ok(false)` - レコードが見つからなかった場合
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn delete(&self, id: i32) -> Result<bool, SqlxError> {
+        let result = sqlx::query("DELETE FROM pc_info WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        tracing::debug!("Deleted PC info record ID: {}, rows affected: {}", id, result.rows_affected());
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// PC情報を統合
+    ///
+    /// 統合元の変更履歴を統合先に移し、統合元の最後の値を統合先の履歴（reason: merged）として記録したうえで、
+    /// 統合元を削除する。統合先の初回登録日時は両者の早い方とする。
+    /// すべての操作は1トランザクションで実行される。
+    ///
+    /// # 引数
+    /// * `id` - 統合先のレコードのID（残る）
+    /// * `source_id` - 統合元のレコードのID（削除される）
+    ///
+    /// # 戻り値
+    /// * `Ok(Some(moved))` - 統合先に移した変更履歴の件数（統合元の最後の値を含む）
+    /// * `Ok(None)` - どちらかのレコードが見つからなかった場合
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn merge(&self, id: i32, source_id: i32) -> Result<Option<u64>, SqlxError> {
+        tracing::debug!("Merging PC info record ID: {} into ID: {}", source_id, id);
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+
+        // 統合中に他の更新が入らないよう、両方の行をロックする
+        let found: Vec<i32> = sqlx::query_scalar("SELECT id FROM pc_info WHERE id IN (?, ?) FOR UPDATE")
+            .bind(id)
+            .bind(source_id)
+            .fetch_all(&mut *tx)
+            .await?;
+        if found.len() != 2 {
+            return Ok(None);
+        }
+
+        let moved = sqlx::query("UPDATE pc_info_history SET pc_info_id = ? WHERE pc_info_id = ?")
+            .bind(id)
+            .bind(source_id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        let recorded = Self::record_history(&mut tx, source_id, id, HistoryReason::Merged, now).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE pc_info p
+            JOIN pc_info s ON s.id = ?
+            SET p.created_at = LEAST(p.created_at, s.created_at)
+            WHERE p.id = ?
+            "#,
+        )
+        .bind(source_id)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM pc_info WHERE id = ?")
+            .bind(source_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        tracing::debug!("Merged PC info record ID: {} into ID: {}, history moved: {}", source_id, id, moved + recorded);
+        Ok(Some(moved + recorded))
+    }
+
+    /// PC情報の現在の値を変更履歴に記録
+    ///
+    /// # 引数
+    /// * `source_id` - 値を記録するレコードのID
+    /// * `pc_info_id` - 履歴を関連付けるレコードのID（統合の場合は統合先）
+    /// * `reason` - 記録の理由
+    /// * `now` - 記録日時
+    ///
+    /// # 戻り値
+    /// 記録した件数（レコードが見つからない場合は 0）
+    async fn record_history(
+        tx: &mut Transaction<'_, MySql>,
+        source_id: i32,
+        pc_info_id: i32,
+        reason: HistoryReason,
+        now: DateTime<Utc>,
+    ) -> Result<u64, SqlxError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO pc_info_history (
+                pc_info_id, uuid, mac_address, network_type, user_name,
+                ip_address, ipv6_address, os, os_version, model_name,
+                agent_version, reason, valid_from, recorded_at
+            )
+            SELECT ?, uuid, mac_address, network_type, user_name,
+                   ip_address, ipv6_address, os, os_version, model_name,
+                   agent_version, ?, updated_at, ?
+            FROM pc_info
+            WHERE id = ?
+            "#,
+        )
+        .bind(pc_info_id)
+        .bind(reason.as_str())
+        .bind(now)
+        .bind(source_id)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// PCのソフトウェア一覧を同期
+    ///
+    /// full_sync の場合は既存の一覧をすべて削除してから added を登録し、
+    /// 差分の場合は removed を削除したうえで added を登録（既存なら更新）する。
+    /// すべての操作は1トランザクションで実行される。
+    ///
+    /// # 引数
+    /// * `pc_info_id` - 対象PCの pc_info.id
+    /// * `request` - ソフトウェア一覧リクエストDTO（検証済みであること）
+    ///
+    /// # 戻り値
+    /// * `Ok((added, removed))` - 登録件数と削除件数
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn sync_software(&self, pc_info_id: i32, request: &PcSoftwareRequest) -> Result<(u64, u64), SqlxError> {
+        tracing::debug!(
+            "Syncing software for PC info ID: {} (full_sync: {}, added: {}, removed: {})",
+            pc_info_id, request.full_sync, request.added.len(), request.removed.len()
+        );
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+        let mut removed = 0;
+
+        if request.full_sync {
+            let result = sqlx::query("DELETE FROM pc_software WHERE pc_info_id = ?")
+                .bind(pc_info_id)
+                .execute(&mut *tx)
+                .await?;
+            removed += result.rows_affected();
+        } else {
+            for item in &request.removed {
+                let result = sqlx::query(
+                    "DELETE FROM pc_software WHERE pc_info_id = ? AND name = ? AND version = ?",
+                )
+                .bind(pc_info_id)
+                .bind(&item.name)
+                .bind(&item.version)
+                .execute(&mut *tx)
+                .await?;
+                removed += result.rows_affected();
+            }
+        }
+
+        for item in &request.added {
+            sqlx::query(
+                r#"
+                INSERT INTO pc_software (
+                    pc_info_id, name, version, publisher, install_date,
+                    created_at, updated_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?)
+                ON DUPLICATE KEY UPDATE
+                    publisher = VALUES(publisher),
+                    install_date = VALUES(install_date),
+                    updated_at = VALUES(updated_at)
+                "#,
+            )
+            .bind(pc_info_id)
+            .bind(&item.name)
+            .bind(&item.version)
+            .bind(&item.publisher)
+            .bind(item.parsed_install_date().unwrap_or(None))
+            .bind(now)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        let added = request.added.len() as u64;
+        tracing::debug!(
+            "Successfully synced software for PC info ID: {}, added: {}, removed: {}",
+            pc_info_id, added, removed
+        );
+
+        Ok((added, removed))
+    }
+
+    /// ソフトウェア名の部分一致でインストール情報を検索
+    ///
+    /// 製品名・PC ID・バージョン順に、続きの位置より後のインストール情報を取得する。
+    ///
+    /// # 引数
+    /// * `name` - 製品名の部分一致フィルタ（None の場合は全件）
+    /// * `after` - 続きの位置（None の場合は先頭から）
+    /// * `limit` - 最大件数
+    ///
+    /// # 戻り値
+    /// * `Ok(Vec<SoftwareInstall>)` - 製品名・PC ID・バージョン順のインストール情報
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn search_software(
+        &self,
+        name: Option<&str>,
+        after: Option<&SoftwareCursor>,
+        limit: u32,
+    ) -> Result<Vec<SoftwareInstall>, SqlxError> {
+        tracing::debug!("Searching software with name filter: {:?}, after: {:?}, limit: {}", name, after, limit);
+
+        let pattern = match name {
+            Some(name) => format!("%{}%", escape_like(name)),
+            None => "%".to_string(),
+        };
+
+        let mut builder: QueryBuilder<MySql> = QueryBuilder::new(
+            r#"
+            SELECT s.pc_info_id, p.uuid, p.user_name, p.ip_address,
+                   p.updated_at, p.last_seen_at,
+                   s.name, s.version, s.publisher, s.install_date
+            FROM pc_software s
+            INNER JOIN pc_info p ON p.id = s.pc_info_id
+            WHERE s.name LIKE "#,
+        );
+        builder.push_bind(pattern);
+
+        if let Some(cursor) = after {
+            builder
+                .push(" AND (s.name, s.pc_info_id, s.version) > (")
+                .push_bind(cursor.name.clone())
+                .push(", ")
+                .push_bind(cursor.pc_info_id)
+                .push(", ")
+                .push_bind(cursor.version.clone())
+                .push(")");
+        }
+        builder.push(" ORDER BY s.name, s.pc_info_id, s.version LIMIT ").push_bind(limit);
+
+        let result = builder.build_query_as::<SoftwareInstall>().fetch_all(&self.pool).await?;
+
+        tracing::debug!("Found {} software installs", result.len());
+
+        Ok(result)
+    }
+
+    /// ソフトウェア名の完全一致でインストール情報を取得
+    ///
+    /// # 引数
+    /// * `name` - 製品名
+    ///
+    /// # 戻り値
+    /// * `Ok(Vec<SoftwareInstall>)` - PC ID 順のインストール情報
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn find_software_installs(&self, name: &str) -> Result<Vec<SoftwareInstall>, SqlxError> {
+        tracing::debug!("Fetching software installs for: {}", name);
+
+        let result = sqlx::query_as::<_, SoftwareInstall>(
+            r#"
+            SELECT s.pc_info_id, p.uuid, p.user_name, p.ip_address,
+                   p.updated_at, p.last_seen_at,
+                   s.name, s.version, s.publisher, s.install_date
+            FROM pc_software s
+            INNER JOIN pc_info p ON p.id = s.pc_info_id
+            WHERE s.name = ?
+            ORDER BY s.pc_info_id
+            "#,
+        )
+        .bind(name)
+        .fetch_all(&self.pool)
+        .await?;
+
+        tracing::debug!("Found {} installs of {}", result.len(), name);
+
+        Ok(result)
+    }
+
+    /// PCのWindows Update適用状況を更新
+    ///
+    /// pc_info のビルドリビジョンと更新日時を更新し、
+    /// pc_hotfix の一覧をリクエストの一覧で置き換える。
+    /// すべての操作は1トランザクションで実行される。
+    ///
+    /// # 引数
+    /// * `pc_info_id` - 対象PCの pc_info.id
+    /// * `request` - Windows Update適用状況リクエストDTO（検証済みであること）
+    ///
+    /// # 戻り値
+    /// * `Ok(u64)` - 登録した更新プログラムの件数
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn sync_updates(&self, pc_info_id: i32, request: &PcUpdateRequest) -> Result<u64, SqlxError> {
+        tracing::debug!(
+            "Syncing updates for PC info ID: {} (UBR: {:?}, hotfixes: {})",
+            pc_info_id, request.os_build_revision, request.hotfixes.len()
+        );
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            "UPDATE pc_info SET os_build_revision = ?, hotfixes_updated_at = ? WHERE id = ?",
+        )
+        .bind(request.os_build_revision)
+        .bind(now)
+        .bind(pc_info_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM pc_hotfix WHERE pc_info_id = ?")
+            .bind(pc_info_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for hotfix in &request.hotfixes {
+            sqlx::query(
+                r#"
+                INSERT IGNORE INTO pc_hotfix (
+                    pc_info_id, hotfix_id, description, installed_on, created_at
+                ) VALUES (?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(pc_info_id)
+            .bind(&hotfix.hotfix_id)
+            .bind(&hotfix.description)
+            .bind(hotfix.parsed_installed_on().unwrap_or(None))
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        let count = request.hotfixes.len() as u64;
+        tracing::debug!("Successfully synced updates for PC info ID: {}, hotfixes: {}", pc_info_id, count);
+
+        Ok(count)
+    }
+
+    /// PCのネットワークアダプタ一覧を置き換え
+    ///
+    /// 既存の一覧を削除してから、送信された全アダプタを登録する。
+    /// IPアドレスの一覧はカンマ区切りで保存する。
+    /// すべての操作は1トランザクションで実行される。
+    ///
+    /// # 引数
+    /// * `pc_info_id` - 対象PCの pc_info.id
+    /// * `adapters` - ネットワークアダプタ一覧（検証済みであること）
+    ///
+    /// # 戻り値
+    /// * `Ok(u64)` - 実際に登録したアダプタの件数
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn sync_adapters(&self, pc_info_id: i32, adapters: &[AdapterItem]) -> Result<u64, SqlxError> {
+        tracing::debug!("Syncing {} network adapters for PC info ID: {}", adapters.len(), pc_info_id);
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM pc_network_adapter WHERE pc_info_id = ?")
+            .bind(pc_info_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let mut synced = 0;
+        for adapter in adapters {
+            synced += sqlx::query(
+                r#"
+                INSERT IGNORE INTO pc_network_adapter (
+                    pc_info_id, name, mac_address, ipv4_addresses, ipv6_addresses,
+                    network_type, is_up, is_primary, updated_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(pc_info_id)
+            .bind(&adapter.name)
+            .bind(&adapter.mac_address)
+            .bind(adapter.ipv4_addresses.join(","))
+            .bind(adapter.ipv6_addresses.join(","))
+            .bind(adapter.network_type.as_str())
+            .bind(adapter.is_up)
+            .bind(adapter.is_primary)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+
+        tx.commit().await?;
+
+        tracing::debug!("Successfully synced {} network adapters for PC info ID: {}", synced, pc_info_id);
+
+        Ok(synced)
+    }
+
+    /// Windows Update適用状況の条件に該当するPCを検索
+    ///
+    /// 適用状況を一度も報告していないPC（hotfixes_updated_at が NULL）は対象外。
+    /// 指定された条件はすべて AND で評価される。
+    ///
+    /// # 引数
+    /// * `query` - 抽出条件（未適用のKB、ビルドリビジョンの下限、OSバージョン）
+    ///
+    /// # 戻り値
+    /// * `Ok(Vec<UpdateReportEntry>)` - 該当するPC（ID順）
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn find_update_noncompliant(&self, query: &UpdateReportQuery) -> Result<Vec<UpdateReportEntry>, SqlxError> {
+        tracing::debug!("Searching PCs by update status: {:?}", query);
+
+        let mut builder: QueryBuilder<MySql> = QueryBuilder::new(
+            r#"
+            SELECT p.id, p.uuid, p.user_name, p.ip_address,
+                   p.os, p.os_version, p.os_build_revision,
+                   p.updated_at, p.last_seen_at
+            FROM pc_info p
+            WHERE p.hotfixes_updated_at IS NOT NULL
+            "#,
+        );
+
+        if let Some(kb) = &query.missing_kb {
+            builder
+                .push(" AND NOT EXISTS (SELECT 1 FROM pc_hotfix h WHERE h.pc_info_id = p.id AND h.hotfix_id = ")
+                .push_bind(kb)
+                .push(")");
+        }
+        if let Some(revision) = query.below_revision {
+            // UBR が不明なPC（取得できない・未送信）は適用状況を確認できないため、対象に含める
+            builder
+                .push(" AND (p.os_build_revision < ")
+                .push_bind(revision)
+                .push(" OR p.os_build_revision IS NULL)");
+        }
+        if let Some(os_version) = &query.os_version {
+            builder
+                .push(" AND p.os_version = ")
+                .push_bind(os_version);
+        }
+        builder.push(" ORDER BY p.id");
+
+        let result = builder
+            .build_query_as::<UpdateReportEntry>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        tracing::debug!("Found {} PCs matching update criteria", result.len());
+
+        Ok(result)
+    }
+
+    /// すべてのPCのクライアントの情報を取得
+    ///
+    /// # 戻り値
+    /// * `Ok(Vec<AgentInstall>)` - PCごとのクライアントのバージョン（ID順）
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn find_agent_installs(&self) -> Result<Vec<AgentInstall>, SqlxError> {
+        tracing::debug!("Fetching agent versions of all PCs");
+
+        let result = sqlx::query_as::<_, AgentInstall>(
+            r#"
+            SELECT id, uuid, user_name, ip_address,
+                   agent_version, agent_build, agent_capabilities,
+                   last_seen_at
+            FROM pc_info
+            ORDER BY id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        tracing::debug!("Found {} PCs", result.len());
+
+        Ok(result)
+    }
+
+    /// 配布用クライアントを登録
+    ///
+    /// # 引数
+    /// * `upload` - アップロードのパラメータ（チャネル・ターゲット・バージョン・署名）
+    /// * `sha256` - 実行ファイルのSHA-256
+    /// * `size` - 実行ファイルのサイズ（バイト）
+    ///
+    /// # 戻り値
+    /// * `Ok(ClientRelease)` - 登録したレコード
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn create_release(&self, upload: &ReleaseUpload, sha256: &str, size: u64) -> Result<ClientRelease, SqlxError> {
+        tracing::debug!("Creating client release: {} {} ({})", upload.channel, upload.version, upload.target);
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO client_releases (channel, target, version, sha256, size, signature, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, NOW())
+            "#,
+        )
+        .bind(&upload.channel)
+        .bind(&upload.target)
+        .bind(&upload.version)
+        .bind(sha256)
+        .bind(size)
+        .bind(&upload.signature)
+        .execute(&self.pool)
+        .await?;
+
+        let id = result.last_insert_id() as i32;
+        tracing::info!("Client release created with ID: {}", id);
+
+        self.find_release(id).await?.ok_or(SqlxError::RowNotFound)
+    }
+
+    /// IDで配布用クライアントを検索
+    ///
+    /// # 戻り値
+    /// * `Ok(Some(ClientRelease))` - レコードが見つかった場合
+    /// * `Ok(None)` - レコードが見つからなかった場合
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn find_release(&self, id: i32) -> Result<Option<ClientRelease>, SqlxError> {
+        sqlx::query_as::<_, ClientRelease>(
+            r#"
+            SELECT id, channel, target, version, sha256, size, signature, created_at
+            FROM client_releases
+            WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// 配布チャネル・ターゲットに登録されている配布用クライアントの一覧
+    ///
+    /// バージョン番号の比較は SQL では行えないため、すべて取得して呼び出し側で比較する。
+    ///
+    /// # 戻り値
+    /// * `Ok(Vec<ClientRelease>)` - 登録されているクライアント（登録順）
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn find_releases(&self, channel: &str, target: &str) -> Result<Vec<ClientRelease>, SqlxError> {
+        tracing::debug!("Fetching client releases: {} ({})", channel, target);
+
+        sqlx::query_as::<_, ClientRelease>(
+            r#"
+            SELECT id, channel, target, version, sha256, size, signature, created_at
+            FROM client_releases
+            WHERE channel = ? AND target = ?
+            ORDER BY id
+            "#,
+        )
+        .bind(channel)
+        .bind(target)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// API キーを登録
+    ///
+    /// # 引数
+    /// * `name` - 用途・利用者の名前
+    /// * `key_hash` - API キーのハッシュ（キーそのものは保存しない）
+    ///
+    /// # 戻り値
+    /// * `Ok(ApiKey)` - 登録したレコード
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn create_api_key(&self, name: &str, key_hash: &str) -> Result<ApiKey, SqlxError> {
+        let now = Utc::now();
+        let result = sqlx::query("INSERT INTO api_keys (name, key_hash, created_at) VALUES (?, ?, ?)")
+            .bind(name)
+            .bind(key_hash)
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+
+        let id = result.last_insert_id() as i32;
+        tracing::info!("API key created with ID: {} ({})", id, name);
+
+        Ok(ApiKey {
+            id,
+            name: name.to_string(),
+            created_at: now,
+            last_used_at: None,
+        })
+    }
+
+    /// API キーの一覧
+    ///
+    /// # 戻り値
+    /// * `Ok(Vec<ApiKey>)` - 登録されている API キー（ID順）
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn find_api_keys(&self) -> Result<Vec<ApiKey>, SqlxError> {
+        sqlx::query_as::<_, ApiKey>("SELECT id, name, created_at, last_used_at FROM api_keys ORDER BY id")
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// API キーを削除
+    ///
+    /// # 戻り値
+    /// * `Ok(true)` - 削除した場合
+    /// * `Ok(false)` - レコードが見つからなかった場合
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn delete_api_key(&self, id: i32) -> Result<bool, SqlxError> {
+        let result = sqlx::query("DELETE FROM api_keys WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// API キーを認証し、最終使用日時を更新
+    ///
+    /// # 引数
+    /// * `key_hash` - リクエストの API キーのハッシュ
+    ///
+    /// # 戻り値
+    /// * `Ok(Some(ApiKey))` - 登録されている API キーの場合
+    /// * `Ok(None)` - 登録されていない場合
+    /// * `Err(SqlxError)` - データベースエラーが発生した場合
+    pub async fn authenticate_api_key(&self, key_hash: &str) -> Result<Option<ApiKey>, SqlxError> {
+        let key = sqlx::query_as::<_, ApiKey>("SELECT id, name, created_at, last_used_at FROM api_keys WHERE key_hash = ?")
+            .bind(key_hash)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        if let Some(key) = &key {
+            sqlx::query("UPDATE api_keys SET last_used_at = ? WHERE id = ?")
+                .bind(Utc::now())
+                .bind(key.id)
+                .execute(&self.pool)
+                .await?;
+        }
+
+        Ok(key)
+    }
+
     /// データベース接続プールを取得
+    #[allow(dead_code)]
     pub fn pool(&self) -> &MySqlPool {
         &self.pool
     }
 }
+
+/// pc_info に保存するクライアントの情報
+struct AgentColumns {
+    version: Option<String>,
+    build: Option<String>,
+    /// 収集できる項目（カンマ区切り、例: "hardware,adapters,updates"）
+    capabilities: Option<String>,
+}
+
+impl AgentColumns {
+    fn from_request(request: &PcInfoRequest) -> Self {
+        match &request.agent {
+            Some(agent) => Self {
+                version: Some(agent.version.clone()),
+                build: agent.build_hash.clone(),
+                capabilities: Some(format_capabilities(&agent.capabilities)),
+            },
+            None => Self {
+                version: None,
+                build: None,
+                capabilities: None,
+            },
+        }
+    }
+}
+
+/// LIKE 検索用に特殊文字（\, %, _）をエスケープ
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_like() {
+        assert_eq!(escape_like("Chrome"), "Chrome");
+        assert_eq!(escape_like("100%_\\"), "100\\%\\_\\\\");
+    }
+}
@@ -3,7 +3,7 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
-use serde::Serialize;
+use pc_inventory_protocol::{ErrorResponse, ValidationError};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -17,6 +17,12 @@ pub enum ServerError {
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
 
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
     #[error("Internal server error: {0}")]
     InternalError(String),
 
@@ -24,10 +30,10 @@ pub enum ServerError {
     IoError(#[from] std::io::Error),
 }
 
-#[derive(Serialize)]
-pub struct ErrorResponse {
-    pub status: String,
-    pub message: String,
+impl From<ValidationError> for ServerError {
+    fn from(e: ValidationError) -> Self {
+        ServerError::InvalidRequest(e.to_string())
+    }
 }
 
 impl IntoResponse for ServerError {
@@ -45,6 +51,14 @@ impl IntoResponse for ServerError {
                 tracing::warn!("Invalid request: {}", e);
                 (StatusCode::BAD_REQUEST, e.clone())
             }
+            ServerError::NotFound(ref e) => {
+                tracing::warn!("Not found: {}", e);
+                (StatusCode::NOT_FOUND, e.clone())
+            }
+            ServerError::Unauthorized(ref e) => {
+                tracing::warn!("Unauthorized: {}", e);
+                (StatusCode::UNAUTHORIZED, e.clone())
+            }
             ServerError::InternalError(ref e) => {
                 tracing::error!("Internal error: {}", e);
                 (StatusCode::INTERNAL_SERVER_ERROR, e.clone())
@@ -55,10 +69,7 @@ impl IntoResponse for ServerError {
             }
         };
 
-        let body = Json(ErrorResponse {
-            status: "error".to_string(),
-            message,
-        });
+        let body = Json(ErrorResponse::new(message));
 
         (status, body).into_response()
     }
@@ -0,0 +1,13 @@
+use axum::Json;
+use pc_inventory_protocol::CapabilitiesResponse;
+
+/// GET /api/capabilities エンドポイントハンドラー
+///
+/// サーバーが受け付けるAPIバージョンと任意の項目（hardware, adapters, software, updates）を返す。
+/// クライアントはこの結果から使用するバージョンと送信する項目を決定する。
+///
+/// # 戻り値
+/// * `Json<CapabilitiesResponse>` - 対応状況
+pub async fn handle_capabilities() -> Json<CapabilitiesResponse> {
+    Json(CapabilitiesResponse::current())
+}
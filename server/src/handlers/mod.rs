@@ -1 +1,8 @@
+pub mod admin;
+pub mod capabilities;
+pub mod network_adapter;
 pub mod pc_info;
+pub mod release;
+pub mod reports;
+pub mod software;
+pub mod update;
@@ -0,0 +1,46 @@
+use axum::{extract::State, Json};
+use crate::db::repository::PcInfoRepository;
+use pc_inventory_protocol::{PcAdapterRequest, PcAdapterResponse};
+use crate::error::ServerError;
+
+/// POST /api/pc-adapters エンドポイントハンドラー
+///
+/// クライアントから送信されたすべてのネットワークアダプタの情報を受け取り、
+/// UUIDで特定したPCのアダプタ一覧を置き換える。
+/// 対象PCは事前に POST /api/pc-info で登録されている必要がある。
+///
+/// # 引数
+/// * `State(repo)` - PcInfoRepositoryインスタンス
+/// * `Json(payload)` - ネットワークアダプタ一覧リクエストDTO
+///
+/// # 戻り値
+/// * `Ok(Json<PcAdapterResponse>)` - 成功時のレスポンス
+/// * `Err(ServerError)` - エラー時のレスポンス（未登録のPCの場合は404）
+pub async fn handle_pc_adapters(
+    State(repo): State<PcInfoRepository>,
+    Json(payload): Json<PcAdapterRequest>,
+) -> Result<Json<PcAdapterResponse>, ServerError> {
+    // バリデーション: UUID・各アダプタの名前とIPアドレス・代表アダプタの数
+    payload.validate()?;
+
+    // UUIDでPCを検索
+    let pc_info = repo
+        .find_by_uuid(&payload.uuid)
+        .await
+        .map_err(ServerError::DatabaseError)?
+        .ok_or_else(|| ServerError::NotFound(format!("PC not registered: {}", payload.uuid)))?;
+
+    let adapters = repo
+        .sync_adapters(pc_info.id, &payload.adapters)
+        .await
+        .map_err(ServerError::DatabaseError)?;
+
+    tracing::info!(
+        "Synced network adapters. ID: {}, UUID: {}, adapters: {}",
+        pc_info.id,
+        payload.uuid,
+        adapters
+    );
+
+    Ok(Json(PcAdapterResponse::synced(pc_info.id, adapters)))
+}
@@ -1,15 +1,22 @@
-use axum::{extract::State, Json};
+use axum::{extract::{Query, State}, Json};
+use std::sync::Arc;
+use crate::config::ClientConfigSettings;
 use crate::db::repository::PcInfoRepository;
-use crate::models::pc_info::{PcInfoRequest, PcInfoResponse};
+use crate::models::pc_info::{PcInfoSearchQuery, PcInfoSearchResponse};
+use pc_inventory_protocol::{v1, ConfigOverrides, HeartbeatRequest, HeartbeatResponse, PcInfoRequest, PcInfoResponse};
 use crate::error::ServerError;
 
-/// POST /api/pc-info エンドポイントハンドラー
+/// POST /api/v2/pc-info エンドポイントハンドラー
 ///
 /// クライアントから送信されたPC情報を受け取り、
-/// UUIDで既存レコードを検索し、新規登録または更新を行う
+/// UUIDで既存レコードを検索し、新規登録または更新を行う。
+/// 既存レコードと内容が同じ場合は最終接触日時（last_seen_at）のみを更新し、
+/// 最終更新日時（updated_at）は変更しない。
+/// レスポンスにはPCのタグに応じてクライアントに配布する設定を含める。
 ///
 /// # 引数
 /// * `State(repo)` - PcInfoRepositoryインスタンス
+/// * `State(client_config)` - クライアントに配布する設定
 /// * `Json(payload)` - PC情報リクエストDTO
 ///
 /// # 戻り値
@@ -17,24 +24,38 @@ use crate::error::ServerError;
 /// * `Err(ServerError)` - エラー時のレスポンス（自動的にHTTPレスポンスに変換される）
 pub async fn handle_pc_info(
     State(repo): State<PcInfoRepository>,
-    Json(payload): Json<PcInfoRequest>,
+    State(client_config): State<Arc<ClientConfigSettings>>,
+    Json(mut payload): Json<PcInfoRequest>,
 ) -> Result<Json<PcInfoResponse>, ServerError> {
-    // バリデーション: UUIDが空でないことを確認
-    if payload.uuid.trim().is_empty() {
-        return Err(ServerError::InvalidRequest(
-            "UUID cannot be empty".to_string(),
-        ));
-    }
+    // バリデーション: 必須項目とIPアドレス（IPv4/IPv6）を検証し、表記を正規化
+    payload.validate()?;
+    payload.normalize_addresses()?;
+
+    let config = config_for(&client_config, &payload.uuid, &payload.tags);
 
     // UUIDで既存レコードを検索
     let existing = repo
         .find_by_uuid(&payload.uuid)
         .await
-        .map_err(|e| ServerError::DatabaseError(e))?;
+        .map_err(ServerError::DatabaseError)?;
 
     match existing {
+        Some(pc_info) if !pc_info.differs_from(&payload) => {
+            // 既存レコードと内容が同じ場合: 最終接触日時のみ更新
+            tracing::info!(
+                "PC info unchanged. ID: {}, UUID: {}",
+                pc_info.id,
+                payload.uuid
+            );
+
+            repo.mark_seen(pc_info.id)
+                .await
+                .map_err(ServerError::DatabaseError)?;
+
+            Ok(Json(PcInfoResponse::unchanged(pc_info.id).with_config(config)))
+        }
         Some(pc_info) => {
-            // 既存レコードが見つかった場合: 更新
+            // 既存レコードと内容が異なる場合: 更新
             tracing::info!(
                 "Updating existing PC info. ID: {}, UUID: {}",
                 pc_info.id,
@@ -43,9 +64,9 @@ pub async fn handle_pc_info(
 
             repo.update(pc_info.id, &payload)
                 .await
-                .map_err(|e| ServerError::DatabaseError(e))?;
+                .map_err(ServerError::DatabaseError)?;
 
-            Ok(Json(PcInfoResponse::updated(pc_info.id)))
+            Ok(Json(PcInfoResponse::updated(pc_info.id).with_config(config)))
         }
         None => {
             // 既存レコードが見つからない場合: 新規作成
@@ -54,11 +75,102 @@ pub async fn handle_pc_info(
             let id = repo
                 .create(&payload)
                 .await
-                .map_err(|e| ServerError::DatabaseError(e))?;
+                .map_err(ServerError::DatabaseError)?;
 
             tracing::info!("Created new PC info. ID: {}, UUID: {}", id, payload.uuid);
 
-            Ok(Json(PcInfoResponse::created(id)))
+            Ok(Json(PcInfoResponse::created(id).with_config(config)))
         }
     }
 }
+
+/// POST /api/v1/pc-info（および従来の POST /api/pc-info）エンドポイントハンドラー
+///
+/// バージョン導入前の形式（v1）で送信されたPC情報を現在の形式に変換し、
+/// handle_pc_info と同じ処理を行う。v1 にはハードウェア情報が無いため、
+/// 保存済みのハードウェア情報は変更しない。
+///
+/// # 引数
+/// * `State(repo)` - PcInfoRepositoryインスタンス
+/// * `Json(payload)` - PC情報リクエストDTO（v1）
+///
+/// # 戻り値
+/// handle_pc_info と同様
+pub async fn handle_pc_info_v1(
+    State(repo): State<PcInfoRepository>,
+    State(client_config): State<Arc<ClientConfigSettings>>,
+    Json(payload): Json<v1::PcInfoRequest>,
+) -> Result<Json<PcInfoResponse>, ServerError> {
+    tracing::debug!("Mapping v1 PC info request onto current model. UUID: {}", payload.uuid);
+    handle_pc_info(State(repo), State(client_config), Json(payload.into())).await
+}
+
+/// GET /api/pc-info エンドポイントハンドラー
+///
+/// IPアドレス（IPv4/IPv6）でPC情報を検索する。
+/// 完全なアドレスの場合はネットワークアダプタのアドレスも含めて完全一致、
+/// それ以外は代表アドレスの前方一致で検索する。
+///
+/// # 引数
+/// * `State(repo)` - PcInfoRepositoryインスタンス
+/// * `Query(query)` - 検索条件
+///
+/// # 戻り値
+/// * `Ok(Json<PcInfoSearchResponse>)` - 検索結果
+/// * `Err(ServerError)` - エラー時のレスポンス
+pub async fn handle_pc_search(
+    State(repo): State<PcInfoRepository>,
+    Query(query): Query<PcInfoSearchQuery>,
+) -> Result<Json<PcInfoSearchResponse>, ServerError> {
+    let items = repo
+        .search_by_ip(query.ip.as_deref())
+        .await
+        .map_err(ServerError::DatabaseError)?;
+
+    tracing::info!("PC info search (ip: {:?}): {} results", query.ip, items.len());
+
+    Ok(Json(PcInfoSearchResponse::new(items)))
+}
+
+/// POST /api/pc-heartbeat エンドポイントハンドラー
+///
+/// 前回送信時からPC情報に変化が無いクライアントから呼び出され、
+/// PCの最終接触日時（last_seen_at）のみを更新する。updated_at は変更しない。
+/// レスポンスには handle_pc_info と同様にクライアントに配布する設定を含める。
+///
+/// # 引数
+/// * `State(repo)` - PcInfoRepositoryインスタンス
+/// * `State(client_config)` - クライアントに配布する設定
+/// * `Json(payload)` - ハートビートリクエストDTO
+///
+/// # 戻り値
+/// * `Ok(Json<HeartbeatResponse>)` - 成功時のレスポンス
+/// * `Err(ServerError)` - エラー時のレスポンス（未登録のPCの場合は404）
+pub async fn handle_heartbeat(
+    State(repo): State<PcInfoRepository>,
+    State(client_config): State<Arc<ClientConfigSettings>>,
+    Json(payload): Json<HeartbeatRequest>,
+) -> Result<Json<HeartbeatResponse>, ServerError> {
+    // バリデーション: UUIDが空でないことを確認
+    payload.validate()?;
+
+    let (id, last_seen_at) = repo
+        .touch_last_seen(&payload.uuid)
+        .await
+        .map_err(ServerError::DatabaseError)?
+        .ok_or_else(|| ServerError::NotFound(format!("PC not registered: {}", payload.uuid)))?;
+
+    tracing::info!("Heartbeat received. ID: {}, UUID: {}", id, payload.uuid);
+
+    let config = config_for(&client_config, &payload.uuid, &payload.tags);
+    Ok(Json(HeartbeatResponse::touched(id, last_seen_at).with_config(config)))
+}
+
+/// PCのタグに応じてクライアントに配布する設定を決定
+fn config_for(client_config: &ClientConfigSettings, uuid: &str, tags: &[String]) -> Option<ConfigOverrides> {
+    let config = client_config.overrides_for(tags);
+    if let Some(config) = &config {
+        tracing::debug!("Client config for UUID: {} (tags: {:?}): {:?}", uuid, tags, config);
+    }
+    config
+}
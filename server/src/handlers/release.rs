@@ -0,0 +1,136 @@
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::header,
+    response::IntoResponse,
+    Json,
+};
+use pc_inventory_protocol::release::sha256_hex;
+use pc_inventory_protocol::{ReleaseResponse, ReleaseUpload};
+use std::sync::Arc;
+use crate::db::repository::PcInfoRepository;
+use crate::error::ServerError;
+use crate::models::release::{latest_release, LatestReleaseQuery};
+use crate::storage::ReleaseStorage;
+
+/// クライアントの配布エンドポイントの状態
+#[derive(Clone)]
+pub struct ReleaseState {
+    pub repository: PcInfoRepository,
+    pub storage: Arc<ReleaseStorage>,
+}
+
+/// POST /api/releases エンドポイントハンドラー
+///
+/// 配布用クライアントの実行ファイルを登録する。
+/// ボディは実行ファイルそのもので、チャネル・ターゲット・バージョン・署名はクエリパラメータで指定する。
+/// 署名は設定ファイルの公開鍵で検証し、一致しない場合は登録しない。
+///
+/// # 引数
+/// * `State(state)` - ReleaseStateインスタンス
+/// * `Query(upload)` - チャネル・ターゲット・バージョン・署名
+/// * `body` - 実行ファイル
+///
+/// # 戻り値
+/// * `Ok(Json<ReleaseResponse>)` - 登録した配布用クライアント
+/// * `Err(ServerError)` - 署名が一致しない、または同じバージョンが登録済みの場合は400
+pub async fn handle_release_upload(
+    State(state): State<ReleaseState>,
+    Query(upload): Query<ReleaseUpload>,
+    body: Bytes,
+) -> Result<Json<ReleaseResponse>, ServerError> {
+    tracing::info!("Received client release: {} {} ({}, {} bytes)",
+        upload.channel, upload.version, upload.target, body.len());
+
+    upload.validate()?;
+
+    if body.is_empty() {
+        return Err(ServerError::InvalidRequest("Release binary is empty".to_string()));
+    }
+
+    state
+        .storage
+        .key()
+        .verify(&upload.channel, &upload.target, &upload.version, &body, &upload.signature)?;
+
+    let existing = state
+        .repository
+        .find_releases(&upload.channel, &upload.target)
+        .await
+        .map_err(ServerError::DatabaseError)?;
+    if existing.iter().any(|release| release.version == upload.version) {
+        return Err(ServerError::InvalidRequest(format!(
+            "Release {} is already published to {} ({})",
+            upload.version, upload.channel, upload.target
+        )));
+    }
+
+    let sha256 = sha256_hex(&body);
+    state.storage.save(&sha256, &body).await?;
+
+    let release = state
+        .repository
+        .create_release(&upload, &sha256, body.len() as u64)
+        .await
+        .map_err(ServerError::DatabaseError)?;
+
+    tracing::info!("Client release published. id: {}, version: {}, sha256: {}", release.id, release.version, sha256);
+
+    Ok(Json(ReleaseResponse::new(Some(release.into()))))
+}
+
+/// GET /api/releases/latest エンドポイントハンドラー
+///
+/// 配布チャネル・ターゲットに登録されているうち、最新のバージョンの配布用クライアントを返す。
+///
+/// # 引数
+/// * `State(state)` - ReleaseStateインスタンス
+/// * `Query(query)` - チャネル・ターゲット
+///
+/// # 戻り値
+/// * `Ok(Json<ReleaseResponse>)` - 最新の配布用クライアント（登録されていない場合は release が null）
+/// * `Err(ServerError)` - エラー時のレスポンス
+pub async fn handle_latest_release(
+    State(state): State<ReleaseState>,
+    Query(query): Query<LatestReleaseQuery>,
+) -> Result<Json<ReleaseResponse>, ServerError> {
+    let releases = state
+        .repository
+        .find_releases(&query.channel, &query.target)
+        .await
+        .map_err(ServerError::DatabaseError)?;
+
+    let latest = latest_release(releases);
+    tracing::debug!("Latest client release for {} ({}): {:?}",
+        query.channel, query.target, latest.as_ref().map(|r| &r.version));
+
+    Ok(Json(ReleaseResponse::new(latest.map(Into::into))))
+}
+
+/// GET /api/releases/{id}/binary エンドポイントハンドラー
+///
+/// 配布用クライアントの実行ファイルを返す。
+///
+/// # 引数
+/// * `State(state)` - ReleaseStateインスタンス
+/// * `Path(id)` - 配布用クライアントのID
+///
+/// # 戻り値
+/// * `Ok(実行ファイル)` - application/octet-stream
+/// * `Err(ServerError)` - 登録されていない場合は404
+pub async fn handle_release_binary(
+    State(state): State<ReleaseState>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ServerError> {
+    let release = state
+        .repository
+        .find_release(id)
+        .await
+        .map_err(ServerError::DatabaseError)?
+        .ok_or_else(|| ServerError::NotFound(format!("Release not found: {}", id)))?;
+
+    let binary = state.storage.read(&release.sha256).await?;
+    tracing::info!("Serving client release {} ({} bytes)", release.version, binary.len());
+
+    Ok(([(header::CONTENT_TYPE, "application/octet-stream")], binary))
+}
@@ -0,0 +1,95 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use crate::db::repository::PcInfoRepository;
+use crate::models::agent::AgentVersionReport;
+use crate::models::software::SoftwareVersionReport;
+use crate::models::update::{UpdateReport, UpdateReportQuery};
+use crate::error::ServerError;
+
+/// GET /api/reports/software/{name} エンドポイントハンドラー
+///
+/// 指定したソフトウェア（製品名の完全一致）のバージョン分布と、
+/// 各バージョンがインストールされているPCの一覧を返す。
+///
+/// # 引数
+/// * `State(repo)` - PcInfoRepositoryインスタンス
+/// * `Path(name)` - 製品名
+///
+/// # 戻り値
+/// * `Ok(Json<SoftwareVersionReport>)` - バージョン分布レポート（新しいバージョン順）
+/// * `Err(ServerError)` - エラー時のレスポンス
+pub async fn handle_software_report(
+    State(repo): State<PcInfoRepository>,
+    Path(name): Path<String>,
+) -> Result<Json<SoftwareVersionReport>, ServerError> {
+    let installs = repo
+        .find_software_installs(&name)
+        .await
+        .map_err(ServerError::DatabaseError)?;
+
+    tracing::info!("Software version report. name: {}, installs: {}", name, installs.len());
+
+    Ok(Json(SoftwareVersionReport::from_installs(name, installs)))
+}
+
+/// GET /api/reports/updates エンドポイントハンドラー
+///
+/// 指定した更新プログラム（KB）が未適用のPC、
+/// またはビルドリビジョン（UBR）が指定値未満のPCの一覧を返す。
+///
+/// # 引数
+/// * `State(repo)` - PcInfoRepositoryインスタンス
+/// * `Query(query)` - 抽出条件（missing_kb, below_revision, os_version）
+///
+/// # 戻り値
+/// * `Ok(Json<UpdateReport>)` - 該当するPCの一覧
+/// * `Err(ServerError)` - missing_kb と below_revision のどちらも指定されていない場合は400
+pub async fn handle_update_report(
+    State(repo): State<PcInfoRepository>,
+    Query(mut query): Query<UpdateReportQuery>,
+) -> Result<Json<UpdateReport>, ServerError> {
+    query.missing_kb = query.missing_kb.map(|kb| kb.trim().to_uppercase()).filter(|kb| !kb.is_empty());
+    query.os_version = query.os_version.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+    if query.missing_kb.is_none() && query.below_revision.is_none() {
+        return Err(ServerError::InvalidRequest(
+            "Either missing_kb or below_revision must be specified".to_string(),
+        ));
+    }
+
+    let pcs = repo
+        .find_update_noncompliant(&query)
+        .await
+        .map_err(ServerError::DatabaseError)?;
+
+    tracing::info!("Update report. query: {:?}, results: {}", query, pcs.len());
+
+    Ok(Json(UpdateReport::new(pcs)))
+}
+
+/// GET /api/reports/agents エンドポイントハンドラー
+///
+/// すべてのPCをクライアントのバージョンごとに集計し、
+/// 各バージョンのPC数と一覧（ビルドハッシュ・収集できる項目を含む）を返す。
+/// クライアントの更新が全PCに行き渡ったかの確認に使用する。
+///
+/// # 引数
+/// * `State(repo)` - PcInfoRepositoryインスタンス
+///
+/// # 戻り値
+/// * `Ok(Json<AgentVersionReport>)` - バージョン別レポート（新しいバージョン順、バージョン不明は最後）
+/// * `Err(ServerError)` - エラー時のレスポンス
+pub async fn handle_agent_report(
+    State(repo): State<PcInfoRepository>,
+) -> Result<Json<AgentVersionReport>, ServerError> {
+    let installs = repo
+        .find_agent_installs()
+        .await
+        .map_err(ServerError::DatabaseError)?;
+
+    tracing::info!("Agent version report. PCs: {}", installs.len());
+
+    Ok(Json(AgentVersionReport::from_installs(installs)))
+}
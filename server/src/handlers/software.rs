@@ -0,0 +1,101 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use crate::db::repository::PcInfoRepository;
+use crate::models::software::{
+    collect_matching, SoftwareInstall, SoftwareSearchQuery, SoftwareSearchResponse, SOFTWARE_SEARCH_PAGE_SIZE,
+};
+use pc_inventory_protocol::{PcSoftwareRequest, PcSoftwareResponse};
+use crate::error::ServerError;
+
+/// POST /api/pc-software エンドポイントハンドラー
+///
+/// クライアントから送信されたインストール済みソフトウェア一覧（または差分）を受け取り、
+/// UUIDで特定したPCのソフトウェア一覧を更新する。
+/// 対象PCは事前に POST /api/pc-info で登録されている必要がある。
+///
+/// # 引数
+/// * `State(repo)` - PcInfoRepositoryインスタンス
+/// * `Json(payload)` - ソフトウェア一覧リクエストDTO
+///
+/// # 戻り値
+/// * `Ok(Json<PcSoftwareResponse>)` - 成功時のレスポンス
+/// * `Err(ServerError)` - エラー時のレスポンス（未登録のPCの場合は404）
+pub async fn handle_pc_software(
+    State(repo): State<PcInfoRepository>,
+    Json(payload): Json<PcSoftwareRequest>,
+) -> Result<Json<PcSoftwareResponse>, ServerError> {
+    // バリデーション: UUID・各ソフトウェアの名前・バージョン・インストール日
+    payload.validate()?;
+
+    // UUIDでPCを検索
+    let pc_info = repo
+        .find_by_uuid(&payload.uuid)
+        .await
+        .map_err(ServerError::DatabaseError)?
+        .ok_or_else(|| ServerError::NotFound(format!("PC not registered: {}", payload.uuid)))?;
+
+    let (added, removed) = repo
+        .sync_software(pc_info.id, &payload)
+        .await
+        .map_err(ServerError::DatabaseError)?;
+
+    tracing::info!(
+        "Synced software. ID: {}, UUID: {}, full_sync: {}, added: {}, removed: {}",
+        pc_info.id,
+        payload.uuid,
+        payload.full_sync,
+        added,
+        removed
+    );
+
+    Ok(Json(PcSoftwareResponse::synced(pc_info.id, added, removed)))
+}
+
+/// GET /api/software エンドポイントハンドラー
+///
+/// 製品名の部分一致とバージョン比較でインストール情報を検索する。
+/// バージョン比較はデータベースではなくアプリケーション側で行う
+/// （"1.10" > "1.9" のような数値比較が必要なため）。
+/// データベースから SOFTWARE_SEARCH_PAGE_SIZE 件ずつ取得してバージョンで絞り込み、
+/// 一致した件数が limit（最大 MAX_SOFTWARE_SEARCH_LIMIT）を超えた場合は
+/// limit 件で打ち切ってレスポンスの truncated を true にする。
+///
+/// # 引数
+/// * `State(repo)` - PcInfoRepositoryインスタンス
+/// * `Query(query)` - 検索条件（name, version_op, version, limit）
+///
+/// # 戻り値
+/// * `Ok(Json<SoftwareSearchResponse>)` - 検索結果
+/// * `Err(ServerError)` - version_op と version の片方のみ指定された場合は400
+pub async fn handle_software_search(
+    State(repo): State<PcInfoRepository>,
+    Query(query): Query<SoftwareSearchQuery>,
+) -> Result<Json<SoftwareSearchResponse>, ServerError> {
+    let version_filter = match (query.version_op, query.version.as_deref()) {
+        (Some(op), Some(version)) if !version.trim().is_empty() => Some((op, version.trim())),
+        (None, None) => None,
+        _ => {
+            return Err(ServerError::InvalidRequest(
+                "version_op and version must be specified together".to_string(),
+            ));
+        }
+    };
+
+    let name = query.name.as_deref().map(str::trim).filter(|n| !n.is_empty());
+    let matches = |item: &SoftwareInstall| version_filter.is_none_or(|(op, version)| op.matches(&item.version, version));
+    let (items, truncated) = collect_matching(query.effective_limit(), SOFTWARE_SEARCH_PAGE_SIZE, matches, |after, limit| {
+        let repo = repo.clone();
+        async move { repo.search_software(name, after.as_ref(), limit).await }
+    })
+    .await
+    .map_err(ServerError::DatabaseError)?;
+
+    tracing::info!(
+        "Software search. name: {:?}, version: {:?}, results: {}, truncated: {}",
+        name, version_filter, items.len(), truncated
+    );
+
+    Ok(Json(SoftwareSearchResponse::new(items, truncated)))
+}
@@ -0,0 +1,47 @@
+use axum::{extract::State, Json};
+use crate::db::repository::PcInfoRepository;
+use pc_inventory_protocol::{PcUpdateRequest, PcUpdateResponse};
+use crate::error::ServerError;
+
+/// POST /api/pc-updates エンドポイントハンドラー
+///
+/// クライアントから送信されたビルドリビジョン（UBR）と更新プログラム一覧を受け取り、
+/// UUIDで特定したPCのWindows Update適用状況を更新する。
+/// 対象PCは事前に POST /api/pc-info で登録されている必要がある。
+///
+/// # 引数
+/// * `State(repo)` - PcInfoRepositoryインスタンス
+/// * `Json(payload)` - Windows Update適用状況リクエストDTO
+///
+/// # 戻り値
+/// * `Ok(Json<PcUpdateResponse>)` - 成功時のレスポンス
+/// * `Err(ServerError)` - エラー時のレスポンス（未登録のPCの場合は404）
+pub async fn handle_pc_updates(
+    State(repo): State<PcInfoRepository>,
+    Json(payload): Json<PcUpdateRequest>,
+) -> Result<Json<PcUpdateResponse>, ServerError> {
+    // バリデーション: UUID・各更新プログラムのIDとインストール日
+    payload.validate()?;
+
+    // UUIDでPCを検索
+    let pc_info = repo
+        .find_by_uuid(&payload.uuid)
+        .await
+        .map_err(ServerError::DatabaseError)?
+        .ok_or_else(|| ServerError::NotFound(format!("PC not registered: {}", payload.uuid)))?;
+
+    let hotfixes = repo
+        .sync_updates(pc_info.id, &payload)
+        .await
+        .map_err(ServerError::DatabaseError)?;
+
+    tracing::info!(
+        "Synced update status. ID: {}, UUID: {}, UBR: {:?}, hotfixes: {}",
+        pc_info.id,
+        payload.uuid,
+        payload.os_build_revision,
+        hotfixes
+    );
+
+    Ok(Json(PcUpdateResponse::synced(pc_info.id, hotfixes)))
+}
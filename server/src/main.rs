@@ -3,27 +3,67 @@ mod db;
 mod error;
 mod handlers;
 mod models;
+mod routes;
+mod storage;
+mod systemd;
 
-use axum::{routing::post, Router};
+use clap::Parser;
+use pc_inventory_protocol::{ApiVersion, CreateApiKeyRequest, ReleaseKey};
 use sqlx::mysql::MySqlPoolOptions;
 use std::time::Duration;
-use tower_http::trace::TraceLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use crate::config::ServerConfig;
 use crate::db::repository::PcInfoRepository;
-use crate::handlers::pc_info::handle_pc_info;
+use crate::storage::ReleaseStorage;
+use crate::systemd::Notification;
+
+/// PC情報収集サーバー
+#[derive(Parser)]
+#[command(about, version)]
+#[command(group(clap::ArgGroup::new("mode").args(["dry_run", "validate_config", "print_systemd_unit", "create_api_key"])))]
+struct Cli {
+    /// 設定ファイルのパス
+    #[arg(long, value_name = "PATH", env = "CONFIG_PATH", default_value = "config.toml")]
+    config: String,
+
+    /// ログレベル（config.toml・環境変数 RUST_LOG より優先）
+    #[arg(long, value_name = "LEVEL", value_parser = ["trace", "debug", "info", "warn", "error"])]
+    log_level: Option<String>,
+
+    /// データベースへの接続とポートの待ち受けを確認し、リクエストを受け付けずに終了
+    #[arg(long)]
+    dry_run: bool,
+
+    /// 設定ファイルを検証して終了
+    #[arg(long)]
+    validate_config: bool,
+
+    /// systemd のユニットファイルを出力して終了（Linux）
+    #[arg(long)]
+    print_systemd_unit: bool,
+
+    /// 管理用API（pc-inventory-admin）の API キーを作成して表示し、終了（最初のキーの作成に使用）
+    #[arg(long, value_name = "NAME")]
+    create_api_key: Option<String>,
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // 設定ファイルのパス（環境変数またはデフォルト）
-    let config_path = std::env::var("CONFIG_PATH").unwrap_or_else(|_| "config.toml".to_string());
+    let cli = Cli::parse();
+    if cli.print_systemd_unit {
+        return print_systemd_unit();
+    }
+    if cli.validate_config {
+        return validate_config(&cli.config);
+    }
 
     // 設定読み込み
+    let config_path = cli.config;
     let config = ServerConfig::load(&config_path)?;
 
     // ログ初期化
-    init_logging(&config);
+    init_logging(&config, cli.log_level.as_deref());
     tracing::info!("Starting PC Inventory Server...");
     tracing::info!("Configuration loaded from: {}", config_path);
 
@@ -40,34 +80,127 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // リポジトリ作成
     let repository = PcInfoRepository::new(pool);
 
+    if let Some(name) = cli.create_api_key {
+        return create_api_key(&repository, name).await;
+    }
+
+    // 配布用クライアントの保存先（[releases] セクションがある場合のみ）
+    let releases = config.releases.as_ref().map(ReleaseStorage::from_settings).transpose()?;
+
     // Axumルーター設定
-    let app = Router::new()
-        .route(&config.api.endpoint_path, post(handle_pc_info))
-        .layer(TraceLayer::new_for_http())
-        .with_state(repository);
+    let release_enabled = releases.is_some();
+    let app = routes::router(repository, &config.api.endpoint_path, releases, config.client_config.clone());
 
     // サーバーアドレス設定
     let addr = format!("{}:{}", config.server.host, config.server.port);
     tracing::info!("Server listening on {}", addr);
     tracing::info!("API endpoint: POST {}", config.api.endpoint_path);
+    tracing::info!("API endpoint: GET {}", config.api.endpoint_path);
+    tracing::info!("API endpoint: POST /api/pc-heartbeat");
+    tracing::info!("API endpoint: POST /api/pc-adapters");
+    tracing::info!("API endpoint: POST /api/pc-software");
+    tracing::info!("API endpoint: POST /api/pc-updates");
+    for version in ApiVersion::ALL {
+        tracing::info!("API endpoint: POST {}/{{pc-info,pc-heartbeat,pc-adapters,pc-software,pc-updates}}",
+            version.path_prefix());
+    }
+    tracing::info!("API endpoint: GET /api/capabilities");
+    tracing::info!("API endpoint: GET /api/software");
+    tracing::info!("API endpoint: GET /api/reports/software/{{name}}");
+    tracing::info!("API endpoint: GET /api/reports/updates");
+    tracing::info!("API endpoint: GET /api/reports/agents");
+    tracing::info!("API endpoint: GET,DELETE /api/admin/pcs[/{{id}}], POST /api/admin/pcs/{{id}}/merge (API key required)");
+    tracing::info!("API endpoint: GET,POST,DELETE /api/admin/api-keys[/{{id}}] (API key required)");
+    if release_enabled {
+        tracing::info!("API endpoint: POST /api/releases");
+        tracing::info!("API endpoint: GET /api/releases/latest");
+        tracing::info!("API endpoint: GET /api/releases/{{id}}/binary");
+    } else {
+        tracing::info!("Client self-update disabled ([releases] section not configured)");
+    }
+    if let Some(overrides) = config.client_config.overrides_for(&[]) {
+        tracing::info!("Client config pushed to all clients: {:?}", overrides);
+    }
+    for group in &config.client_config.groups {
+        tracing::info!("Client config pushed to tag '{}': {:?}", group.tag, group.overrides);
+    }
 
-    // サーバー起動
+    // サーバー起動（データベース接続とポートの待ち受けの後に起動完了を通知）
     let listener = tokio::net::TcpListener::bind(&addr).await?;
-    axum::serve(listener, app).await?;
+    if cli.dry_run {
+        tracing::info!("Dry run: database and listener checks passed, exiting without serving");
+        return Ok(());
+    }
+    systemd::notify(Notification::Ready);
+    // 待ち受け中のポートに要求し、応答がある間だけウォッチドッグに通知する
+    let probe_addr = systemd::probe_addr(listener.local_addr()?);
+    systemd::spawn_watchdog(move || systemd::probe(probe_addr));
+
+    axum::serve(listener, app)
+        .with_graceful_shutdown(systemd::shutdown_signal())
+        .await?;
+
+    tracing::info!("Server stopped");
+    Ok(())
+}
+
+/// 設定ファイルを検証
+///
+/// 起動時と同じ検証に加えて、[releases] の公開鍵の形式を確認します（データベースには接続しません）。
+fn validate_config(config_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let config = ServerConfig::load(config_path)?;
+    if let Some(releases) = &config.releases {
+        ReleaseKey::parse(&releases.public_key).map_err(|e| format!("releases.public_key: {}", e))?;
+    }
+
+    println!("Configuration OK: {}", config_path);
+    println!("  Listen address: {}:{}", config.server.host, config.server.port);
+    println!("  API endpoint: {}", config.api.endpoint_path);
+    println!("  Client self-update: {}", if config.releases.is_some() { "enabled" } else { "disabled" });
+    Ok(())
+}
+
+/// 管理用APIの API キーを作成して表示
+///
+/// キーはデータベースにハッシュのみ保存するため、表示された値を控えてください。
+async fn create_api_key(repository: &PcInfoRepository, name: String) -> Result<(), Box<dyn std::error::Error>> {
+    let created = handlers::admin::create_api_key(repository, &CreateApiKeyRequest { name }).await?;
+
+    println!("API key created (id: {}, name: {})", created.key.id, created.key.name);
+    println!("{}", created.secret);
+    eprintln!("Store the key now; it cannot be shown again. Use it with: PC_INVENTORY_API_KEY=<key> pc-inventory-admin ...");
+    Ok(())
+}
 
+/// 現在の実行ファイルを起動する systemd のユニットファイルを出力
+fn print_systemd_unit() -> Result<(), Box<dyn std::error::Error>> {
+    let executable = std::env::current_exe()?.canonicalize()?;
+    print!("{}", systemd::unit_file(&executable).render());
+    eprintln!("Save the output to /etc/systemd/system/{} and run: systemctl enable --now {}",
+        systemd::UNIT_NAME, systemd::UNIT_NAME);
     Ok(())
 }
 
 /// ログ初期化
-fn init_logging(config: &ServerConfig) {
+///
+/// systemd のサービスとして起動された場合は、標準出力の代わりに journald に出力します。
+/// ログレベルはコマンドライン（`level`）、環境変数 RUST_LOG、config.toml の順に優先します。
+fn init_logging(config: &ServerConfig, level: Option<&str>) {
     let file_appender = tracing_appender::rolling::never(".", &config.logging.file);
+    let journald = systemd::journald_layer();
+    let stdout = journald
+        .is_none()
+        .then(|| tracing_subscriber::fmt::layer().with_writer(std::io::stdout));
 
     tracing_subscriber::registry()
         .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| config.logging.level.clone().into()),
+            level
+                .map(tracing_subscriber::EnvFilter::new)
+                .or_else(|| tracing_subscriber::EnvFilter::try_from_default_env().ok())
+                .unwrap_or_else(|| config.logging.level.clone().into()),
         )
         .with(tracing_subscriber::fmt::layer().with_writer(file_appender))
-        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stdout))
+        .with(journald)
+        .with(stdout)
         .init();
 }
@@ -0,0 +1,157 @@
+use chrono::{DateTime, Utc};
+use pc_inventory_protocol::Section;
+use serde::Serialize;
+use sqlx::FromRow;
+use std::collections::BTreeMap;
+use crate::models::version::compare_versions;
+
+/// データベースから取得するPCごとのクライアントの情報
+#[derive(Debug, FromRow)]
+pub struct AgentInstall {
+    pub id: i32,
+    pub uuid: String,
+    pub user_name: Option<String>,
+    pub ip_address: Option<String>,
+    pub agent_version: Option<String>,
+    pub agent_build: Option<String>,
+    /// 収集できる項目（カンマ区切り）
+    pub agent_capabilities: Option<String>,
+    pub last_seen_at: Option<DateTime<Utc>>,
+}
+
+/// クライアントのバージョン別レポートに含めるPCの情報
+#[derive(Debug, Serialize)]
+pub struct AgentPc {
+    pub id: i32,
+    pub uuid: String,
+    pub user_name: Option<String>,
+    pub ip_address: Option<String>,
+    pub build_hash: Option<String>,
+    pub capabilities: Vec<String>,
+    pub last_seen_at: Option<DateTime<Utc>>,
+}
+
+/// バージョンごとのPC数
+#[derive(Debug, Serialize)]
+pub struct AgentVersionGroup {
+    /// クライアントのバージョン（None はバージョンを送信しない旧クライアント）
+    pub version: Option<String>,
+    pub count: usize,
+    pub pcs: Vec<AgentPc>,
+}
+
+/// クライアントのバージョン別レポートDTO（GET /api/reports/agents）
+#[derive(Debug, Serialize)]
+pub struct AgentVersionReport {
+    pub status: String,
+    pub total_pcs: usize,
+    /// 新しいバージョン順（バージョン不明のPCは最後）
+    pub versions: Vec<AgentVersionGroup>,
+}
+
+impl AgentVersionReport {
+    /// PCごとのクライアントの情報をバージョンごとに集計してレポートを生成
+    ///
+    /// # 引数
+    /// * `installs` - すべてのPCのクライアントの情報
+    pub fn from_installs(installs: Vec<AgentInstall>) -> Self {
+        let total_pcs = installs.len();
+
+        let mut grouped: BTreeMap<Option<String>, Vec<AgentPc>> = BTreeMap::new();
+        for install in installs {
+            grouped.entry(install.agent_version).or_default().push(AgentPc {
+                id: install.id,
+                uuid: install.uuid,
+                user_name: install.user_name,
+                ip_address: install.ip_address,
+                build_hash: install.agent_build,
+                capabilities: parse_capabilities(install.agent_capabilities.as_deref()),
+                last_seen_at: install.last_seen_at,
+            });
+        }
+
+        let mut versions: Vec<AgentVersionGroup> = grouped
+            .into_iter()
+            .map(|(version, pcs)| AgentVersionGroup {
+                version,
+                count: pcs.len(),
+                pcs,
+            })
+            .collect();
+        versions.sort_by(|a, b| match (&a.version, &b.version) {
+            (Some(a), Some(b)) => compare_versions(b, a),
+            (a, b) => b.is_some().cmp(&a.is_some()),
+        });
+
+        Self {
+            status: "success".to_string(),
+            total_pcs,
+            versions,
+        }
+    }
+}
+
+/// 収集できる項目を pc_info.agent_capabilities の形式（カンマ区切り）に変換
+pub fn format_capabilities(capabilities: &[Section]) -> String {
+    capabilities
+        .iter()
+        .map(Section::as_str)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// pc_info.agent_capabilities（カンマ区切り）を項目名の一覧に変換
+fn parse_capabilities(capabilities: Option<&str>) -> Vec<String> {
+    capabilities
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn install(id: i32, version: Option<&str>) -> AgentInstall {
+        AgentInstall {
+            id,
+            uuid: format!("uuid-{}", id),
+            user_name: None,
+            ip_address: None,
+            agent_version: version.map(str::to_string),
+            agent_build: version.map(|_| "1a2b3c4d5e6f".to_string()),
+            agent_capabilities: version.map(|_| "hardware,adapters,updates".to_string()),
+            last_seen_at: None,
+        }
+    }
+
+    #[test]
+    fn test_groups_by_version_newest_first() {
+        let report = AgentVersionReport::from_installs(vec![
+            install(1, Some("0.9.0")),
+            install(2, None),
+            install(3, Some("0.10.0")),
+            install(4, Some("0.9.0")),
+        ]);
+
+        assert_eq!(report.total_pcs, 4);
+        let versions: Vec<(Option<&str>, usize)> = report
+            .versions
+            .iter()
+            .map(|g| (g.version.as_deref(), g.count))
+            .collect();
+        assert_eq!(versions, vec![(Some("0.10.0"), 1), (Some("0.9.0"), 2), (None, 1)]);
+
+        assert_eq!(report.versions[0].pcs[0].capabilities, vec!["hardware", "adapters", "updates"]);
+        assert!(report.versions[2].pcs[0].capabilities.is_empty());
+    }
+
+    #[test]
+    fn test_format_capabilities() {
+        assert_eq!(format_capabilities(&[Section::Hardware, Section::Software]), "hardware,software");
+        assert_eq!(format_capabilities(&[]), "");
+    }
+}
@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use pc_inventory_protocol::release::sha256_hex;
+use pc_inventory_protocol::ApiKeyInfo;
+use sqlx::FromRow;
+
+/// API キーの接頭辞（ログや設定ファイルに誤って記載された場合に見分けるため）
+pub const API_KEY_PREFIX: &str = "pcinv_";
+
+/// データベースから取得する API キーのモデル（api_keys、ハッシュを除く）
+#[derive(Debug, Clone, FromRow)]
+pub struct ApiKey {
+    pub id: i32,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl From<ApiKey> for ApiKeyInfo {
+    fn from(key: ApiKey) -> Self {
+        Self {
+            id: key.id,
+            name: key.name,
+            created_at: key.created_at,
+            last_used_at: key.last_used_at,
+        }
+    }
+}
+
+/// 新しい API キーを作成
+///
+/// # 戻り値
+/// API キー（"pcinv_" + 32バイトの乱数の16進表記）
+///
+/// # エラー
+/// 乱数を取得できなかった場合
+pub fn generate_secret() -> Result<String, getrandom::Error> {
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes)?;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    Ok(format!("{}{}", API_KEY_PREFIX, hex))
+}
+
+/// データベースに保存する API キーのハッシュ（SHA-256、16進表記）
+pub fn hash_secret(secret: &str) -> String {
+    sha256_hex(secret.as_bytes())
+}
+
+/// Authorization ヘッダーから API キーを取り出す
+///
+/// # 戻り値
+/// "Bearer <キー>" 形式の場合はキー（それ以外は None）
+pub fn bearer_token(authorization: &str) -> Option<&str> {
+    let (scheme, token) = authorization.trim().split_once(' ')?;
+    let token = token.trim();
+    (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty()).then_some(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_secret() {
+        let secret = generate_secret().unwrap();
+        assert!(secret.starts_with(API_KEY_PREFIX));
+        assert_eq!(secret.len(), API_KEY_PREFIX.len() + 64);
+        assert_ne!(secret, generate_secret().unwrap());
+        assert_eq!(hash_secret(&secret).len(), 64);
+    }
+
+    #[test]
+    fn test_bearer_token() {
+        assert_eq!(bearer_token("Bearer pcinv_abc"), Some("pcinv_abc"));
+        assert_eq!(bearer_token("bearer  pcinv_abc "), Some("pcinv_abc"));
+        assert_eq!(bearer_token("Basic dXNlcjpwYXNz"), None);
+        assert_eq!(bearer_token("Bearer "), None);
+        assert_eq!(bearer_token("pcinv_abc"), None);
+    }
+}
@@ -1 +1,7 @@
+pub mod agent;
+pub mod api_key;
 pub mod pc_info;
+pub mod release;
+pub mod software;
+pub mod update;
+pub mod version;
@@ -1,9 +1,11 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use pc_inventory_protocol::{HistoryReason, PcHistoryEntry, PcInfoRequest, PcRecord};
+use crate::models::agent::format_capabilities;
 
 /// データベースから取得するPC情報のモデル
-#[derive(Debug, FromRow)]
+#[derive(Debug, Serialize, FromRow)]
 pub struct PcInfo {
     pub id: i32,
     pub uuid: String,
@@ -11,50 +13,228 @@ pub struct PcInfo {
     pub network_type: Option<String>,
     pub user_name: Option<String>,
     pub ip_address: Option<String>,
+    pub ipv6_address: Option<String>,
     pub os: Option<String>,
     pub os_version: Option<String>,
     pub model_name: Option<String>,
+    pub manufacturer: Option<String>,
+    /// 物理メモリの合計（バイト）
+    pub total_memory_bytes: Option<u64>,
+    pub logical_processors: Option<u32>,
+    /// クライアントのバージョン
+    pub agent_version: Option<String>,
+    /// クライアントのビルドハッシュ
+    pub agent_build: Option<String>,
+    /// クライアントが収集できる項目（カンマ区切り）
+    pub agent_capabilities: Option<String>,
     pub created_at: DateTime<Utc>,
+    /// 最終更新日時（いずれかの項目の値が変化した日時）
     pub updated_at: DateTime<Utc>,
+    /// 最終接触日時（PC情報の送信・ハートビートを受信した日時）
+    pub last_seen_at: Option<DateTime<Utc>>,
 }
 
-/// クライアントからのリクエストDTO
-#[derive(Debug, Deserialize)]
-pub struct PcInfoRequest {
+/// データベースから取得するPC情報の変更履歴のモデル（pc_info_history）
+#[derive(Debug, FromRow)]
+pub struct PcHistory {
     pub uuid: String,
-    pub mac_address: String,
-    pub network_type: String,
-    pub user_name: String,
-    pub ip_address: String,
-    pub os: String,
-    pub os_version: String,
-    pub model_name: String,
+    pub mac_address: Option<String>,
+    pub network_type: Option<String>,
+    pub user_name: Option<String>,
+    pub ip_address: Option<String>,
+    pub ipv6_address: Option<String>,
+    pub os: Option<String>,
+    pub os_version: Option<String>,
+    pub model_name: Option<String>,
+    pub agent_version: Option<String>,
+    /// 記録の理由（"updated" / "merged"）
+    pub reason: String,
+    pub valid_from: DateTime<Utc>,
+    pub recorded_at: DateTime<Utc>,
 }
 
-/// API レスポンスDTO（成功時）
+/// GET /api/pc-info のクエリパラメータ
+///
+/// 例: `/api/pc-info?ip=2001:db8::10`、`/api/pc-info?ip=192.168.1.`
+#[derive(Debug, Deserialize)]
+pub struct PcInfoSearchQuery {
+    /// IPアドレス（完全なアドレスの場合は完全一致、それ以外は前方一致）
+    pub ip: Option<String>,
+}
+
+/// PC情報検索レスポンスDTO
 #[derive(Debug, Serialize)]
-pub struct PcInfoResponse {
+pub struct PcInfoSearchResponse {
     pub status: String,
-    pub action: String, // "created" or "updated"
-    pub id: i32,
+    pub count: usize,
+    pub items: Vec<PcInfo>,
+}
+
+impl PcInfo {
+    /// リクエストの内容が保存済みの値と異なるか
+    ///
+    /// normalize_addresses で正規化済みのリクエストと比較すること。
+    /// ハードウェア情報・クライアントの情報が送信されなかった場合、それらは比較しない。
+    pub fn differs_from(&self, request: &PcInfoRequest) -> bool {
+        self.mac_address.as_deref() != Some(request.mac_address.as_str())
+            || self.network_type.as_deref() != Some(request.network_type.as_str())
+            || self.user_name.as_deref() != Some(request.user_name.as_str())
+            || self.ip_address.as_deref() != Some(request.ip_address.as_str())
+            || self.ipv6_address != request.ipv6_address
+            || self.os.as_deref() != Some(request.os.as_str())
+            || self.os_version.as_deref() != Some(request.os_version.as_str())
+            || self.model_name.as_deref() != Some(request.model_name.as_str())
+            || request.hardware.as_ref().is_some_and(|hardware| {
+                self.manufacturer != hardware.manufacturer
+                    || self.total_memory_bytes != hardware.total_memory_bytes
+                    || self.logical_processors != hardware.logical_processors
+            })
+            || request.agent.as_ref().is_some_and(|agent| {
+                self.agent_version.as_deref() != Some(agent.version.as_str())
+                    || self.agent_build != agent.build_hash
+                    || self.agent_capabilities.as_deref() != Some(format_capabilities(&agent.capabilities).as_str())
+            })
+    }
 }
 
-impl PcInfoResponse {
-    /// 新規作成時のレスポンスを生成
-    pub fn created(id: i32) -> Self {
+impl From<PcInfo> for PcRecord {
+    fn from(pc: PcInfo) -> Self {
         Self {
-            status: "success".to_string(),
-            action: "created".to_string(),
-            id,
+            id: pc.id,
+            uuid: pc.uuid,
+            mac_address: pc.mac_address,
+            network_type: pc.network_type,
+            user_name: pc.user_name,
+            ip_address: pc.ip_address,
+            ipv6_address: pc.ipv6_address,
+            os: pc.os,
+            os_version: pc.os_version,
+            model_name: pc.model_name,
+            manufacturer: pc.manufacturer,
+            total_memory_bytes: pc.total_memory_bytes,
+            logical_processors: pc.logical_processors,
+            agent_version: pc.agent_version,
+            created_at: pc.created_at,
+            updated_at: pc.updated_at,
+            last_seen_at: pc.last_seen_at,
         }
     }
+}
 
-    /// 更新時のレスポンスを生成
-    pub fn updated(id: i32) -> Self {
+impl From<PcHistory> for PcHistoryEntry {
+    fn from(history: PcHistory) -> Self {
+        Self {
+            uuid: history.uuid,
+            mac_address: history.mac_address,
+            network_type: history.network_type,
+            user_name: history.user_name,
+            ip_address: history.ip_address,
+            ipv6_address: history.ipv6_address,
+            os: history.os,
+            os_version: history.os_version,
+            model_name: history.model_name,
+            agent_version: history.agent_version,
+            reason: if history.reason == HistoryReason::Merged.as_str() {
+                HistoryReason::Merged
+            } else {
+                HistoryReason::Updated
+            },
+            valid_from: history.valid_from,
+            recorded_at: history.recorded_at,
+        }
+    }
+}
+
+impl PcInfoSearchResponse {
+    /// 検索結果からレスポンスを生成
+    pub fn new(items: Vec<PcInfo>) -> Self {
         Self {
             status: "success".to_string(),
-            action: "updated".to_string(),
-            id,
+            count: items.len(),
+            items,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pc_inventory_protocol::testing::pc_info_request as request;
+    use pc_inventory_protocol::{AgentInfo, HardwareInfo, Section};
+
+    /// リクエストの内容を保存した状態のPC情報（ハードウェア情報・クライアントの情報は保存済みの値）
+    fn stored(req: &PcInfoRequest) -> PcInfo {
+        let now = Utc::now();
+        PcInfo {
+            id: 1,
+            uuid: req.uuid.clone(),
+            mac_address: Some(req.mac_address.clone()),
+            network_type: Some(req.network_type.to_string()),
+            user_name: Some(req.user_name.clone()),
+            ip_address: Some(req.ip_address.clone()),
+            ipv6_address: req.ipv6_address.clone(),
+            os: Some(req.os.clone()),
+            os_version: Some(req.os_version.clone()),
+            model_name: Some(req.model_name.clone()),
+            manufacturer: Some("Dell Inc.".to_string()),
+            total_memory_bytes: Some(17_179_869_184),
+            logical_processors: Some(8),
+            agent_version: Some("0.1.0".to_string()),
+            agent_build: None,
+            agent_capabilities: Some("hardware,updates".to_string()),
+            created_at: now,
+            updated_at: now,
+            last_seen_at: Some(now),
         }
     }
+
+    #[test]
+    fn test_differs_from_detects_field_changes() {
+        let mut req = request("2001:DB8::10", Some("2001:db8::10"));
+        req.normalize_addresses().unwrap();
+        assert_eq!(req.ip_address, "2001:db8::10");
+        let stored = stored(&req);
+        assert!(!stored.differs_from(&req));
+
+        req.user_name = "otheruser".to_string();
+        assert!(stored.differs_from(&req));
+
+        req.user_name = "testuser".to_string();
+        req.ipv6_address = None;
+        assert!(stored.differs_from(&req));
+    }
+
+    #[test]
+    fn test_differs_from_ignores_unreported_sections() {
+        let mut req = request("192.168.1.100", None);
+        let stored = stored(&req);
+        // v1 のクライアントはハードウェア情報を送信しない
+        assert!(!stored.differs_from(&req));
+
+        let mut hardware = HardwareInfo {
+            manufacturer: Some("Dell Inc.".to_string()),
+            total_memory_bytes: Some(17_179_869_184),
+            logical_processors: Some(8),
+        };
+        req.hardware = Some(hardware.clone());
+        assert!(!stored.differs_from(&req));
+
+        hardware.total_memory_bytes = Some(34_359_738_368);
+        req.hardware = Some(hardware);
+        assert!(stored.differs_from(&req));
+
+        // クライアントの更新（バージョンの変化）は内容の変化として扱う
+        req.hardware = None;
+        let mut agent = AgentInfo {
+            version: "0.1.0".to_string(),
+            build_hash: None,
+            capabilities: vec![Section::Hardware, Section::Updates],
+        };
+        req.agent = Some(agent.clone());
+        assert!(!stored.differs_from(&req));
+
+        agent.version = "0.2.0".to_string();
+        req.agent = Some(agent);
+        assert!(stored.differs_from(&req));
+    }
 }
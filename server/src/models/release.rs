@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use pc_inventory_protocol::ReleaseInfo;
+use serde::Deserialize;
+use sqlx::FromRow;
+use crate::models::version::compare_versions;
+
+/// データベースから取得する配布用クライアントの情報（client_releases）
+#[derive(Debug, Clone, FromRow)]
+pub struct ClientRelease {
+    pub id: i32,
+    pub channel: String,
+    pub target: String,
+    pub version: String,
+    pub sha256: String,
+    pub size: u64,
+    pub signature: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ClientRelease> for ReleaseInfo {
+    fn from(release: ClientRelease) -> Self {
+        Self {
+            id: release.id,
+            channel: release.channel,
+            target: release.target,
+            version: release.version,
+            sha256: release.sha256,
+            size: release.size,
+            signature: release.signature,
+            published_at: release.created_at,
+        }
+    }
+}
+
+/// GET /api/releases/latest のクエリパラメータ
+#[derive(Debug, Deserialize)]
+pub struct LatestReleaseQuery {
+    /// 配布チャネル（例: "stable"）
+    pub channel: String,
+    /// 実行環境のターゲット（例: "x86_64-pc-windows-msvc"）
+    pub target: String,
+}
+
+/// 最新のバージョンを選択
+///
+/// 登録順ではなくバージョン番号で比較するため、
+/// 修正版を後から登録しても新しいバージョンが優先されます。
+pub fn latest_release(releases: Vec<ClientRelease>) -> Option<ClientRelease> {
+    releases
+        .into_iter()
+        .max_by(|a, b| compare_versions(&a.version, &b.version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(id: i32, version: &str) -> ClientRelease {
+        ClientRelease {
+            id,
+            channel: "stable".to_string(),
+            target: "x86_64-pc-windows-msvc".to_string(),
+            version: version.to_string(),
+            sha256: "0".repeat(64),
+            size: 0,
+            signature: String::new(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_latest_release_compares_versions() {
+        let releases = vec![release(1, "0.10.0"), release(2, "0.9.1"), release(3, "0.10.0-rc1")];
+        assert_eq!(latest_release(releases).unwrap().id, 1);
+        assert!(latest_release(Vec::new()).is_none());
+    }
+}
@@ -0,0 +1,308 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::BTreeMap;
+use std::future::Future;
+use crate::models::version::{compare_versions, VersionOp};
+
+/// ソフトウェア検索で返すインストール情報の最大件数
+pub const MAX_SOFTWARE_SEARCH_LIMIT: u32 = 10_000;
+
+/// ソフトウェア検索で一度にデータベースから取得するインストール情報の件数
+pub const SOFTWARE_SEARCH_PAGE_SIZE: u32 = 1_000;
+
+/// データベースから取得するインストール情報（pc_software と pc_info の結合結果）
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct SoftwareInstall {
+    pub pc_info_id: i32,
+    pub uuid: String,
+    pub user_name: Option<String>,
+    pub ip_address: Option<String>,
+    /// PC情報の最終更新日時（pc_info.updated_at）
+    pub updated_at: DateTime<Utc>,
+    /// PCの最終接触日時（pc_info.last_seen_at）
+    pub last_seen_at: Option<DateTime<Utc>>,
+    pub name: String,
+    pub version: String,
+    pub publisher: Option<String>,
+    pub install_date: Option<NaiveDate>,
+}
+
+/// ソフトウェア検索の続きの位置（製品名・PC ID・バージョン順で最後に取得したインストール情報）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SoftwareCursor {
+    pub name: String,
+    pub pc_info_id: i32,
+    pub version: String,
+}
+
+impl From<&SoftwareInstall> for SoftwareCursor {
+    fn from(install: &SoftwareInstall) -> Self {
+        Self {
+            name: install.name.clone(),
+            pc_info_id: install.pc_info_id,
+            version: install.version.clone(),
+        }
+    }
+}
+
+/// GET /api/software のクエリパラメータ
+///
+/// 例: `/api/software?name=Chrome&version_op=lt&version=120&limit=500`
+#[derive(Debug, Deserialize)]
+pub struct SoftwareSearchQuery {
+    /// 製品名の部分一致フィルタ
+    pub name: Option<String>,
+    /// バージョン比較演算子（eq, ne, lt, le, gt, ge）
+    pub version_op: Option<VersionOp>,
+    /// 比較対象のバージョン
+    pub version: Option<String>,
+    /// 返す最大件数（None の場合は MAX_SOFTWARE_SEARCH_LIMIT）
+    pub limit: Option<u32>,
+}
+
+impl SoftwareSearchQuery {
+    /// 最大件数（MAX_SOFTWARE_SEARCH_LIMIT を超える場合は MAX_SOFTWARE_SEARCH_LIMIT）
+    pub fn effective_limit(&self) -> u32 {
+        self.limit.unwrap_or(MAX_SOFTWARE_SEARCH_LIMIT).min(MAX_SOFTWARE_SEARCH_LIMIT)
+    }
+}
+
+/// ソフトウェア検索レスポンスDTO
+#[derive(Debug, Serialize)]
+pub struct SoftwareSearchResponse {
+    pub status: String,
+    pub count: usize,
+    /// 最大件数に達したため、検索結果の一部のみを返した場合は true
+    pub truncated: bool,
+    pub items: Vec<SoftwareInstall>,
+}
+
+/// バージョン分布レポートに含めるPCの概要
+#[derive(Debug, Serialize)]
+pub struct PcSummary {
+    pub id: i32,
+    pub uuid: String,
+    pub user_name: Option<String>,
+    pub ip_address: Option<String>,
+    pub updated_at: DateTime<Utc>,
+    pub last_seen_at: Option<DateTime<Utc>>,
+}
+
+/// バージョンごとのインストール数
+#[derive(Debug, Serialize)]
+pub struct VersionDistribution {
+    pub version: String,
+    pub count: usize,
+    pub pcs: Vec<PcSummary>,
+}
+
+/// ソフトウェアのバージョン分布レポートDTO
+#[derive(Debug, Serialize)]
+pub struct SoftwareVersionReport {
+    pub status: String,
+    pub name: String,
+    pub total_installs: usize,
+    /// 新しいバージョン順
+    pub versions: Vec<VersionDistribution>,
+}
+
+impl SoftwareSearchResponse {
+    /// 検索結果からレスポンスを生成
+    ///
+    /// # 引数
+    /// * `items` - 検索結果
+    /// * `truncated` - 最大件数に達して打ち切ったか
+    pub fn new(items: Vec<SoftwareInstall>, truncated: bool) -> Self {
+        Self {
+            status: "success".to_string(),
+            count: items.len(),
+            truncated,
+            items,
+        }
+    }
+}
+
+/// インストール情報をページ単位で取得し、条件に一致するものを最大件数まで集める
+///
+/// バージョン比較はデータベースで行えないため、最大件数で打ち切る前に条件で絞り込む。
+/// 最大件数を超えて一致した場合、または最後のページまで取得した場合に終了する。
+///
+/// # 引数
+/// * `limit` - 最大件数
+/// * `page_size` - 一度に取得する件数
+/// * `matches` - 絞り込みの条件
+/// * `fetch_page` - 続きの位置（None の場合は先頭）と件数を受け取り、製品名・PC ID・バージョン順に取得する
+///
+/// # 戻り値
+/// * `Ok((Vec<SoftwareInstall>, bool))` - 条件に一致したインストール情報と、最大件数で打ち切ったか
+/// * `Err(E)` - 取得に失敗した場合
+pub async fn collect_matching<M, F, Fut, E>(
+    limit: u32,
+    page_size: u32,
+    matches: M,
+    mut fetch_page: F,
+) -> Result<(Vec<SoftwareInstall>, bool), E>
+where
+    M: Fn(&SoftwareInstall) -> bool,
+    F: FnMut(Option<SoftwareCursor>, u32) -> Fut,
+    Fut: Future<Output = Result<Vec<SoftwareInstall>, E>>,
+{
+    let limit = limit as usize;
+    let mut items = Vec::new();
+    let mut cursor = None;
+
+    loop {
+        let page = fetch_page(cursor.take(), page_size).await?;
+        let last_page = page.len() < page_size as usize;
+        cursor = page.last().map(SoftwareCursor::from);
+        items.extend(page.into_iter().filter(|install| matches(install)));
+
+        if last_page || items.len() > limit {
+            break;
+        }
+    }
+
+    let truncated = items.len() > limit;
+    items.truncate(limit);
+    Ok((items, truncated))
+}
+
+impl SoftwareVersionReport {
+    /// インストール情報をバージョンごとに集計してレポートを生成
+    ///
+    /// # 引数
+    /// * `name` - ソフトウェア名
+    /// * `installs` - 対象ソフトウェアのインストール情報
+    pub fn from_installs(name: String, installs: Vec<SoftwareInstall>) -> Self {
+        let total_installs = installs.len();
+
+        let mut grouped: BTreeMap<String, Vec<PcSummary>> = BTreeMap::new();
+        for install in installs {
+            grouped.entry(install.version).or_default().push(PcSummary {
+                id: install.pc_info_id,
+                uuid: install.uuid,
+                user_name: install.user_name,
+                ip_address: install.ip_address,
+                updated_at: install.updated_at,
+                last_seen_at: install.last_seen_at,
+            });
+        }
+
+        let mut versions: Vec<VersionDistribution> = grouped
+            .into_iter()
+            .map(|(version, pcs)| VersionDistribution {
+                version,
+                count: pcs.len(),
+                pcs,
+            })
+            .collect();
+        versions.sort_by(|a, b| compare_versions(&b.version, &a.version));
+
+        Self {
+            status: "success".to_string(),
+            name,
+            total_installs,
+            versions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn install(pc_info_id: i32, version: &str) -> SoftwareInstall {
+        SoftwareInstall {
+            pc_info_id,
+            uuid: format!("uuid-{}", pc_info_id),
+            user_name: None,
+            ip_address: None,
+            updated_at: Utc::now(),
+            last_seen_at: None,
+            name: "Google Chrome".to_string(),
+            version: version.to_string(),
+            publisher: None,
+            install_date: None,
+        }
+    }
+
+    #[test]
+    fn test_software_search_query_effective_limit() {
+        let query = |limit| SoftwareSearchQuery { name: None, version_op: None, version: None, limit };
+        assert_eq!(query(None).effective_limit(), MAX_SOFTWARE_SEARCH_LIMIT);
+        assert_eq!(query(Some(500)).effective_limit(), 500);
+        assert_eq!(query(Some(u32::MAX)).effective_limit(), MAX_SOFTWARE_SEARCH_LIMIT);
+    }
+
+    /// pc_software の代わりに、製品名・PC ID・バージョン順の一覧から続きの位置以降を返す
+    fn fetch_page(
+        installs: &[SoftwareInstall],
+        after: Option<SoftwareCursor>,
+        page_size: u32,
+        fetched: &mut Vec<Option<i32>>,
+    ) -> std::future::Ready<Result<Vec<SoftwareInstall>, ()>> {
+        fetched.push(after.as_ref().map(|cursor| cursor.pc_info_id));
+        std::future::ready(Ok(installs
+            .iter()
+            .filter(|install| after.as_ref().is_none_or(|cursor| install.pc_info_id > cursor.pc_info_id))
+            .take(page_size as usize)
+            .cloned()
+            .collect()))
+    }
+
+    #[tokio::test]
+    async fn test_collect_matching_filters_before_limit() {
+        // 先頭の 25 台は新しいバージョンで、古いバージョンは 26 台目以降のみ
+        let installs: Vec<SoftwareInstall> = (1..=40)
+            .map(|id| install(id, if id <= 25 { "120.0.6099.130" } else { "99.0.4844.84" }))
+            .collect();
+        let older = |install: &SoftwareInstall| VersionOp::Lt.matches(&install.version, "120");
+
+        // 一致するのは 15 台で、最大件数（10）で打ち切る
+        let mut fetched = Vec::new();
+        let (items, truncated) = collect_matching(10, 10, older, |after, size| fetch_page(&installs, after, size, &mut fetched))
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 10);
+        assert!(truncated);
+        assert_eq!(items[0].pc_info_id, 26);
+        assert_eq!(fetched, vec![None, Some(10), Some(20), Some(30)]);
+
+        // 一致したものをすべて返す場合は truncated にならない
+        let mut fetched = Vec::new();
+        let (items, truncated) = collect_matching(15, 10, older, |after, size| fetch_page(&installs, after, size, &mut fetched))
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 15);
+        assert!(!truncated);
+        assert_eq!(items.last().unwrap().pc_info_id, 40);
+        assert_eq!(fetched, vec![None, Some(10), Some(20), Some(30), Some(40)]);
+
+        // 条件が無い場合は最大件数の次の 1 件まで取得して終了する
+        let mut fetched = Vec::new();
+        let (items, truncated) = collect_matching(10, 11, |_: &SoftwareInstall| true, |after, size| {
+            fetch_page(&installs, after, size, &mut fetched)
+        })
+        .await
+        .unwrap();
+        assert_eq!(items.len(), 10);
+        assert!(truncated);
+        assert_eq!(fetched, vec![None]);
+    }
+
+    #[test]
+    fn test_version_report_groups_and_sorts() {
+        let report = SoftwareVersionReport::from_installs(
+            "Google Chrome".to_string(),
+            vec![install(1, "99.0.4844.84"), install(2, "120.0.6099.130"), install(3, "99.0.4844.84")],
+        );
+
+        assert_eq!(report.total_installs, 3);
+        assert_eq!(report.versions.len(), 2);
+        assert_eq!(report.versions[0].version, "120.0.6099.130");
+        assert_eq!(report.versions[1].version, "99.0.4844.84");
+        assert_eq!(report.versions[1].count, 2);
+        assert_eq!(report.versions[1].pcs[0].id, 1);
+    }
+}
@@ -0,0 +1,82 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// GET /api/reports/updates のクエリパラメータ
+///
+/// 指定した条件はすべて AND で評価される。
+/// 例: `/api/reports/updates?missing_kb=KB5034441&os_version=10.0.19045`
+#[derive(Debug, Deserialize)]
+pub struct UpdateReportQuery {
+    /// この更新プログラムが適用されていないPCを抽出
+    pub missing_kb: Option<String>,
+    /// ビルドリビジョン（UBR）がこの値未満のPCを抽出（UBR が不明なPCを含む）
+    pub below_revision: Option<u32>,
+    /// 対象をこのOSバージョン（例: "10.0.19045"）に限定
+    pub os_version: Option<String>,
+}
+
+/// レポートに含めるPCの情報
+#[derive(Debug, Serialize, FromRow)]
+pub struct UpdateReportEntry {
+    pub id: i32,
+    pub uuid: String,
+    pub user_name: Option<String>,
+    pub ip_address: Option<String>,
+    pub os: Option<String>,
+    pub os_version: Option<String>,
+    pub os_build_revision: Option<u32>,
+    /// PC情報の最終更新日時
+    pub updated_at: DateTime<Utc>,
+    /// PCの最終接触日時
+    pub last_seen_at: Option<DateTime<Utc>>,
+}
+
+/// Windows Update適用状況レポートDTO
+#[derive(Debug, Serialize)]
+pub struct UpdateReport {
+    pub status: String,
+    pub count: usize,
+    /// ビルドリビジョン（UBR）が不明なPCの台数（pcs に含まれる）
+    pub unknown_revision: usize,
+    pub pcs: Vec<UpdateReportEntry>,
+}
+
+impl UpdateReport {
+    /// 抽出結果からレポートを生成
+    pub fn new(pcs: Vec<UpdateReportEntry>) -> Self {
+        Self {
+            status: "success".to_string(),
+            count: pcs.len(),
+            unknown_revision: pcs.iter().filter(|pc| pc.os_build_revision.is_none()).count(),
+            pcs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: i32, os_build_revision: Option<u32>) -> UpdateReportEntry {
+        UpdateReportEntry {
+            id,
+            uuid: format!("uuid-{}", id),
+            user_name: None,
+            ip_address: None,
+            os: None,
+            os_version: Some("10.0.19045".to_string()),
+            os_build_revision,
+            updated_at: Utc::now(),
+            last_seen_at: None,
+        }
+    }
+
+    #[test]
+    fn test_update_report_counts_unknown_revision() {
+        let report = UpdateReport::new(vec![entry(1, Some(3803)), entry(2, None), entry(3, None)]);
+
+        assert_eq!(report.count, 3);
+        assert_eq!(report.unknown_revision, 2);
+    }
+}
@@ -0,0 +1,46 @@
+use serde::Deserialize;
+use std::cmp::Ordering;
+
+/// バージョン文字列の比較（クライアントと共通）
+pub use pc_inventory_protocol::compare_versions;
+
+/// バージョン比較演算子（クエリパラメータ version_op）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl VersionOp {
+    /// `left <op> right` を評価
+    pub fn matches(self, left: &str, right: &str) -> bool {
+        let ordering = compare_versions(left, right);
+        match self {
+            VersionOp::Eq => ordering == Ordering::Equal,
+            VersionOp::Ne => ordering != Ordering::Equal,
+            VersionOp::Lt => ordering == Ordering::Less,
+            VersionOp::Le => ordering != Ordering::Greater,
+            VersionOp::Gt => ordering == Ordering::Greater,
+            VersionOp::Ge => ordering != Ordering::Less,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_op_matches() {
+        assert!(VersionOp::Lt.matches("119.0.6045.200", "120"));
+        assert!(!VersionOp::Lt.matches("120.0", "120"));
+        assert!(VersionOp::Le.matches("120.0", "120"));
+        assert!(VersionOp::Ge.matches("120.0.1", "120"));
+        assert!(VersionOp::Ne.matches("1.0", "1.1"));
+    }
+}
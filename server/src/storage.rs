@@ -0,0 +1,77 @@
+use pc_inventory_protocol::ReleaseKey;
+use std::fs;
+use std::path::PathBuf;
+use crate::config::ReleaseSettings;
+use crate::error::{Result, ServerError};
+
+/// 配布用クライアントの実行ファイルの保存先
+///
+/// 実行ファイルは "<storage_dir>/<SHA-256>.bin" に保存し、
+/// バージョンなどの情報はデータベース（client_releases）で管理します。
+/// アップロードされた実行ファイルは、設定した公開鍵で署名を検証してから保存します。
+pub struct ReleaseStorage {
+    dir: PathBuf,
+    key: ReleaseKey,
+    max_upload_bytes: usize,
+}
+
+impl ReleaseStorage {
+    /// 設定から作成（保存先ディレクトリが無い場合は作成）
+    ///
+    /// # エラー
+    /// 公開鍵の形式が不正、またはディレクトリを作成できない場合
+    pub fn from_settings(settings: &ReleaseSettings) -> Result<Self> {
+        let key = ReleaseKey::parse(&settings.public_key)
+            .map_err(|e| ServerError::ConfigError(format!("releases.public_key: {}", e)))?;
+
+        let dir = PathBuf::from(&settings.storage_dir);
+        fs::create_dir_all(&dir)?;
+
+        Ok(Self {
+            dir,
+            key,
+            max_upload_bytes: (settings.max_upload_mb * 1024 * 1024) as usize,
+        })
+    }
+
+    /// 署名の検証に使用する公開鍵
+    pub fn key(&self) -> &ReleaseKey {
+        &self.key
+    }
+
+    /// アップロードできる実行ファイルの最大サイズ（バイト）
+    pub fn max_upload_bytes(&self) -> usize {
+        self.max_upload_bytes
+    }
+
+    /// 実行ファイルを保存（一時ファイルに書き込んでから置き換え）
+    ///
+    /// # 引数
+    /// * `sha256` - 実行ファイルのSHA-256（ファイル名に使用）
+    /// * `binary` - 実行ファイル
+    pub async fn save(&self, sha256: &str, binary: &[u8]) -> Result<()> {
+        let path = self.path_for(sha256);
+        let temp_path = path.with_extension("tmp");
+
+        tokio::fs::write(&temp_path, binary).await?;
+        if let Err(e) = tokio::fs::rename(&temp_path, &path).await {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            return Err(e.into());
+        }
+
+        tracing::debug!("Release binary saved: {}", path.display());
+        Ok(())
+    }
+
+    /// 実行ファイルを読み込み
+    ///
+    /// # 引数
+    /// * `sha256` - 実行ファイルのSHA-256
+    pub async fn read(&self, sha256: &str) -> Result<Vec<u8>> {
+        Ok(tokio::fs::read(self.path_for(sha256)).await?)
+    }
+
+    fn path_for(&self, sha256: &str) -> PathBuf {
+        self.dir.join(format!("{}.bin", sha256))
+    }
+}
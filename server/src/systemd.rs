@@ -0,0 +1,117 @@
+//! systemd との連携（Linux）
+//!
+//! 起動完了はデータベース接続とポートの待ち受けの後に通知します。
+//! ウォッチドッグは、待ち受け中のポートへの要求（GET /api/capabilities）に応答がある間だけ通知します。
+//! 通知・ウォッチドッグ・journald の処理はクライアントと共通（pc-inventory-systemd）です。
+
+pub use pc_inventory_systemd::{journald_layer, notify, spawn_watchdog, Notification, UnitFile};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::Path;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// ユニット名（--print-systemd-unit の出力の保存先）
+pub const UNIT_NAME: &str = "pc-inventory-server.service";
+
+/// ユニットファイルに設定するウォッチドッグのタイムアウト
+const WATCHDOG_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Ctrl+C または SIGTERM（systemctl stop）を受信するまで待機し、停止開始を通知
+pub async fn shutdown_signal() {
+    pc_inventory_systemd::shutdown_signal().await;
+    tracing::info!("Shutdown requested, waiting for in-flight requests");
+    notify(Notification::Stopping);
+}
+
+/// ウォッチドッグの確認で接続するアドレス
+///
+/// すべてのアドレスで待ち受けている場合（0.0.0.0、::）はループバックアドレスに接続します。
+pub fn probe_addr(local_addr: SocketAddr) -> SocketAddr {
+    let ip = match local_addr.ip() {
+        IpAddr::V4(ip) if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        IpAddr::V6(ip) if ip.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+        ip => ip,
+    };
+    SocketAddr::new(ip, local_addr.port())
+}
+
+/// 待ち受け中のポートに GET /api/capabilities を要求し、200 が返るか確認
+///
+/// # 戻り値
+/// 応答があった場合は true（接続・送受信に失敗した場合、200 以外の場合は false）
+pub async fn probe(addr: SocketAddr) -> bool {
+    let result = async {
+        let mut stream = TcpStream::connect(addr).await?;
+        stream
+            .write_all(b"GET /api/capabilities HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await?;
+        let mut status_line = [0u8; 12];
+        stream.read_exact(&mut status_line).await?;
+        Ok::<_, std::io::Error>(status_line.starts_with(b"HTTP/1.1 200"))
+    };
+
+    match result.await {
+        Ok(true) => true,
+        Ok(false) => {
+            tracing::warn!("Watchdog probe to {} returned a non-success status", addr);
+            false
+        }
+        Err(e) => {
+            tracing::warn!("Watchdog probe to {} failed: {}", addr, e);
+            false
+        }
+    }
+}
+
+/// 実行ファイルを Type=notify のサービスとして起動するユニットファイル
+///
+/// データベースが同じホストにある場合は、その起動後に開始します。
+///
+/// # 引数
+/// * `executable` - 実行ファイルのパス（通常は現在の実行ファイル）
+pub fn unit_file(executable: &Path) -> UnitFile {
+    let mut unit = UnitFile::for_executable("PC Inventory Server", executable);
+    unit.after.extend(["mysql.service".to_string(), "mariadb.service".to_string()]);
+    unit.watchdog_timeout = WATCHDOG_TIMEOUT;
+    unit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probe_addr() {
+        assert_eq!(probe_addr("0.0.0.0:8080".parse().unwrap()), "127.0.0.1:8080".parse().unwrap());
+        assert_eq!(probe_addr("[::]:8080".parse().unwrap()), "[::1]:8080".parse().unwrap());
+        assert_eq!(probe_addr("192.168.1.10:8080".parse().unwrap()), "192.168.1.10:8080".parse().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_probe() {
+        use axum::{routing::get, Router};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = Router::new().route("/api/capabilities", get(|| async { "ok" }));
+        let server = tokio::spawn(async move { axum::serve(listener, app).await });
+        assert!(probe(addr).await);
+
+        // 待ち受けを停止した後は応答しない
+        server.abort();
+        let _ = server.await;
+        assert!(!probe(addr).await);
+    }
+
+    #[test]
+    fn test_unit_file() {
+        let unit = unit_file(Path::new("/opt/pc-inventory/pc-inventory-server")).render();
+
+        assert!(unit.contains("After=network-online.target mysql.service mariadb.service\n"));
+        assert!(unit.contains("Type=notify\n"));
+        assert!(unit.contains("ExecStart=/opt/pc-inventory/pc-inventory-server\n"));
+        assert!(unit.contains("WorkingDirectory=/opt/pc-inventory\n"));
+        assert!(unit.contains("WatchdogSec=30\n"));
+    }
+}
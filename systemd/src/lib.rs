@@ -0,0 +1,325 @@
+//! systemd との連携（サーバー・クライアント共通、Linux）
+//!
+//! Type=notify のユニットとして起動された場合に、起動完了・停止開始・ウォッチドッグを systemd に通知します。
+//! ウォッチドッグは、各実行ファイルが処理の進行（クライアントはスケジューラの状態遷移、サーバーは応答）を
+//! 確認できた場合のみ通知します。
+//! systemd 以外から起動された場合（NOTIFY_SOCKET が無い場合）や Linux 以外では、通知は何もしません。
+//!
+//! ユニット名・ウォッチドッグのタイムアウトなど、実行ファイルごとの設定は各クレートの `systemd` モジュールにあります。
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 異常終了の後、再起動するまでの待機時間（既定値）
+pub const DEFAULT_RESTART_DELAY: Duration = Duration::from_secs(10);
+
+/// ユニットファイルに設定するウォッチドッグのタイムアウト（既定値）
+pub const DEFAULT_WATCHDOG_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// systemd への通知
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Notification {
+    /// 起動完了
+    Ready,
+    /// 停止開始
+    Stopping,
+    /// ウォッチドッグ
+    Watchdog,
+}
+
+/// systemd に通知（systemd から起動されていない場合は何もしない）
+pub fn notify(notification: Notification) {
+    if let Err(e) = platform::notify(notification) {
+        tracing::warn!("Failed to notify systemd ({:?}): {}", notification, e);
+    }
+}
+
+/// ウォッチドッグの通知間隔
+///
+/// # 戻り値
+/// ユニットの WatchdogSec の半分（ウォッチドッグが無効の場合は None）
+pub fn watchdog_interval() -> Option<Duration> {
+    platform::watchdog_usec().and_then(ping_interval)
+}
+
+/// WATCHDOG_USEC から通知間隔を決定（タイムアウトの半分）
+fn ping_interval(watchdog_usec: u64) -> Option<Duration> {
+    (watchdog_usec > 0).then(|| Duration::from_micros(watchdog_usec / 2))
+}
+
+/// ウォッチドッグが有効な場合、通知するタスクを開始
+///
+/// 通知の前に `check` で処理が進んでいるかを確認し、確認できた場合のみ通知します。
+/// 確認できない状態（処理の停止・応答なし）が続くと通知が途切れ、systemd が再起動します。
+///
+/// # 引数
+/// * `check` - 処理が進んでいる場合に true を返す確認（通知間隔を過ぎても終わらない場合は false とみなす）
+pub fn spawn_watchdog<F, Fut>(check: F)
+where
+    F: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = bool> + Send,
+{
+    let Some(interval) = watchdog_interval() else {
+        return;
+    };
+    tracing::info!("systemd watchdog enabled (interval: {}ms)", interval.as_millis());
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match tokio::time::timeout(interval, check()).await {
+                Ok(true) => notify(Notification::Watchdog),
+                Ok(false) => tracing::warn!("No progress since the last watchdog check, skipping notification"),
+                Err(_) => tracing::warn!("Watchdog check timed out, skipping notification"),
+            }
+        }
+    });
+}
+
+/// 処理の進行状況（ウォッチドッグの確認に使用）
+///
+/// 処理を進める側が、次に進むまでの期限を `expect_within` で設定します。
+/// 期限を過ぎても再び設定されない場合は停止しているとみなします。
+/// clone したインスタンスは期限を共有します。
+#[derive(Debug, Clone)]
+pub struct Progress {
+    start: Instant,
+    /// 期限（start からの経過ミリ秒、u64::MAX の場合は期限なし）
+    deadline_ms: Arc<AtomicU64>,
+}
+
+impl Progress {
+    /// 期限なしの状態で作成
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            deadline_ms: Arc::new(AtomicU64::new(u64::MAX)),
+        }
+    }
+
+    /// 現在から `duration` 以内に再び進むことを設定
+    pub fn expect_within(&self, duration: Duration) {
+        let deadline = self.elapsed_ms().saturating_add(duration.as_millis().try_into().unwrap_or(u64::MAX));
+        self.deadline_ms.store(deadline, Ordering::Relaxed);
+    }
+
+    /// 期限を過ぎているか
+    pub fn is_stalled(&self) -> bool {
+        self.elapsed_ms() > self.deadline_ms.load(Ordering::Relaxed)
+    }
+
+    fn elapsed_ms(&self) -> u64 {
+        self.start.elapsed().as_millis().try_into().unwrap_or(u64::MAX)
+    }
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 標準出力・標準エラーが journald に接続されているか（systemd のサービスとして起動された場合）
+pub fn is_journal_stream() -> bool {
+    std::env::var_os("JOURNAL_STREAM").is_some_and(|value| !value.is_empty())
+}
+
+/// journald へのログ出力（systemd のサービスとして起動された場合のみ）
+///
+/// journald に接続できない場合は警告を表示して None を返します（コンソールに出力する）。
+#[cfg(target_os = "linux")]
+pub fn journald_layer() -> Option<tracing_journald::Layer> {
+    if !is_journal_stream() {
+        return None;
+    }
+    match tracing_journald::layer() {
+        Ok(layer) => Some(layer),
+        Err(e) => {
+            eprintln!("Warning: journald unavailable, logging to console: {}", e);
+            None
+        }
+    }
+}
+
+/// journald へのログ出力（Linux 以外では常に None）
+#[cfg(not(target_os = "linux"))]
+pub fn journald_layer() -> Option<tracing_subscriber::layer::Identity> {
+    None
+}
+
+/// Ctrl+C または SIGTERM（systemctl stop）を受信するまで待機
+pub async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = terminate.recv() => {}
+                }
+                return;
+            }
+            Err(e) => tracing::warn!("Failed to listen for SIGTERM: {}", e),
+        }
+    }
+
+    if tokio::signal::ctrl_c().await.is_err() {
+        // シグナルを受信できない場合は停止要求を待たない
+        std::future::pending::<()>().await;
+    }
+}
+
+/// systemd のユニットファイル
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitFile {
+    pub description: String,
+    /// 実行ファイルのパス
+    pub executable: PathBuf,
+    /// 作業ディレクトリ（config.toml・ログなどの相対パスの基準）
+    pub working_directory: PathBuf,
+    /// このユニットより先に起動するユニット（After=）
+    pub after: Vec<String>,
+    /// 異常終了・再起動要求の後、再起動するまでの待機時間
+    pub restart_delay: Duration,
+    /// ウォッチドッグのタイムアウト
+    pub watchdog_timeout: Duration,
+}
+
+impl UnitFile {
+    /// 実行ファイルを Type=notify のサービスとして起動するユニットファイルを作成
+    ///
+    /// 作業ディレクトリは実行ファイルのディレクトリとし、ネットワークの起動後に開始します。
+    ///
+    /// # 引数
+    /// * `description` - ユニットの説明
+    /// * `executable` - 実行ファイルのパス（通常は現在の実行ファイル）
+    pub fn for_executable(description: impl Into<String>, executable: impl Into<PathBuf>) -> Self {
+        let executable = executable.into();
+        let working_directory = executable.parent().map(Path::to_path_buf).unwrap_or_else(|| PathBuf::from("/"));
+        Self {
+            description: description.into(),
+            executable,
+            working_directory,
+            after: vec!["network-online.target".to_string()],
+            restart_delay: DEFAULT_RESTART_DELAY,
+            watchdog_timeout: DEFAULT_WATCHDOG_TIMEOUT,
+        }
+    }
+
+    /// ユニットファイルの内容
+    pub fn render(&self) -> String {
+        format!(
+            "[Unit]\n\
+             Description={}\n\
+             Wants=network-online.target\n\
+             After={}\n\
+             \n\
+             [Service]\n\
+             Type=notify\n\
+             ExecStart={}\n\
+             WorkingDirectory={}\n\
+             Restart=on-failure\n\
+             RestartSec={}\n\
+             WatchdogSec={}\n\
+             \n\
+             [Install]\n\
+             WantedBy=multi-user.target\n",
+            self.description,
+            self.after.join(" "),
+            exec_path(&self.executable),
+            self.working_directory.display(),
+            self.restart_delay.as_secs(),
+            self.watchdog_timeout.as_secs(),
+        )
+    }
+}
+
+/// ExecStart に記載する実行ファイルのパス（空白を含む場合は引用符で囲む）
+fn exec_path(path: &Path) -> String {
+    let path = path.display().to_string();
+    if path.contains(char::is_whitespace) {
+        format!("\"{}\"", path.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        path
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::Notification;
+    use sd_notify::NotifyState;
+
+    pub(super) fn notify(notification: Notification) -> std::io::Result<()> {
+        let state = match notification {
+            Notification::Ready => NotifyState::Ready,
+            Notification::Stopping => NotifyState::Stopping,
+            Notification::Watchdog => NotifyState::Watchdog,
+        };
+        sd_notify::notify(false, &[state])
+    }
+
+    pub(super) fn watchdog_usec() -> Option<u64> {
+        let mut usec = 0;
+        sd_notify::watchdog_enabled(false, &mut usec).then_some(usec)
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod platform {
+    use super::Notification;
+
+    pub(super) fn notify(_notification: Notification) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    pub(super) fn watchdog_usec() -> Option<u64> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ping_interval() {
+        assert_eq!(ping_interval(60_000_000), Some(Duration::from_secs(30)));
+        assert_eq!(ping_interval(0), None);
+    }
+
+    #[test]
+    fn test_progress_stalls_after_deadline() {
+        let progress = Progress::new();
+        assert!(!progress.is_stalled());
+
+        progress.expect_within(Duration::from_secs(60));
+        assert!(!progress.is_stalled());
+
+        // clone したインスタンスは期限を共有する
+        progress.clone().expect_within(Duration::ZERO);
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(progress.is_stalled());
+    }
+
+    #[test]
+    fn test_unit_file_render() {
+        let unit = UnitFile::for_executable("PC Inventory Client", "/opt/pc-inventory/pc-inventory-client").render();
+
+        assert!(unit.contains("Description=PC Inventory Client\n"));
+        assert!(unit.contains("After=network-online.target\n"));
+        assert!(unit.contains("Type=notify\n"));
+        assert!(unit.contains("ExecStart=/opt/pc-inventory/pc-inventory-client\n"));
+        assert!(unit.contains("WorkingDirectory=/opt/pc-inventory\n"));
+        assert!(unit.contains("Restart=on-failure\nRestartSec=10\n"));
+        assert!(unit.contains("WatchdogSec=60\n"));
+        assert!(unit.ends_with("WantedBy=multi-user.target\n"));
+
+        let unit = UnitFile::for_executable("PC Inventory Client", "/opt/pc inventory/pc-inventory-client").render();
+        assert!(unit.contains("ExecStart=\"/opt/pc inventory/pc-inventory-client\"\n"));
+        assert!(unit.contains("WorkingDirectory=/opt/pc inventory\n"));
+    }
+}